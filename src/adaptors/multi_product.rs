@@ -4,7 +4,8 @@ use Option::{self as CurrentItems, None as NotYetPopulated, Some as Populated};
 
 use alloc::vec::Vec;
 
-use crate::size_hint;
+use crate::combinations::split_evenly;
+use crate::size_hint::{self, SizeHint};
 
 #[derive(Clone)]
 /// An iterator adaptor that iterates over the cartesian product of
@@ -31,6 +32,17 @@ where
     iters: Vec<MultiProductIter<I>>,
     /// Not populated at the beginning then it holds the current item of each iterator.
     cur: CurrentItems<Vec<I::Item>>,
+    /// The last item generated from the back, if any.
+    back: Option<Back>,
+}
+
+/// The last item generated from the back of a `MultiProduct`.
+#[derive(Clone, Debug)]
+struct Back {
+    /// Its position in each iterator.
+    positions: Vec<usize>,
+    /// The length of each iterator, which is known once items are generated from the back.
+    lens: Vec<usize>,
 }
 
 impl<I> std::fmt::Debug for MultiProduct<I>
@@ -46,7 +58,7 @@ where
     I: Iterator + Clone + std::fmt::Debug,
     I::Item: Clone + std::fmt::Debug,
 {
-    debug_fmt_fields!(MultiProductInner, iters, cur);
+    debug_fmt_fields!(MultiProductInner, iters, cur, back);
 }
 
 /// Create a new cartesian product iterator over an arbitrary number
@@ -65,10 +77,304 @@ where
             .map(|i| MultiProductIter::new(i.into_iter()))
             .collect(),
         cur: NotYetPopulated,
+        back: None,
     };
     MultiProduct(ProductInProgress(inner))
 }
 
+/// Returns the exact value of a size hint, if known.
+fn exact(sh: SizeHint) -> Option<usize> {
+    match sh {
+        (low, Some(upp)) if low == upp => Some(low),
+        _ => None,
+    }
+}
+
+impl<I> MultiProductInner<I>
+where
+    I: Iterator + Clone,
+    I::Item: Clone,
+{
+    /// Returns the length of each iterator, if their size hints are exact.
+    fn lens(&self) -> Option<Vec<usize>> {
+        self.iters
+            .iter()
+            .map(|iter| exact(iter.iter_orig.size_hint()))
+            .collect()
+    }
+
+    /// Returns the position of the current item in each iterator, given their lengths.
+    fn positions(&self, lens: &[usize]) -> Option<Vec<usize>> {
+        self.iters
+            .iter()
+            .zip(lens)
+            .map(|(iter, len)| len.checked_sub(exact(iter.iter.size_hint())? + 1))
+            .collect()
+    }
+
+    /// Returns true if the current item is not before the last item generated from the back.
+    fn reached_back(&self) -> bool {
+        let back = match &self.back {
+            Some(back) => back,
+            None => return false,
+        };
+        // Compare the positions lexicographically, without collecting them.
+        for ((iter, &len), &back_pos) in self.iters.iter().zip(&back.lens).zip(&back.positions) {
+            // The size hints are exact when items are generated from the back.
+            let pos = match exact(iter.iter.size_hint()).and_then(|rest| len.checked_sub(rest + 1))
+            {
+                Some(pos) => pos,
+                None => return true,
+            };
+            if pos != back_pos {
+                return pos > back_pos;
+            }
+        }
+        true
+    }
+
+    /// Returns the count of items between the current one and the last item generated from
+    /// the back, or None if it would overflow.
+    fn count_to_back(&self, back: &Back) -> Option<usize> {
+        let front = match self.cur {
+            NotYetPopulated => None,
+            Populated(_) => Some(self.positions(&back.lens)?),
+        };
+        // Subtract `front` from `back` as numbers written in the mixed radix `lens`.
+        let mut diff = alloc::vec![0; back.lens.len()];
+        let mut borrow = 0;
+        for (i, &len) in back.lens.iter().enumerate().rev() {
+            let pos = back.positions[i];
+            let sub = front.as_ref().map_or(0, |front| front[i]) + borrow;
+            borrow = usize::from(pos < sub);
+            diff[i] = if pos < sub {
+                len - sub + pos
+            } else {
+                pos - sub
+            };
+        }
+        if borrow > 0 {
+            return Some(0);
+        }
+        let diff = diff
+            .iter()
+            .zip(&back.lens)
+            .try_fold(0usize, |acc, (&digit, &len)| {
+                acc.checked_mul(len)?.checked_add(digit)
+            })?;
+        // Exclude the current item itself.
+        Some(diff.saturating_sub(usize::from(front.is_some())))
+    }
+
+    /// Moves every iterator to the given positions and returns the item there.
+    fn seek(&mut self, positions: &[usize]) -> Vec<I::Item> {
+        let values: Vec<_> = self
+            .iters
+            .iter_mut()
+            .zip(positions)
+            .map(|(iter, &pos)| {
+                iter.iter = iter.iter_orig.clone();
+                iter.iter.nth(pos).expect("size hint should be exact")
+            })
+            .collect();
+        self.cur = Populated(values.clone());
+        values
+    }
+}
+
+impl<I> MultiProduct<I>
+where
+    I: Iterator + Clone,
+    I::Item: Clone,
+{
+    /// Generates the item before the last one generated from the back, knowing that the size
+    /// hints of the iterators are exact.
+    fn next_back_exact(&mut self) -> Option<Vec<I::Item>> {
+        let inner = self.0.as_mut()?;
+        let lens = match &inner.back {
+            Some(back) => back.lens.clone(),
+            None => inner.lens()?,
+        };
+        if lens.contains(&0) {
+            self.0 = ProductEnded;
+            return None;
+        }
+        // The positions of the current item, once populated.
+        let front = inner.cur.as_ref().map(|_| inner.positions(&lens));
+        let back = match &mut inner.back {
+            Some(Back {
+                positions: back, ..
+            }) => {
+                // Decrement `back` as a number written in the mixed radix `lens`.
+                let decremented = back.iter_mut().zip(&lens).rev().any(|(pos, len)| {
+                    if *pos > 0 {
+                        *pos -= 1;
+                        true
+                    } else {
+                        *pos = len - 1;
+                        false
+                    }
+                });
+                if !decremented {
+                    self.0 = ProductEnded;
+                    return None;
+                }
+                back
+            }
+            None => {
+                let positions = lens.iter().map(|len| len - 1).collect();
+                &mut inner.back.insert(Back { positions, lens }).positions
+            }
+        };
+        let reached = front.map_or(false, |front| front.map_or(true, |front| &front >= back));
+        if reached {
+            self.0 = ProductEnded;
+            return None;
+        }
+        let item: Option<Vec<_>> = back
+            .iter()
+            .zip(&inner.iters)
+            .map(|(&pos, iter)| iter.iter_orig.clone().nth(pos))
+            .collect();
+        if item.is_none() {
+            self.0 = ProductEnded;
+        }
+        item
+    }
+
+    /// Returns the positions in each iterator of the `n`-th next item, if the lengths of the
+    /// iterators are known from their size hints. The outer `None` means they are unknown.
+    fn nth_positions(&self, n: usize) -> Option<Option<Vec<usize>>> {
+        let inner = self.0.as_ref()?;
+        let lens = inner.lens()?;
+        if inner.iters.is_empty() {
+            return None;
+        }
+        if lens.contains(&0) {
+            return Some(None);
+        }
+        let (mut positions, mut carry) = match inner.cur {
+            NotYetPopulated => (alloc::vec![0; lens.len()], n as u128),
+            Populated(_) => (inner.positions(&lens)?, n as u128 + 1),
+        };
+        // Add `carry` as a number written in the mixed radix `lens`.
+        for (pos, &len) in positions.iter_mut().zip(&lens).rev() {
+            if carry == 0 {
+                break;
+            }
+            let sum = *pos as u128 + carry;
+            *pos = (sum % len as u128) as usize;
+            carry = sum / len as u128;
+        }
+        let reached = inner
+            .back
+            .as_ref()
+            .map_or(false, |back| positions >= back.positions);
+        if carry > 0 || reached {
+            Some(None)
+        } else {
+            Some(Some(positions))
+        }
+    }
+}
+
+impl<I> MultiProduct<I>
+where
+    I: ExactSizeIterator + Clone,
+    I::Item: Clone,
+{
+    /// Returns the rank of an item in this product given the position of each of its
+    /// elements in their respective iterator, that is the count of items before it when
+    /// iterating from the start.
+    ///
+    /// Returns `None` if the positions do not match the iterators, if the rank would
+    /// overflow, or once this iterator is exhausted.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// let mut it = vec![0..2, 0..3, 0..4].into_iter().multi_cartesian_product();
+    /// let rank = it.rank_of(&[1, 0, 2]).unwrap();
+    /// assert_eq!(rank, 14);
+    /// assert_eq!(it.nth(rank), Some(vec![1, 0, 2]));
+    /// assert_eq!(it.rank_of(&[1, 3, 0]), None);
+    /// ```
+    pub fn rank_of(&self, positions: &[usize]) -> Option<usize> {
+        let inner = self.0.as_ref()?;
+        if positions.len() != inner.iters.len() {
+            return None;
+        }
+        inner
+            .iters
+            .iter()
+            .zip(positions)
+            .try_fold(0usize, |rank, (iter, &pos)| {
+                let len = iter.iter_orig.len();
+                if pos >= len {
+                    return None;
+                }
+                rank.checked_mul(len)?.checked_add(pos)
+            })
+    }
+
+    /// Splits the remaining items into the first `rank` ones and the others.
+    ///
+    /// The two iterators generate disjoint contiguous ranges of items which together cover
+    /// the ones this iterator would have generated, so that they can be consumed
+    /// independently (on different threads for example). The first one is shorter than
+    /// `rank` if there are not enough remaining items.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// let (head, tail) = vec![0..2, 0..2].into_iter().multi_cartesian_product().split_at(3);
+    /// itertools::assert_equal(head, vec![vec![0, 0], vec![0, 1], vec![1, 0]]);
+    /// itertools::assert_equal(tail, vec![vec![1, 1]]);
+    /// ```
+    pub fn split_at(mut self, rank: usize) -> (Self, Self) {
+        let mut tail = self.clone();
+        if rank > 0 && tail.nth(rank - 1).is_none() {
+            return (self, tail);
+        }
+        // The head stops right before the first item of the tail.
+        let next = match tail.nth_positions(0) {
+            Some(positions) => positions,
+            // The product of no iterators has a single item, made of no positions.
+            None => tail.0.as_ref().map(|_| Vec::new()),
+        };
+        if let (Some(inner), Some(positions)) = (self.0.as_mut(), next) {
+            let lens = inner
+                .iters
+                .iter()
+                .map(|iter| iter.iter_orig.len())
+                .collect();
+            inner.back = Some(Back { positions, lens });
+        }
+        (self, tail)
+    }
+
+    /// Splits the remaining items into `parts` contiguous ranges whose lengths differ by at
+    /// most one, see [`split_at`](Self::split_at).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `parts` is zero or if the count of remaining items overflows a `usize`.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// let product = vec![0..3, 0..3].into_iter().multi_cartesian_product();
+    /// let parts = product.clone().split_into(2);
+    /// assert_eq!(parts[0].clone().count(), 5);
+    /// assert_eq!(parts[1].clone().count(), 4);
+    /// itertools::assert_equal(parts.into_iter().flatten(), product);
+    /// ```
+    pub fn split_into(self, parts: usize) -> Vec<Self> {
+        let len = self.size_hint().1.expect("too many items");
+        split_evenly(self, len, parts, Self::split_at)
+    }
+}
+
 #[derive(Clone, Debug)]
 /// Holds the state of a single iterator within a `MultiProduct`.
 struct MultiProductIter<I>
@@ -108,22 +414,33 @@ where
                 debug_assert!(!inner.iters.is_empty());
                 // Find (from the right) a non-finished iterator and
                 // reset the finished ones encountered.
+                let mut advanced = false;
                 for (iter, item) in inner.iters.iter_mut().zip(values.iter_mut()).rev() {
                     if let Some(new) = iter.iter.next() {
                         *item = new;
-                        return Some(values.clone());
+                        advanced = true;
+                        break;
                     } else {
                         iter.iter = iter.iter_orig.clone();
                         // `cur` is populated so the untouched `iter_orig` can not be empty.
                         *item = iter.iter.next().unwrap();
                     }
                 }
+                let values = values.clone();
+                if advanced && !inner.reached_back() {
+                    return Some(values);
+                }
                 self.0 = ProductEnded;
                 None
             }
             // Only the first time.
             NotYetPopulated => {
                 let next: Option<Vec<_>> = inner.iters.iter_mut().map(|i| i.iter.next()).collect();
+                if next.is_some() && inner.reached_back() {
+                    // The only remaining items were generated from the back.
+                    self.0 = ProductEnded;
+                    return None;
+                }
                 if next.is_none() || inner.iters.is_empty() {
                     // This cartesian product had at most one item to generate and now ends.
                     self.0 = ProductEnded;
@@ -135,7 +452,37 @@ where
         }
     }
 
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        match self.nth_positions(n) {
+            Some(Some(positions)) => {
+                let inner = self.0.as_mut()?;
+                Some(inner.seek(&positions))
+            }
+            Some(None) => {
+                self.0 = ProductEnded;
+                None
+            }
+            None => {
+                for _ in 0..n {
+                    self.next()?;
+                }
+                self.next()
+            }
+        }
+    }
+
     fn count(self) -> usize {
+        if let Some(
+            inner @ MultiProductInner {
+                back: Some(back), ..
+            },
+        ) = &self.0
+        {
+            // The remaining items may be counted even if the whole product is too long.
+            return inner
+                .count_to_back(back)
+                .expect("the count of items overflows a usize");
+        }
         match self.0 {
             ProductEnded => 0,
             // The iterator is fresh so the count is the product of the length of each iterator:
@@ -144,6 +491,7 @@ where
             ProductInProgress(MultiProductInner {
                 iters,
                 cur: NotYetPopulated,
+                ..
             }) => iters
                 .into_iter()
                 .map(|iter| iter.iter_orig.count())
@@ -159,6 +507,7 @@ where
             ProductInProgress(MultiProductInner {
                 iters,
                 cur: Populated(_),
+                ..
             }) => iters.into_iter().fold(0, |mut acc, iter| {
                 if acc != 0 {
                     acc *= iter.iter_orig.count();
@@ -169,11 +518,21 @@ where
     }
 
     fn size_hint(&self) -> (usize, Option<usize>) {
+        if let Some(
+            inner @ MultiProductInner {
+                back: Some(back), ..
+            },
+        ) = &self.0
+        {
+            let count = inner.count_to_back(back);
+            return (count.unwrap_or(usize::MAX), count);
+        }
         match &self.0 {
             ProductEnded => (0, Some(0)),
             ProductInProgress(MultiProductInner {
                 iters,
                 cur: NotYetPopulated,
+                ..
             }) => iters
                 .iter()
                 .map(|iter| iter.iter_orig.size_hint())
@@ -181,6 +540,7 @@ where
             ProductInProgress(MultiProductInner {
                 iters,
                 cur: Populated(_),
+                ..
             }) => {
                 if let [first, tail @ ..] = &iters[..] {
                     tail.iter().fold(first.iter.size_hint(), |mut sh, iter| {
@@ -195,8 +555,12 @@ where
         }
     }
 
-    fn last(self) -> Option<Self::Item> {
-        let MultiProductInner { iters, cur } = self.0?;
+    fn last(mut self) -> Option<Self::Item> {
+        if self.0.as_ref()?.back.is_some() {
+            // The last remaining item is the one before the last generated from the back.
+            return self.next_back_exact();
+        }
+        let MultiProductInner { iters, cur, .. } = self.0?;
         // Collect the last item of each iterator of the product.
         if let Populated(values) = cur {
             let mut count = iters.len();
@@ -229,3 +593,13 @@ where
     I::Item: Clone,
 {
 }
+
+impl<I> DoubleEndedIterator for MultiProduct<I>
+where
+    I: ExactSizeIterator + Clone,
+    I::Item: Clone,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.next_back_exact()
+    }
+}
@@ -0,0 +1,820 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::iter::{Fuse, FusedIterator};
+use std::marker::PhantomData;
+use std::ops::{Add, Mul};
+
+use crate::size_hint::SizeHint;
+use crate::MinMaxResult;
+
+/// Creates a new `AggregateRuns` from `iter`
+pub fn new<K, I, F>(iter: I, key: F) -> AggregateRuns<I, F>
+where
+    I: Iterator,
+    F: FnMut(&I::Item) -> K,
+    K: PartialEq,
+{
+    AggregateRuns { iter, key }
+}
+
+/// `AggregateRuns` is an intermediate struct for efficient group-and-fold operations
+/// over runs of consecutive elements with the same key.
+///
+/// Each operation returns a [`RunAggregates`] iterator adaptor, which yields the key
+/// and the result of folding the elements of each run as soon as the run ends.
+/// Only the accumulator of the current run is kept in memory.
+///
+/// See [`.aggregate_runs()`](crate::Itertools::aggregate_runs) for more information.
+#[must_use = "AggregateRuns is lazy and do nothing unless consumed"]
+pub struct AggregateRuns<I, F> {
+    iter: I,
+    key: F,
+}
+
+impl<I, F> Clone for AggregateRuns<I, F>
+where
+    I: Clone,
+    F: Clone,
+{
+    clone_fields!(iter, key);
+}
+
+impl<I, F> fmt::Debug for AggregateRuns<I, F>
+where
+    I: fmt::Debug,
+{
+    debug_fmt_fields!(AggregateRuns, iter);
+}
+
+/// The operation applied to the elements of each run by [`RunAggregates`].
+///
+/// It is implemented by the types of the operations of [`AggregateRuns`].
+pub trait RunAggregator<K, V> {
+    /// The result of the operation for a run.
+    type Out;
+    /// Starts the accumulator of a new run with its first element.
+    fn first(&mut self, key: &K, val: V) -> Self::Out;
+    /// Adds an element to the accumulator of the current run.
+    fn next(&mut self, acc: Self::Out, key: &K, val: V) -> Self::Out;
+}
+
+/// A comparison of two elements of a run, used by the extrema operations of [`AggregateRuns`].
+///
+/// It is implemented by `FnMut(&K, &V, &V) -> Ordering` closures, [`RunByOrd`] and [`RunByKey`].
+pub trait RunCmp<K, V> {
+    /// Compares `a` and `b`, two elements of the run of `key`.
+    fn cmp_in_run(&mut self, key: &K, a: &V, b: &V) -> Ordering;
+}
+
+impl<K, V, F> RunCmp<K, V> for F
+where
+    F: FnMut(&K, &V, &V) -> Ordering,
+{
+    fn cmp_in_run(&mut self, key: &K, a: &V, b: &V) -> Ordering {
+        self(key, a, b)
+    }
+}
+
+/// Compares the elements themselves.
+#[derive(Clone, Copy, Debug)]
+pub struct RunByOrd;
+
+impl<K, V: Ord> RunCmp<K, V> for RunByOrd {
+    fn cmp_in_run(&mut self, _: &K, a: &V, b: &V) -> Ordering {
+        a.cmp(b)
+    }
+}
+
+/// Compares the keys given by a function of the elements.
+#[derive(Clone)]
+pub struct RunByKey<F>(F);
+
+impl<F> fmt::Debug for RunByKey<F> {
+    debug_fmt_fields!(RunByKey,);
+}
+
+impl<K, V, CK, F> RunCmp<K, V> for RunByKey<F>
+where
+    CK: Ord,
+    F: FnMut(&K, &V) -> CK,
+{
+    fn cmp_in_run(&mut self, key: &K, a: &V, b: &V) -> Ordering {
+        (self.0)(key, a).cmp(&(self.0)(key, b))
+    }
+}
+
+/// The aggregator of [`AggregateRuns::aggregate`].
+#[derive(Clone, Debug)]
+pub struct RunAggregate<R, FO>(FO, PhantomData<R>);
+
+impl<K, V, R, FO> RunAggregator<K, V> for RunAggregate<R, FO>
+where
+    FO: FnMut(Option<R>, &K, V) -> Option<R>,
+{
+    type Out = Option<R>;
+    fn first(&mut self, key: &K, val: V) -> Option<R> {
+        (self.0)(None, key, val)
+    }
+    fn next(&mut self, acc: Option<R>, key: &K, val: V) -> Option<R> {
+        (self.0)(acc, key, val)
+    }
+}
+
+/// The aggregator of [`AggregateRuns::fold_with`].
+#[derive(Clone, Debug)]
+pub struct RunFoldWith<FI, FO> {
+    init: FI,
+    operation: FO,
+}
+
+impl<K, V, R, FI, FO> RunAggregator<K, V> for RunFoldWith<FI, FO>
+where
+    FI: FnMut(&K, &V) -> R,
+    FO: FnMut(R, &K, V) -> R,
+{
+    type Out = R;
+    fn first(&mut self, key: &K, val: V) -> R {
+        let acc = (self.init)(key, &val);
+        (self.operation)(acc, key, val)
+    }
+    fn next(&mut self, acc: R, key: &K, val: V) -> R {
+        (self.operation)(acc, key, val)
+    }
+}
+
+/// The aggregator of [`AggregateRuns::fold`].
+#[derive(Clone, Debug)]
+pub struct RunFold<R, FO> {
+    init: R,
+    operation: FO,
+}
+
+impl<K, V, R, FO> RunAggregator<K, V> for RunFold<R, FO>
+where
+    R: Clone,
+    FO: FnMut(R, &K, V) -> R,
+{
+    type Out = R;
+    fn first(&mut self, key: &K, val: V) -> R {
+        (self.operation)(self.init.clone(), key, val)
+    }
+    fn next(&mut self, acc: R, key: &K, val: V) -> R {
+        (self.operation)(acc, key, val)
+    }
+}
+
+/// The aggregator of [`AggregateRuns::reduce`].
+#[derive(Clone, Debug)]
+pub struct RunReduce<FO>(FO);
+
+impl<K, V, FO> RunAggregator<K, V> for RunReduce<FO>
+where
+    FO: FnMut(V, &K, V) -> V,
+{
+    type Out = V;
+    fn first(&mut self, _: &K, val: V) -> V {
+        val
+    }
+    fn next(&mut self, acc: V, key: &K, val: V) -> V {
+        (self.0)(acc, key, val)
+    }
+}
+
+/// The aggregator of [`AggregateRuns::collect`].
+#[derive(Clone, Debug)]
+pub struct RunCollect<C>(PhantomData<C>);
+
+impl<K, V, C> RunAggregator<K, V> for RunCollect<C>
+where
+    C: Default + Extend<V>,
+{
+    type Out = C;
+    fn first(&mut self, key: &K, val: V) -> C {
+        self.next(C::default(), key, val)
+    }
+    fn next(&mut self, mut acc: C, _: &K, val: V) -> C {
+        acc.extend(Some(val));
+        acc
+    }
+}
+
+/// The aggregator of [`AggregateRuns::max`] and its variants.
+#[derive(Clone, Debug)]
+pub struct RunMaxBy<C>(C);
+
+impl<K, V, C> RunAggregator<K, V> for RunMaxBy<C>
+where
+    C: RunCmp<K, V>,
+{
+    type Out = V;
+    fn first(&mut self, _: &K, val: V) -> V {
+        val
+    }
+    fn next(&mut self, acc: V, key: &K, val: V) -> V {
+        match self.0.cmp_in_run(key, &acc, &val) {
+            Ordering::Less | Ordering::Equal => val,
+            Ordering::Greater => acc,
+        }
+    }
+}
+
+/// The aggregator of [`AggregateRuns::min`] and its variants.
+#[derive(Clone, Debug)]
+pub struct RunMinBy<C>(C);
+
+impl<K, V, C> RunAggregator<K, V> for RunMinBy<C>
+where
+    C: RunCmp<K, V>,
+{
+    type Out = V;
+    fn first(&mut self, _: &K, val: V) -> V {
+        val
+    }
+    fn next(&mut self, acc: V, key: &K, val: V) -> V {
+        match self.0.cmp_in_run(key, &acc, &val) {
+            Ordering::Less | Ordering::Equal => acc,
+            Ordering::Greater => val,
+        }
+    }
+}
+
+/// The aggregator of [`AggregateRuns::minmax`] and its variants.
+#[derive(Clone, Debug)]
+pub struct RunMinMaxBy<C>(C);
+
+impl<K, V, C> RunAggregator<K, V> for RunMinMaxBy<C>
+where
+    C: RunCmp<K, V>,
+{
+    type Out = MinMaxResult<V>;
+    fn first(&mut self, _: &K, val: V) -> MinMaxResult<V> {
+        MinMaxResult::OneElement(val)
+    }
+    fn next(&mut self, acc: MinMaxResult<V>, key: &K, val: V) -> MinMaxResult<V> {
+        let compare = &mut self.0;
+        match acc {
+            MinMaxResult::OneElement(e) => {
+                if compare.cmp_in_run(key, &val, &e) == Ordering::Less {
+                    MinMaxResult::MinMax(val, e)
+                } else {
+                    MinMaxResult::MinMax(e, val)
+                }
+            }
+            MinMaxResult::MinMax(min, max) => {
+                if compare.cmp_in_run(key, &val, &min) == Ordering::Less {
+                    MinMaxResult::MinMax(val, max)
+                } else if compare.cmp_in_run(key, &val, &max) != Ordering::Less {
+                    MinMaxResult::MinMax(min, val)
+                } else {
+                    MinMaxResult::MinMax(min, max)
+                }
+            }
+            MinMaxResult::NoElements => unreachable!(),
+        }
+    }
+}
+
+/// The aggregator of [`AggregateRuns::sum`].
+#[derive(Clone, Copy, Debug)]
+pub struct RunSum;
+
+impl<K, V> RunAggregator<K, V> for RunSum
+where
+    V: Add<V, Output = V>,
+{
+    type Out = V;
+    fn first(&mut self, _: &K, val: V) -> V {
+        val
+    }
+    fn next(&mut self, acc: V, _: &K, val: V) -> V {
+        acc + val
+    }
+}
+
+/// The aggregator of [`AggregateRuns::product`].
+#[derive(Clone, Copy, Debug)]
+pub struct RunProduct;
+
+impl<K, V> RunAggregator<K, V> for RunProduct
+where
+    V: Mul<V, Output = V>,
+{
+    type Out = V;
+    fn first(&mut self, _: &K, val: V) -> V {
+        val
+    }
+    fn next(&mut self, acc: V, _: &K, val: V) -> V {
+        acc * val
+    }
+}
+
+/// The aggregator of [`AggregateRuns::count`].
+#[derive(Clone, Copy, Debug)]
+pub struct RunCount;
+
+impl<K, V> RunAggregator<K, V> for RunCount {
+    type Out = usize;
+    fn first(&mut self, _: &K, _: V) -> usize {
+        1
+    }
+    fn next(&mut self, acc: usize, _: &K, _: V) -> usize {
+        acc + 1
+    }
+}
+
+impl<K, I, F> AggregateRuns<I, F>
+where
+    I: Iterator,
+    F: FnMut(&I::Item) -> K,
+    K: PartialEq,
+{
+    fn with<A>(self, aggregator: A) -> RunAggregates<K, I, F, A>
+    where
+        A: RunAggregator<K, I::Item>,
+    {
+        RunAggregates {
+            iter: self.iter.fuse(),
+            key: self.key,
+            aggregator,
+            pending: None,
+        }
+    }
+
+    /// This is the generic way to perform any operation on the runs.
+    ///
+    /// Applies `operation` to the elements of each run sequentially, passing the previously
+    /// accumulated value (`None` for the first element of the run), a reference to the key
+    /// and the current element as arguments.
+    ///
+    /// If `operation` returns `Some(element)` then the accumulator is updated with `element`,
+    /// otherwise the previous accumulation is discarded.
+    ///
+    /// Iterator element type is `(K, Option<R>)`, which is `None` when the last call of
+    /// `operation` of the run discarded the accumulator.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// let runs = vec![1, 3, 4, 0, 6, 5, 0].into_iter()
+    ///     .aggregate_runs(|&n| n % 2)
+    ///     .aggregate(|acc, _key, val| {
+    ///         if val == 0 {
+    ///             None
+    ///         } else {
+    ///             Some(acc.unwrap_or(0) + val)
+    ///         }
+    ///     });
+    ///
+    /// itertools::assert_equal(runs, vec![(1, Some(1 + 3)), (0, Some(6)), (1, Some(5)), (0, None)]);
+    /// ```
+    pub fn aggregate<FO, R>(self, operation: FO) -> RunAggregates<K, I, F, RunAggregate<R, FO>>
+    where
+        FO: FnMut(Option<R>, &K, I::Item) -> Option<R>,
+    {
+        self.with(RunAggregate(operation, PhantomData))
+    }
+
+    /// Folds the elements of each run, starting from an accumulator
+    /// obtained by calling `init` with the first element of the run.
+    ///
+    /// Iterator element type is `(K, R)`.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// let runs = vec![1, 3, 4, 6, 8, 5].into_iter()
+    ///     .aggregate_runs(|&n| n % 2)
+    ///     .fold_with(|&key, _val| key * 100, |acc, _key, val| acc + val);
+    ///
+    /// itertools::assert_equal(runs, vec![(1, 100 + 1 + 3), (0, 4 + 6 + 8), (1, 100 + 5)]);
+    /// ```
+    pub fn fold_with<FI, FO, R>(
+        self,
+        init: FI,
+        operation: FO,
+    ) -> RunAggregates<K, I, F, RunFoldWith<FI, FO>>
+    where
+        FI: FnMut(&K, &I::Item) -> R,
+        FO: FnMut(R, &K, I::Item) -> R,
+    {
+        self.with(RunFoldWith { init, operation })
+    }
+
+    /// Folds the elements of each run, starting from a clone of `init`.
+    ///
+    /// Iterator element type is `(K, R)`.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// let runs = vec![1, 3, 4, 6, 8, 5].into_iter()
+    ///     .aggregate_runs(|&n| n % 2)
+    ///     .fold(0, |acc, _key, val| acc + val);
+    ///
+    /// itertools::assert_equal(runs, vec![(1, 1 + 3), (0, 4 + 6 + 8), (1, 5)]);
+    /// ```
+    pub fn fold<FO, R>(self, init: R, operation: FO) -> RunAggregates<K, I, F, RunFold<R, FO>>
+    where
+        R: Clone,
+        FO: FnMut(R, &K, I::Item) -> R,
+    {
+        self.with(RunFold { init, operation })
+    }
+
+    /// Folds the elements of each run, starting from the first element of the run.
+    ///
+    /// Iterator element type is `(K, I::Item)`.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// let runs = vec![1, 3, 4, 6, 8, 5].into_iter()
+    ///     .aggregate_runs(|&n| n % 2)
+    ///     .reduce(|acc, _key, val| acc * 10 + val);
+    ///
+    /// itertools::assert_equal(runs, vec![(1, 13), (0, 468), (1, 5)]);
+    /// ```
+    pub fn reduce<FO>(self, operation: FO) -> RunAggregates<K, I, F, RunReduce<FO>>
+    where
+        FO: FnMut(I::Item, &K, I::Item) -> I::Item,
+    {
+        self.with(RunReduce(operation))
+    }
+
+    /// Collects the elements of each run in an instance of `C`.
+    /// The iteration order is preserved when inserting elements.
+    ///
+    /// Iterator element type is `(K, C)`.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// let runs = vec![1, 3, 4, 6, 8, 5].into_iter()
+    ///     .aggregate_runs(|&n| n % 2)
+    ///     .collect::<Vec<_>>();
+    ///
+    /// itertools::assert_equal(runs, vec![(1, vec![1, 3]), (0, vec![4, 6, 8]), (1, vec![5])]);
+    /// ```
+    pub fn collect<C>(self) -> RunAggregates<K, I, F, RunCollect<C>>
+    where
+        C: Default + Extend<I::Item>,
+    {
+        self.with(RunCollect(PhantomData))
+    }
+
+    /// Finds the maximum of each run.
+    ///
+    /// If several elements are equally maximum, the last element is picked.
+    ///
+    /// Iterator element type is `(K, I::Item)`.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// let runs = vec![1, 3, 4, 8, 6, 5].into_iter()
+    ///     .aggregate_runs(|&n| n % 2)
+    ///     .max();
+    ///
+    /// itertools::assert_equal(runs, vec![(1, 3), (0, 8), (1, 5)]);
+    /// ```
+    pub fn max(self) -> RunAggregates<K, I, F, RunMaxBy<RunByOrd>>
+    where
+        I::Item: Ord,
+    {
+        self.with(RunMaxBy(RunByOrd))
+    }
+
+    /// Finds the maximum of each run with respect to the specified comparison function.
+    ///
+    /// If several elements are equally maximum, the last element is picked.
+    ///
+    /// Iterator element type is `(K, I::Item)`.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// let runs = vec![1, 3, 4, 8, 6, 5].into_iter()
+    ///     .aggregate_runs(|&n| n % 2)
+    ///     .max_by(|_key, x, y| y.cmp(x));
+    ///
+    /// itertools::assert_equal(runs, vec![(1, 1), (0, 4), (1, 5)]);
+    /// ```
+    pub fn max_by<FC>(self, compare: FC) -> RunAggregates<K, I, F, RunMaxBy<FC>>
+    where
+        FC: FnMut(&K, &I::Item, &I::Item) -> Ordering,
+    {
+        self.with(RunMaxBy(compare))
+    }
+
+    /// Finds the element of each run that gives the maximum from the specified function.
+    ///
+    /// If several elements are equally maximum, the last element is picked.
+    ///
+    /// Iterator element type is `(K, I::Item)`.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// let runs = vec![1, 3, 4, 8, 6, 5].into_iter()
+    ///     .aggregate_runs(|&n| n % 2)
+    ///     .max_by_key(|_key, &val| val % 4);
+    ///
+    /// itertools::assert_equal(runs, vec![(1, 3), (0, 6), (1, 5)]);
+    /// ```
+    pub fn max_by_key<FK, CK>(self, f: FK) -> RunAggregates<K, I, F, RunMaxBy<RunByKey<FK>>>
+    where
+        FK: FnMut(&K, &I::Item) -> CK,
+        CK: Ord,
+    {
+        self.with(RunMaxBy(RunByKey(f)))
+    }
+
+    /// Finds the minimum of each run.
+    ///
+    /// If several elements are equally minimum, the first element is picked.
+    ///
+    /// Iterator element type is `(K, I::Item)`.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// let runs = vec![1, 3, 4, 8, 6, 5].into_iter()
+    ///     .aggregate_runs(|&n| n % 2)
+    ///     .min();
+    ///
+    /// itertools::assert_equal(runs, vec![(1, 1), (0, 4), (1, 5)]);
+    /// ```
+    pub fn min(self) -> RunAggregates<K, I, F, RunMinBy<RunByOrd>>
+    where
+        I::Item: Ord,
+    {
+        self.with(RunMinBy(RunByOrd))
+    }
+
+    /// Finds the minimum of each run with respect to the specified comparison function.
+    ///
+    /// If several elements are equally minimum, the first element is picked.
+    ///
+    /// Iterator element type is `(K, I::Item)`.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// let runs = vec![1, 3, 4, 8, 6, 5].into_iter()
+    ///     .aggregate_runs(|&n| n % 2)
+    ///     .min_by(|_key, x, y| y.cmp(x));
+    ///
+    /// itertools::assert_equal(runs, vec![(1, 3), (0, 8), (1, 5)]);
+    /// ```
+    pub fn min_by<FC>(self, compare: FC) -> RunAggregates<K, I, F, RunMinBy<FC>>
+    where
+        FC: FnMut(&K, &I::Item, &I::Item) -> Ordering,
+    {
+        self.with(RunMinBy(compare))
+    }
+
+    /// Finds the element of each run that gives the minimum from the specified function.
+    ///
+    /// If several elements are equally minimum, the first element is picked.
+    ///
+    /// Iterator element type is `(K, I::Item)`.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// let runs = vec![1, 3, 4, 8, 6, 5].into_iter()
+    ///     .aggregate_runs(|&n| n % 2)
+    ///     .min_by_key(|_key, &val| val % 4);
+    ///
+    /// itertools::assert_equal(runs, vec![(1, 1), (0, 4), (1, 5)]);
+    /// ```
+    pub fn min_by_key<FK, CK>(self, f: FK) -> RunAggregates<K, I, F, RunMinBy<RunByKey<FK>>>
+    where
+        FK: FnMut(&K, &I::Item) -> CK,
+        CK: Ord,
+    {
+        self.with(RunMinBy(RunByKey(f)))
+    }
+
+    /// Finds the minimum and maximum of each run.
+    ///
+    /// If several elements are equally maximum, the last element is picked.
+    /// If several elements are equally minimum, the first element is picked.
+    ///
+    /// Like [`GroupingMap::minmax`](crate::structs::GroupingMap::minmax),
+    /// it never produces a `MinMaxResult::NoElements`.
+    ///
+    /// Iterator element type is `(K, MinMaxResult<I::Item>)`.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    /// use itertools::MinMaxResult::{MinMax, OneElement};
+    ///
+    /// let runs = vec![1, 3, 4, 8, 6, 5].into_iter()
+    ///     .aggregate_runs(|&n| n % 2)
+    ///     .minmax();
+    ///
+    /// itertools::assert_equal(runs, vec![(1, MinMax(1, 3)), (0, MinMax(4, 8)), (1, OneElement(5))]);
+    /// ```
+    pub fn minmax(self) -> RunAggregates<K, I, F, RunMinMaxBy<RunByOrd>>
+    where
+        I::Item: Ord,
+    {
+        self.with(RunMinMaxBy(RunByOrd))
+    }
+
+    /// Finds the minimum and maximum of each run with respect to the specified comparison
+    /// function.
+    ///
+    /// If several elements are equally maximum, the last element is picked.
+    /// If several elements are equally minimum, the first element is picked.
+    ///
+    /// Iterator element type is `(K, MinMaxResult<I::Item>)`.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    /// use itertools::MinMaxResult::{MinMax, OneElement};
+    ///
+    /// let runs = vec![1, 3, 4, 8, 6, 5].into_iter()
+    ///     .aggregate_runs(|&n| n % 2)
+    ///     .minmax_by(|_key, x, y| y.cmp(x));
+    ///
+    /// itertools::assert_equal(runs, vec![(1, MinMax(3, 1)), (0, MinMax(8, 4)), (1, OneElement(5))]);
+    /// ```
+    pub fn minmax_by<FC>(self, compare: FC) -> RunAggregates<K, I, F, RunMinMaxBy<FC>>
+    where
+        FC: FnMut(&K, &I::Item, &I::Item) -> Ordering,
+    {
+        self.with(RunMinMaxBy(compare))
+    }
+
+    /// Finds the elements of each run that give the minimum and maximum from the specified
+    /// function.
+    ///
+    /// If several elements are equally maximum, the last element is picked.
+    /// If several elements are equally minimum, the first element is picked.
+    ///
+    /// Iterator element type is `(K, MinMaxResult<I::Item>)`.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    /// use itertools::MinMaxResult::{MinMax, OneElement};
+    ///
+    /// let runs = vec![1, 3, 4, 8, 6, 5].into_iter()
+    ///     .aggregate_runs(|&n| n % 2)
+    ///     .minmax_by_key(|_key, &val| val % 4);
+    ///
+    /// itertools::assert_equal(runs, vec![(1, MinMax(1, 3)), (0, MinMax(4, 6)), (1, OneElement(5))]);
+    /// ```
+    pub fn minmax_by_key<FK, CK>(self, f: FK) -> RunAggregates<K, I, F, RunMinMaxBy<RunByKey<FK>>>
+    where
+        FK: FnMut(&K, &I::Item) -> CK,
+        CK: Ord,
+    {
+        self.with(RunMinMaxBy(RunByKey(f)))
+    }
+
+    /// Sums the elements of each run.
+    ///
+    /// This is just a shorthand for `self.reduce(|acc, _, val| acc + val)`.
+    ///
+    /// Iterator element type is `(K, I::Item)`.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// let runs = vec![1, 3, 4, 6, 8, 5].into_iter()
+    ///     .aggregate_runs(|&n| n % 2)
+    ///     .sum();
+    ///
+    /// itertools::assert_equal(runs, vec![(1, 1 + 3), (0, 4 + 6 + 8), (1, 5)]);
+    /// ```
+    pub fn sum(self) -> RunAggregates<K, I, F, RunSum>
+    where
+        I::Item: Add<I::Item, Output = I::Item>,
+    {
+        self.with(RunSum)
+    }
+
+    /// Multiplies the elements of each run.
+    ///
+    /// This is just a shorthand for `self.reduce(|acc, _, val| acc * val)`.
+    ///
+    /// Iterator element type is `(K, I::Item)`.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// let runs = vec![1, 3, 4, 6, 8, 5].into_iter()
+    ///     .aggregate_runs(|&n| n % 2)
+    ///     .product();
+    ///
+    /// itertools::assert_equal(runs, vec![(1, 1 * 3), (0, 4 * 6 * 8), (1, 5)]);
+    /// ```
+    pub fn product(self) -> RunAggregates<K, I, F, RunProduct>
+    where
+        I::Item: Mul<I::Item, Output = I::Item>,
+    {
+        self.with(RunProduct)
+    }
+
+    /// Counts the elements of each run.
+    ///
+    /// Iterator element type is `(K, usize)`.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// let runs = "aaabccdd".chars().aggregate_runs(|&c| c).count();
+    ///
+    /// itertools::assert_equal(runs, vec![('a', 3), ('b', 1), ('c', 2), ('d', 2)]);
+    /// ```
+    pub fn count(self) -> RunAggregates<K, I, F, RunCount> {
+        self.with(RunCount)
+    }
+}
+
+/// An iterator adaptor that folds runs of consecutive elements with the same key,
+/// and yields each key with the result as soon as its run ends.
+///
+/// Iterator element type is `(K, R)`, where `R` depends on the operation.
+///
+/// See [`.aggregate_runs()`](crate::Itertools::aggregate_runs) for more information.
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+pub struct RunAggregates<K, I, F, A>
+where
+    I: Iterator,
+{
+    iter: Fuse<I>,
+    key: F,
+    aggregator: A,
+    /// The first element of the next run, along with its key.
+    pending: Option<(K, I::Item)>,
+}
+
+impl<K, I, F, A> Clone for RunAggregates<K, I, F, A>
+where
+    K: Clone,
+    I: Iterator + Clone,
+    I::Item: Clone,
+    F: Clone,
+    A: Clone,
+{
+    clone_fields!(iter, key, aggregator, pending);
+}
+
+impl<K, I, F, A> fmt::Debug for RunAggregates<K, I, F, A>
+where
+    K: fmt::Debug,
+    I: Iterator + fmt::Debug,
+    I::Item: fmt::Debug,
+{
+    debug_fmt_fields!(RunAggregates, iter, pending);
+}
+
+impl<K, I, F, A> Iterator for RunAggregates<K, I, F, A>
+where
+    I: Iterator,
+    F: FnMut(&I::Item) -> K,
+    K: PartialEq,
+    A: RunAggregator<K, I::Item>,
+{
+    type Item = (K, A::Out);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (key, first) = match self.pending.take() {
+            Some(pending) => pending,
+            None => {
+                let elt = self.iter.next()?;
+                ((self.key)(&elt), elt)
+            }
+        };
+        let mut acc = self.aggregator.first(&key, first);
+        for elt in &mut self.iter {
+            let next_key = (self.key)(&elt);
+            if next_key != key {
+                self.pending = Some((next_key, elt));
+                break;
+            }
+            acc = self.aggregator.next(acc, &key, elt);
+        }
+        Some((key, acc))
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        let pending = usize::from(self.pending.is_some());
+        let (low, hi) = self.iter.size_hint();
+        // All remaining elements might end up in a single run.
+        let low = usize::from(low > 0 || pending > 0);
+        (low, hi.and_then(|hi| hi.checked_add(pending)))
+    }
+}
+
+impl<K, I, F, A> FusedIterator for RunAggregates<K, I, F, A>
+where
+    I: Iterator,
+    F: FnMut(&I::Item) -> K,
+    K: PartialEq,
+    A: RunAggregator<K, I::Item>,
+{
+}
@@ -0,0 +1,206 @@
+use alloc::vec::Vec;
+use std::convert::TryFrom;
+use std::fmt;
+use std::iter::FusedIterator;
+
+use super::combinations::PoolIndex;
+use super::lazy_buffer::LazyBuffer;
+use crate::size_hint::SizeHint;
+
+/// Iterator for `Vec` valued tuples returned by [`.cartesian_power()`](crate::Itertools::cartesian_power)
+pub type CartesianPower<I> = CartesianPowerGeneric<I, Vec<usize>>;
+/// Iterator for const generic tuples returned by [`.array_cartesian_power()`](crate::Itertools::array_cartesian_power)
+pub type ArrayCartesianPower<I, const K: usize> = CartesianPowerGeneric<I, [usize; K]>;
+
+/// Create a new `CartesianPower` from an iterator.
+pub fn cartesian_power<I: Iterator>(iter: I, k: usize) -> CartesianPower<I> {
+    CartesianPowerGeneric::new(iter, alloc::vec![0; k])
+}
+
+/// Create a new `ArrayCartesianPower` from an iterator.
+pub fn array_cartesian_power<I: Iterator, const K: usize>(iter: I) -> ArrayCartesianPower<I, K> {
+    CartesianPowerGeneric::new(iter, [0; K])
+}
+
+/// An iterator to iterate through all the `k`-length tuples of elements from an iterator,
+/// with replacement.
+///
+/// See [`.cartesian_power()`](crate::Itertools::cartesian_power) and
+/// [`.array_cartesian_power()`](crate::Itertools::array_cartesian_power) for more information.
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+pub struct CartesianPowerGeneric<I: Iterator, Idx> {
+    indices: Idx,
+    pool: LazyBuffer<I>,
+    first: bool,
+    done: bool,
+}
+
+impl<I, Idx> Clone for CartesianPowerGeneric<I, Idx>
+where
+    I: Iterator + Clone,
+    I::Item: Clone,
+    Idx: Clone,
+{
+    clone_fields!(indices, pool, first, done);
+}
+
+impl<I, Idx> fmt::Debug for CartesianPowerGeneric<I, Idx>
+where
+    I: Iterator + fmt::Debug,
+    I::Item: fmt::Debug,
+    Idx: fmt::Debug,
+{
+    debug_fmt_fields!(CartesianPowerGeneric, indices, pool, first, done);
+}
+
+impl<I: Iterator, Idx: PoolIndex<I::Item>> CartesianPowerGeneric<I, Idx> {
+    fn new(iter: I, indices: Idx) -> Self {
+        Self {
+            indices,
+            pool: LazyBuffer::new(iter),
+            first: true,
+            done: false,
+        }
+    }
+
+    /// Returns the length of a tuple produced by this iterator.
+    #[inline]
+    pub fn k(&self) -> usize {
+        self.indices.len()
+    }
+
+    /// Initialises the iterator by loading the first element if needed.
+    /// Returns true if there are no tuples, false otherwise.
+    fn init(&mut self) -> bool {
+        self.first = false;
+        self.pool.prefill(1);
+        self.k() > 0 && self.pool.len() == 0
+    }
+
+    /// Increments indices like an odometer to advance to the next tuple, only loading
+    /// the elements from the source iterator when they are needed.
+    ///
+    /// Returns true if we've run out of tuples, false otherwise.
+    fn increment_indices(&mut self) -> bool {
+        let indices = self.indices.borrow_mut();
+        for index in indices.iter_mut().rev() {
+            if *index + 1 == self.pool.len() {
+                self.pool.get_next(); // may change pool size
+            }
+            if *index + 1 < self.pool.len() {
+                *index += 1;
+                return false;
+            }
+            *index = 0;
+        }
+        true
+    }
+
+    /// Moves `n` tuples forward, knowing that the whole pool is loaded.
+    ///
+    /// Returns true if we've run out of tuples, false otherwise.
+    fn skip_loaded(&mut self, n: u128) -> bool {
+        let len = self.pool.len() as u128;
+        let indices = self.indices.borrow_mut();
+        // Add `n` as a number written in base `len`.
+        let mut carry = n;
+        for index in indices.iter_mut().rev() {
+            if carry == 0 {
+                break;
+            }
+            let sum = *index as u128 + carry;
+            *index = (sum % len) as usize;
+            carry = sum / len;
+        }
+        carry > 0
+    }
+}
+
+impl<I, Idx> Iterator for CartesianPowerGeneric<I, Idx>
+where
+    I: Iterator,
+    I::Item: Clone,
+    Idx: PoolIndex<I::Item>,
+{
+    type Item = Idx::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let done = self.done
+            || if self.first {
+                self.init()
+            } else {
+                self.increment_indices()
+            };
+        if done {
+            self.done = true;
+            return None;
+        }
+        Some(self.indices.extract_item(&self.pool))
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        if self.done || !self.pool.can_fill_within(n) {
+            // Skip the tuples one by one, which only loads the elements they need, unless
+            // the rest of the source iterator is known to be short enough to load entirely.
+            for _ in 0..n {
+                self.next()?;
+            }
+            return self.next();
+        }
+        self.pool.fill();
+        let done = if self.first {
+            self.init() || self.skip_loaded(n as u128)
+        } else {
+            self.skip_loaded(n as u128 + 1)
+        };
+        if done {
+            self.done = true;
+            return None;
+        }
+        Some(self.indices.extract_item(&self.pool))
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        let (low, upp) = self.pool.size_hint();
+        let remaining = |n| remaining_for(n, self.first, self.done, self.indices.borrow());
+        (
+            remaining(low).unwrap_or(usize::MAX),
+            upp.and_then(remaining),
+        )
+    }
+
+    fn count(mut self) -> usize {
+        self.pool.fill();
+        remaining_for(
+            self.pool.len(),
+            self.first,
+            self.done,
+            self.indices.borrow(),
+        )
+        .unwrap()
+    }
+}
+
+impl<I, Idx> FusedIterator for CartesianPowerGeneric<I, Idx>
+where
+    I: Iterator,
+    I::Item: Clone,
+    Idx: PoolIndex<I::Item>,
+{
+}
+
+/// For a given pool size `n`, return the count of remaining tuples or None if it would overflow.
+fn remaining_for(n: usize, first: bool, done: bool, indices: &[usize]) -> Option<usize> {
+    if done {
+        return Some(0);
+    }
+    let k = u32::try_from(indices.len()).ok()?;
+    if first {
+        return n.checked_pow(k);
+    }
+    // The tuples after the current one are counted digit by digit: for each index, the
+    // greater values it can take, times the count of values the indices to its right can take.
+    indices.iter().try_fold(0usize, |count, &index| {
+        count.checked_mul(n)?.checked_add(n.checked_sub(index + 1)?)
+    })
+}
@@ -0,0 +1,146 @@
+use alloc::vec::Vec;
+use std::fmt;
+use std::iter::{Fuse, FusedIterator};
+
+use crate::size_hint::SizeHint;
+
+/// An iterator adaptor that groups consecutive elements with the same key
+/// into owned vectors.
+///
+/// Iterator element type is `(K, Vec<I::Item>)`.
+///
+/// See [`.chunk_by_owned()`](crate::Itertools::chunk_by_owned) for more information.
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+pub struct ChunkByOwned<K, I, F>
+where
+    I: Iterator,
+{
+    iter: Fuse<I>,
+    key: F,
+    /// The first element of the next group, along with its key.
+    pending: Option<(K, I::Item)>,
+}
+
+impl<K, I, F> Clone for ChunkByOwned<K, I, F>
+where
+    K: Clone,
+    I: Iterator + Clone,
+    I::Item: Clone,
+    F: Clone,
+{
+    clone_fields!(iter, key, pending);
+}
+
+impl<K, I, F> fmt::Debug for ChunkByOwned<K, I, F>
+where
+    K: fmt::Debug,
+    I: Iterator + fmt::Debug,
+    I::Item: fmt::Debug,
+{
+    debug_fmt_fields!(ChunkByOwned, iter, pending);
+}
+
+/// Create a new `ChunkByOwned`.
+pub fn chunk_by_owned<K, I, F>(iter: I, key: F) -> ChunkByOwned<K, I, F>
+where
+    I: Iterator,
+{
+    ChunkByOwned {
+        iter: iter.fuse(),
+        key,
+        pending: None,
+    }
+}
+
+impl<K, I, F> Iterator for ChunkByOwned<K, I, F>
+where
+    I: Iterator,
+    F: FnMut(&I::Item) -> K,
+    K: PartialEq,
+{
+    type Item = (K, Vec<I::Item>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (key, first) = match self.pending.take() {
+            Some(pending) => pending,
+            None => {
+                let elt = self.iter.next()?;
+                ((self.key)(&elt), elt)
+            }
+        };
+        let mut group = alloc::vec![first];
+        for elt in &mut self.iter {
+            let next_key = (self.key)(&elt);
+            if next_key != key {
+                self.pending = Some((next_key, elt));
+                break;
+            }
+            group.push(elt);
+        }
+        Some((key, group))
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        let pending = usize::from(self.pending.is_some());
+        let (low, hi) = self.iter.size_hint();
+        // All remaining elements might end up in a single group.
+        let low = usize::from(low > 0 || pending > 0);
+        (low, hi.and_then(|hi| hi.checked_add(pending)))
+    }
+}
+
+impl<K, I, F> FusedIterator for ChunkByOwned<K, I, F>
+where
+    I: Iterator,
+    F: FnMut(&I::Item) -> K,
+    K: PartialEq,
+{
+}
+
+/// An iterator adaptor that yields owned chunks of a fixed size.
+///
+/// Iterator element type is `Vec<I::Item>`.
+///
+/// See [`.chunks_owned()`](crate::Itertools::chunks_owned) for more information.
+#[derive(Clone, Debug)]
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+pub struct ChunksOwned<I> {
+    iter: Fuse<I>,
+    size: usize,
+}
+
+/// Create a new `ChunksOwned`.
+pub fn chunks_owned<I>(iter: I, size: usize) -> ChunksOwned<I>
+where
+    I: Iterator,
+{
+    debug_assert_ne!(size, 0);
+    ChunksOwned {
+        iter: iter.fuse(),
+        size,
+    }
+}
+
+impl<I> Iterator for ChunksOwned<I>
+where
+    I: Iterator,
+{
+    type Item = Vec<I::Item>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let first = self.iter.next()?;
+        let mut chunk = alloc::vec![first];
+        chunk.extend(self.iter.by_ref().take(self.size - 1));
+        Some(chunk)
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        let div_ceil = |n: usize| n / self.size + usize::from(n % self.size != 0);
+        let (low, hi) = self.iter.size_hint();
+        (div_ceil(low), hi.map(div_ceil))
+    }
+}
+
+impl<I> ExactSizeIterator for ChunksOwned<I> where I: ExactSizeIterator {}
+
+impl<I> FusedIterator for ChunksOwned<I> where I: Iterator {}
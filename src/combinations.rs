@@ -1,13 +1,14 @@
 use alloc::boxed::Box;
 use core::array;
 use core::borrow::BorrowMut;
+use std::convert::TryFrom;
 use std::fmt;
 use std::iter::FusedIterator;
 
 use super::lazy_buffer::LazyBuffer;
 use alloc::vec::Vec;
 
-use crate::adaptors::checked_binomial;
+use crate::count::{binomial, binomial_u128};
 
 /// Iterator for `Vec` valued combinations returned by [`.combinations()`](crate::Itertools::combinations)
 pub type Combinations<I> = CombinationsGeneric<I, Vec<usize>>;
@@ -38,6 +39,8 @@ pub struct CombinationsGeneric<I: Iterator, Idx> {
     indices: Idx,
     pool: LazyBuffer<I>,
     first: bool,
+    /// The last combination generated from the back, if any.
+    back: Option<Idx>,
 }
 
 /// A type holding indices of elements in a pool or buffer of items from an inner iterator
@@ -91,7 +94,7 @@ where
     I::Item: Clone,
     Idx: Clone,
 {
-    clone_fields!(indices, pool, first);
+    clone_fields!(indices, pool, first, back);
 }
 
 impl<I, Idx> fmt::Debug for CombinationsGeneric<I, Idx>
@@ -100,7 +103,7 @@ where
     I::Item: fmt::Debug,
     Idx: fmt::Debug,
 {
-    debug_fmt_fields!(Combinations, indices, pool, first);
+    debug_fmt_fields!(Combinations, indices, pool, first, back);
 }
 
 impl<I: Iterator, Idx: PoolIndex<I::Item>> CombinationsGeneric<I, Idx> {
@@ -110,6 +113,7 @@ impl<I: Iterator, Idx: PoolIndex<I::Item>> CombinationsGeneric<I, Idx> {
             indices,
             pool: LazyBuffer::new(iter),
             first: true,
+            back: None,
         }
     }
 
@@ -132,15 +136,64 @@ impl<I: Iterator, Idx: PoolIndex<I::Item>> CombinationsGeneric<I, Idx> {
         &self.pool
     }
 
+    /// Returns the indices of the last generated combination, if any.
+    #[inline]
+    pub(crate) fn current(&self) -> Option<&[usize]> {
+        if self.first {
+            None
+        } else {
+            Some(self.indices.borrow())
+        }
+    }
+
+    /// Loads all remaining elements of the source iterator into the pool.
+    #[inline]
+    pub(crate) fn fill(&mut self) {
+        self.pool.fill();
+    }
+
     /// Return the length of the inner iterator and the count of remaining combinations.
     pub(crate) fn n_and_count(self) -> (usize, usize) {
         let Self {
             indices,
             pool,
             first,
+            back,
         } = self;
         let n = pool.count();
-        (n, remaining_for(n, first, indices.borrow()).unwrap())
+        let back = back.as_ref().map(|back| back.borrow());
+        (
+            n,
+            remaining_between(n, first, indices.borrow(), back).unwrap(),
+        )
+    }
+
+    /// Returns the count of remaining combinations, or `None` if it does not fit in a `usize`.
+    ///
+    /// Unlike [`count`](Iterator::count), this does not panic on overflow nor consume the
+    /// iterator, but it loads the whole source iterator.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// let mut it = (0..5).combinations(2);
+    /// assert_eq!(it.checked_len(), Some(10));
+    /// it.next();
+    /// assert_eq!(it.checked_len(), Some(9));
+    /// assert_eq!((0..100).combinations(50).checked_len(), None);
+    /// ```
+    pub fn checked_len(&mut self) -> Option<usize> {
+        self.fill();
+        let back = self.back.as_ref().map(|back| back.borrow());
+        remaining_between(self.n(), self.first, self.indices.borrow(), back)
+    }
+
+    /// Returns true if the front has caught up with the combinations generated from the back.
+    fn front_reached_back(&self) -> bool {
+        match &self.back {
+            Some(back) => !self.first && self.indices.borrow() >= back.borrow(),
+            None => false,
+        }
     }
 
     /// Initialises the iterator by filling a buffer with elements from the
@@ -167,30 +220,13 @@ impl<I: Iterator, Idx: PoolIndex<I::Item>> CombinationsGeneric<I, Idx> {
         if indices.is_empty() {
             return true; // Done
         }
-        // Scan from the end, looking for an index to increment
-        let mut i: usize = indices.len() - 1;
 
         // Check if we need to consume more from the iterator
-        if indices[i] == self.pool.len() - 1 {
+        if indices[indices.len() - 1] == self.pool.len() - 1 {
             self.pool.get_next(); // may change pool size
         }
 
-        while indices[i] == i + self.pool.len() - indices.len() {
-            if i > 0 {
-                i -= 1;
-            } else {
-                // Reached the last combination
-                return true;
-            }
-        }
-
-        // Increment index, and reset the ones to its right
-        indices[i] += 1;
-        for j in i + 1..indices.len() {
-            indices[j] = indices[j - 1] + 1;
-        }
-        // If we've made it this far, we haven't run out of combos
-        false
+        increment_indices(indices, self.pool.len())
     }
 
     /// Returns the n-th item or the number of successful steps.
@@ -204,16 +240,153 @@ impl<I: Iterator, Idx: PoolIndex<I::Item>> CombinationsGeneric<I, Idx> {
         } else {
             self.increment_indices()
         };
-        if done {
+        if done || self.front_reached_back() {
             return Err(0);
         }
+        if n > 0 && self.pool.can_fill_within(n) {
+            // The source is (almost) exhausted: load it entirely so that the length of the pool
+            // is known, then jump directly to the n-th next combination with the combinatorial
+            // number system. Otherwise, stepping only loads the elements it needs.
+            self.pool.fill();
+            let pool_len = self.pool.len();
+            let back = self.back.as_ref().map(|back| back.borrow());
+            let indices = self.indices.borrow_mut();
+            if let Some(remaining) = remaining_between(pool_len, false, indices, back) {
+                if remaining < n {
+                    // Park the indices on the last combination so that we stay exhausted.
+                    match back {
+                        Some(back) => indices.copy_from_slice(back),
+                        None => set_last(indices, pool_len),
+                    }
+                    return Err(remaining + 1);
+                }
+            }
+            // If the rank does not fit in a `u128`, fall back to stepping.
+            if let Some(target) = rank_of(pool_len, indices).and_then(|r| r.checked_add(n as u128))
+            {
+                unrank_into(pool_len, target, indices);
+                return Ok(self.indices.extract_item(&self.pool));
+            }
+        }
         for i in 0..n {
-            if self.increment_indices() {
+            if self.increment_indices() || self.front_reached_back() {
                 return Err(i + 1);
             }
         }
         Ok(self.indices.extract_item(&self.pool))
     }
+
+    /// Returns the rank of the combination made of the given pool `indices`, in the
+    /// lexicographic order in which this iterator yields the `k`-combinations of the pool.
+    ///
+    /// Returns `None` if `indices` is not a strictly increasing sequence of `k` indices
+    /// smaller than [`n`](Self::n), or if the rank does not fit in a `u128`.
+    ///
+    /// Note that ranks depend on the length of the pool, which is only final once the
+    /// source iterator is exhausted.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// let mut it = (0..5).combinations(3);
+    /// assert_eq!(it.nth(6), Some(vec![1, 2, 3]));
+    /// // The source is now fully buffered.
+    /// assert_eq!(it.n(), 5);
+    /// assert_eq!(it.rank(&[1, 2, 3]), Some(6));
+    /// assert_eq!(it.rank(&[2, 1, 3]), None);
+    /// assert_eq!(itertools::count::unrank_combination(5, 3, 6), Some(vec![1, 2, 3]));
+    /// ```
+    ///
+    /// The inverse is [`count::unrank_combination`](crate::count::unrank_combination).
+    pub fn rank(&self, indices: &[usize]) -> Option<u128> {
+        let n = self.n();
+        let valid = indices.len() == self.k()
+            && indices.windows(2).all(|w| w[0] < w[1])
+            && indices.last().map_or(true, |&last| last < n);
+        if !valid {
+            return None;
+        }
+        rank_of(n, indices)
+    }
+}
+
+impl<I, Idx> CombinationsGeneric<I, Idx>
+where
+    I: Iterator + Clone,
+    I::Item: Clone,
+    Idx: PoolIndex<I::Item> + Clone,
+{
+    /// Splits the remaining combinations into the first `rank` ones and the others.
+    ///
+    /// The two iterators generate disjoint contiguous ranges of combinations which together
+    /// cover the ones this iterator would have generated, so that they can be consumed
+    /// independently (on different threads for example). The first one is shorter than
+    /// `rank` if there are not enough remaining combinations.
+    ///
+    /// The whole source iterator is loaded.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// let (head, tail) = (0..4).combinations(2).split_at(2);
+    /// itertools::assert_equal(head, vec![vec![0, 1], vec![0, 2]]);
+    /// itertools::assert_equal(tail, vec![vec![0, 3], vec![1, 2], vec![1, 3], vec![2, 3]]);
+    /// ```
+    pub fn split_at(mut self, rank: usize) -> (Self, Self) {
+        self.fill();
+        let mut tail = self.clone();
+        if rank > 0 && tail.try_nth(rank - 1).is_err() {
+            return (self, tail);
+        }
+        // The head stops right before the first combination of the tail.
+        let mut probe = tail.clone();
+        if probe.next().is_some() {
+            self.back = Some(probe.indices);
+        }
+        (self, tail)
+    }
+
+    /// Splits the remaining combinations into `parts` contiguous ranges whose lengths differ
+    /// by at most one, see [`split_at`](Self::split_at).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `parts` is zero or if the count of remaining combinations overflows a `usize`.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// let parts = (0..5).combinations(3).split_into(3);
+    /// let lens: Vec<_> = parts.iter().map(|part| part.clone().count()).collect();
+    /// assert_eq!(lens, vec![4, 3, 3]);
+    /// itertools::assert_equal(parts.into_iter().flatten(), (0..5).combinations(3));
+    /// ```
+    pub fn split_into(mut self, parts: usize) -> Vec<Self> {
+        self.fill();
+        let len = self.size_hint().1.expect("too many combinations");
+        split_evenly(self, len, parts, Self::split_at)
+    }
+}
+
+/// Splits `iter`, which has `len` remaining items, into `parts` contiguous pieces whose lengths
+/// differ by at most one, with the given `split_at` function.
+pub(crate) fn split_evenly<T>(
+    iter: T,
+    len: usize,
+    parts: usize,
+    split_at: impl Fn(T, usize) -> (T, T),
+) -> Vec<T> {
+    assert!(parts > 0, "can not split into zero parts");
+    let (size, extra) = (len / parts, len % parts);
+    let mut pieces = Vec::with_capacity(parts);
+    let mut rest = iter;
+    for i in 1..parts {
+        let (piece, tail) = split_at(rest, size + usize::from(i <= extra));
+        pieces.push(piece);
+        rest = tail;
+    }
+    pieces.push(rest);
+    pieces
 }
 
 impl<I, Idx> Iterator for CombinationsGeneric<I, Idx>
@@ -230,7 +403,7 @@ where
             self.increment_indices()
         };
 
-        if done {
+        if done || self.front_reached_back() {
             return None;
         }
 
@@ -243,8 +416,10 @@ where
 
     fn size_hint(&self) -> (usize, Option<usize>) {
         let (mut low, mut upp) = self.pool.size_hint();
-        low = remaining_for(low, self.first, self.indices.borrow()).unwrap_or(usize::MAX);
-        upp = upp.and_then(|upp| remaining_for(upp, self.first, self.indices.borrow()));
+        let back = self.back.as_ref().map(|back| back.borrow());
+        let remaining = |n| remaining_between(n, self.first, self.indices.borrow(), back);
+        low = remaining(low).unwrap_or(usize::MAX);
+        upp = upp.and_then(remaining);
         (low, upp)
     }
 
@@ -254,6 +429,64 @@ where
     }
 }
 
+impl<I, Idx> DoubleEndedIterator for CombinationsGeneric<I, Idx>
+where
+    I: Iterator,
+    I::Item: Clone,
+    Idx: PoolIndex<I::Item> + Clone,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        // The last combinations can only be known once the source is exhausted.
+        self.pool.fill();
+        let (n, k) = (self.n(), self.k());
+        if k > n {
+            return None;
+        }
+        let back = match &mut self.back {
+            Some(back) => {
+                if decrement_indices(back.borrow_mut(), n) {
+                    return None;
+                }
+                back
+            }
+            None => {
+                let mut back = self.indices.clone();
+                set_last(back.borrow_mut(), n);
+                self.back.insert(back)
+            }
+        };
+        if !self.first && self.indices.borrow() >= back.borrow() {
+            return None;
+        }
+        Some(back.extract_item(&self.pool))
+    }
+
+    fn rfold<B, F>(mut self, init: B, mut f: F) -> B
+    where
+        F: FnMut(B, Self::Item) -> B,
+    {
+        let mut acc = match self.next_back() {
+            Some(item) => f(init, item),
+            None => return init,
+        };
+        let n = self.n();
+        let Self {
+            indices,
+            pool,
+            first,
+            back,
+        } = self;
+        // `next_back` has loaded the whole pool and initialized `back`.
+        let mut back = back.unwrap();
+        while !decrement_indices(back.borrow_mut(), n)
+            && (first || indices.borrow() < back.borrow())
+        {
+            acc = f(acc, back.extract_item(&pool));
+        }
+        acc
+    }
+}
+
 impl<I, Idx> FusedIterator for CombinationsGeneric<I, Idx>
 where
     I: Iterator,
@@ -269,6 +502,7 @@ impl<I: Iterator> Combinations<I> {
     /// elements.
     pub(crate) fn reset(&mut self, k: usize) {
         self.first = true;
+        self.back = None;
 
         if k < self.indices.len() {
             self.indices.truncate(k);
@@ -285,13 +519,99 @@ impl<I: Iterator> Combinations<I> {
     }
 }
 
+/// Increments indices representing a combination of `0..n` to advance to the next
+/// (in lexicographic order by increasing sequence) combination. For example
+/// if we have n=4 & k=2 then `[0, 1] -> [0, 2] -> [0, 3] -> [1, 2] -> ...`.
+///
+/// Returns true if there is no next combination, false otherwise.
+pub(crate) fn increment_indices(indices: &mut [usize], n: usize) -> bool {
+    if indices.is_empty() {
+        return true; // Done
+    }
+    // Scan from the end, looking for an index to increment
+    let mut i: usize = indices.len() - 1;
+
+    while indices[i] == i + n - indices.len() {
+        if i > 0 {
+            i -= 1;
+        } else {
+            // Reached the last combination
+            return true;
+        }
+    }
+
+    // Increment index, and reset the ones to its right
+    indices[i] += 1;
+    for j in i + 1..indices.len() {
+        indices[j] = indices[j - 1] + 1;
+    }
+    // If we've made it this far, we haven't run out of combos
+    false
+}
+
+/// Decrements indices representing a combination of `0..n` to go back to the previous
+/// (in lexicographic order by increasing sequence) combination. For example
+/// if we have n=4 & k=2 then `[1, 2] -> [0, 3] -> [0, 2] -> [0, 1]`.
+///
+/// Returns true if there is no previous combination, false otherwise.
+pub(crate) fn decrement_indices(indices: &mut [usize], n: usize) -> bool {
+    // Scan from the end, looking for an index that can be decremented
+    let mut i = indices.len();
+    loop {
+        if i == 0 {
+            // Reached the first combination
+            return true;
+        }
+        i -= 1;
+        let min = if i == 0 { 0 } else { indices[i - 1] + 1 };
+        if indices[i] > min {
+            break;
+        }
+    }
+
+    // Decrement index, and push the ones to its right to their maximum
+    indices[i] -= 1;
+    let k = indices.len();
+    for (j, index) in indices.iter_mut().enumerate().skip(i + 1) {
+        *index = n - k + j;
+    }
+    false
+}
+
+/// Sets `indices` to the last combination of `0..n`.
+pub(crate) fn set_last(indices: &mut [usize], n: usize) {
+    let k = indices.len();
+    for (i, index) in indices.iter_mut().enumerate() {
+        *index = n - k + i;
+    }
+}
+
+/// For a given size `n`, return the count of combinations strictly between the front ones and
+/// the `back` ones or None if it would overflow.
+fn remaining_between(
+    n: usize,
+    first: bool,
+    indices: &[usize],
+    back: Option<&[usize]>,
+) -> Option<usize> {
+    match back {
+        None => remaining_for(n, first, indices),
+        Some(back) => {
+            // `n` is known to be exact when combinations are generated from the back.
+            let back = rank_of(n, back)?;
+            let front = if first { 0 } else { rank_of(n, indices)? + 1 };
+            usize::try_from(back.saturating_sub(front)).ok()
+        }
+    }
+}
+
 /// For a given size `n`, return the count of remaining combinations or None if it would overflow.
-fn remaining_for(n: usize, first: bool, indices: &[usize]) -> Option<usize> {
+pub(crate) fn remaining_for(n: usize, first: bool, indices: &[usize]) -> Option<usize> {
     let k = indices.len();
     if n < k {
         Some(0)
     } else if first {
-        checked_binomial(n, k)
+        binomial(n, k)
     } else {
         // https://en.wikipedia.org/wiki/Combinatorial_number_system
         // http://www.site.uottawa.ca/~lucia/courses/5165-09/GenCombObj.pdf
@@ -312,7 +632,39 @@ fn remaining_for(n: usize, first: bool, indices: &[usize]) -> Option<usize> {
 
         // Below, `n0` resembles indices[i].
         indices.iter().enumerate().try_fold(0usize, |sum, (i, n0)| {
-            sum.checked_add(checked_binomial(n - 1 - *n0, k - i)?)
+            sum.checked_add(binomial(n - 1 - *n0, k - i)?)
         })
     }
 }
+
+/// Returns the lexicographic rank of the given valid `indices` among the `k`-combinations
+/// of `0..n`, or `None` if it would overflow.
+fn rank_of(n: usize, indices: &[usize]) -> Option<u128> {
+    let k = indices.len();
+    let total = binomial_u128(n, k)?;
+    // Same counting of the subsequent combinations as in `remaining_for`.
+    let remaining = indices.iter().enumerate().try_fold(0u128, |sum, (i, n0)| {
+        sum.checked_add(binomial_u128(n - 1 - *n0, k - i)?)
+    })?;
+    Some(total - 1 - remaining)
+}
+
+/// Writes into `indices` the `k`-combination of `0..n` with the given lexicographic `rank`,
+/// where `k = indices.len()` and `rank` is smaller than `binomial(n, k)`.
+pub(crate) fn unrank_into(n: usize, mut rank: u128, indices: &mut [usize]) {
+    let k = indices.len();
+    let mut n0 = 0;
+    for (i, index) in indices.iter_mut().enumerate() {
+        // Skip the blocks of combinations with a smaller index at position `i`.
+        // A block too large for a `u128` necessarily contains the target.
+        while let Some(count) = binomial_u128(n - 1 - n0, k - 1 - i) {
+            if rank < count {
+                break;
+            }
+            rank -= count;
+            n0 += 1;
+        }
+        *index = n0;
+        n0 += 1;
+    }
+}
@@ -0,0 +1,142 @@
+use alloc::vec::Vec;
+use std::fmt;
+use std::iter::FusedIterator;
+
+use super::combinations::remaining_for;
+use super::lazy_buffer::LazyBuffer;
+use crate::count::binomial;
+use crate::size_hint::SizeHint;
+
+/// An iterator to iterate through the `k`-length combinations of the elements from an
+/// iterator whose prefixes are all accepted by a predicate.
+///
+/// See [`.combinations_pruned()`](crate::Itertools::combinations_pruned) for more information.
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+pub struct CombinationsPruned<I: Iterator, F> {
+    pool: LazyBuffer<I>,
+    k: usize,
+    /// The pool indices of the current prefix.
+    indices: Vec<usize>,
+    /// The elements of the current prefix.
+    prefix: Vec<I::Item>,
+    f: F,
+    first: bool,
+    done: bool,
+}
+
+impl<I, F> Clone for CombinationsPruned<I, F>
+where
+    I: Clone + Iterator,
+    I::Item: Clone,
+    F: Clone,
+{
+    clone_fields!(pool, k, indices, prefix, f, first, done);
+}
+
+impl<I, F> fmt::Debug for CombinationsPruned<I, F>
+where
+    I: Iterator + fmt::Debug,
+    I::Item: fmt::Debug,
+{
+    debug_fmt_fields!(CombinationsPruned, pool, k, indices, prefix, first, done);
+}
+
+/// Create a new `CombinationsPruned` from an iterator.
+pub fn combinations_pruned<I, F>(iter: I, k: usize, f: F) -> CombinationsPruned<I, F>
+where
+    I: Iterator,
+    I::Item: Clone,
+    F: FnMut(&[I::Item]) -> bool,
+{
+    CombinationsPruned {
+        pool: LazyBuffer::new(iter),
+        k,
+        indices: Vec::with_capacity(k),
+        prefix: Vec::with_capacity(k),
+        f,
+        first: true,
+        done: false,
+    }
+}
+
+impl<I, F> CombinationsPruned<I, F>
+where
+    I: Iterator,
+    I::Item: Clone,
+{
+    /// Removes the last element of the prefix and returns the index after it,
+    /// or `None` if the prefix is empty.
+    fn backtrack(&mut self) -> Option<usize> {
+        self.prefix.pop();
+        self.indices.pop().map(|index| index + 1)
+    }
+}
+
+impl<I, F> Iterator for CombinationsPruned<I, F>
+where
+    I: Iterator,
+    I::Item: Clone,
+    F: FnMut(&[I::Item]) -> bool,
+{
+    type Item = Vec<I::Item>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        // The candidate index for the next element of the prefix.
+        let mut candidate = if self.first {
+            self.first = false;
+            Some(0)
+        } else {
+            self.backtrack()
+        };
+        while let Some(index) = candidate {
+            let depth = self.indices.len();
+            if depth == self.k {
+                return Some(self.prefix.clone());
+            }
+            // Only consider prefixes that can be completed with the remaining elements.
+            let needed = index.saturating_add(self.k - depth);
+            self.pool.prefill(needed);
+            if self.pool.len() < needed {
+                candidate = self.backtrack();
+                continue;
+            }
+            self.indices.push(index);
+            self.prefix.push(self.pool[index].clone());
+            if !(self.f)(&self.prefix) {
+                // Skip every combination starting with this prefix.
+                self.backtrack();
+            }
+            // Either the first candidate for the next element, or the next candidate
+            // in place of the rejected one.
+            candidate = Some(index + 1);
+        }
+        self.done = true;
+        None
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        if self.done {
+            return (0, Some(0));
+        }
+        // At most the remaining combinations, when no prefix is rejected.
+        let upp = self.pool.size_hint().1.and_then(|n| {
+            if self.first {
+                binomial(n, self.k)
+            } else {
+                remaining_for(n, false, &self.indices)
+            }
+        });
+        (0, upp)
+    }
+}
+
+impl<I, F> FusedIterator for CombinationsPruned<I, F>
+where
+    I: Iterator,
+    I::Item: Clone,
+    F: FnMut(&[I::Item]) -> bool,
+{
+}
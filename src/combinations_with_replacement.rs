@@ -3,8 +3,8 @@ use std::fmt;
 use std::iter::FusedIterator;
 
 use super::lazy_buffer::LazyBuffer;
-use crate::adaptors::checked_binomial;
 use crate::combinations::PoolIndex;
+use crate::count::multichoose;
 /// An iterator to iterate through all the `n`-length combinations in an iterator, with replacement.
 ///
 /// See [`.combinations_with_replacement()`](crate::Itertools::combinations_with_replacement)
@@ -168,21 +168,9 @@ where
 
 /// For a given size `n`, return the count of remaining combinations with replacement or None if it would overflow.
 fn remaining_for(n: usize, first: bool, indices: &[usize]) -> Option<usize> {
-    // With a "stars and bars" representation, choose k values with replacement from n values is
-    // like choosing k out of k + n − 1 positions (hence binomial(k + n - 1, k) possibilities)
-    // to place k stars and therefore n - 1 bars.
-    // Example (n=4, k=6): ***|*||** represents [0,0,0,1,3,3].
-    let count = |n: usize, k: usize| {
-        let positions = if n == 0 {
-            k.saturating_sub(1)
-        } else {
-            (n - 1).checked_add(k)?
-        };
-        checked_binomial(positions, k)
-    };
     let k = indices.len();
     if first {
-        count(n, k)
+        multichoose(n, k)
     } else {
         // The algorithm is similar to the one for combinations *without replacement*,
         // except we choose values *with replacement* and indices are *non-strictly* monotonically sorted.
@@ -192,18 +180,18 @@ fn remaining_for(n: usize, first: bool, indices: &[usize]) -> Option<usize> {
         //   If subsequent combinations differ in indices[0], then their value for indices[0]
         //   must be at least 1 greater than the current indices[0].
         //   As indices is monotonically sorted, this means we can effectively choose k values with
-        //   replacement from (n - 1 - indices[0]), leading to count(n - 1 - indices[0], k) possibilities.
+        //   replacement from (n - 1 - indices[0]), leading to multichoose(n - 1 - indices[0], k) possibilities.
         // - The subsequent combinations with same indices[0], but differing indices[1]:
         //   Here we can choose k - 1 values with replacement from (n - 1 - indices[1]) values,
-        //   leading to count(n - 1 - indices[1], k - 1) possibilities.
+        //   leading to multichoose(n - 1 - indices[1], k - 1) possibilities.
         // - (...)
         // - The subsequent combinations with same indices[0..=i], but differing indices[i]:
-        //   Here we can choose k - i values with replacement from (n - 1 - indices[i]) values: count(n - 1 - indices[i], k - i).
+        //   Here we can choose k - i values with replacement from (n - 1 - indices[i]) values: multichoose(n - 1 - indices[i], k - i).
         //   Since subsequent combinations can in any index, we must sum up the aforementioned binomial coefficients.
 
         // Below, `n0` resembles indices[i].
         indices.iter().enumerate().try_fold(0usize, |sum, (i, n0)| {
-            sum.checked_add(count(n - 1 - *n0, k - i)?)
+            sum.checked_add(multichoose(n - 1 - *n0, k - i)?)
         })
     }
 }
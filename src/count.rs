@@ -0,0 +1,200 @@
+//! Overflow-aware counting functions for the combinatorial adaptors.
+//!
+//! Each function returns `None` when the count does not fit in the returned type,
+//! instead of panicking or saturating.
+//!
+//! ```
+//! use itertools::count;
+//! use itertools::Itertools;
+//!
+//! assert_eq!(count::binomial(5, 2), Some((0..5).combinations(2).count()));
+//! assert_eq!(count::binomial(100, 50), None);
+//! assert_eq!(
+//!     count::binomial_u128(100, 50),
+//!     Some(100_891_344_545_564_193_334_812_497_256)
+//! );
+//! ```
+
+#[cfg(feature = "use_alloc")]
+use alloc::vec::Vec;
+use std::convert::TryFrom;
+
+/// Returns the count of `k`-combinations of `n` elements, or `None` if it would overflow.
+///
+/// This is the length of [`.combinations(k)`](crate::Itertools::combinations) over `n` elements.
+///
+/// ```
+/// use itertools::count::binomial;
+///
+/// assert_eq!(binomial(5, 2), Some(10));
+/// assert_eq!(binomial(2, 5), Some(0));
+/// assert_eq!(binomial(1000, 500), None);
+/// ```
+// https://en.wikipedia.org/wiki/Binomial_coefficient#In_programming_languages
+pub fn binomial(mut n: usize, mut k: usize) -> Option<usize> {
+    if n < k {
+        return Some(0);
+    }
+    // `factorial(n) / factorial(n - k) / factorial(k)` but trying to avoid it overflows:
+    k = (n - k).min(k); // symmetry
+    let mut c = 1;
+    for i in 1..=k {
+        c = (c / i)
+            .checked_mul(n)?
+            .checked_add((c % i).checked_mul(n)? / i)?;
+        n -= 1;
+    }
+    Some(c)
+}
+
+/// Same as [`binomial`] but with a `u128` result.
+pub fn binomial_u128(n: usize, k: usize) -> Option<u128> {
+    if n < k {
+        return Some(0);
+    }
+    let (mut n, k) = (n as u128, (n - k).min(k) as u128); // symmetry
+    let mut c: u128 = 1;
+    for i in 1..=k {
+        c = (c / i)
+            .checked_mul(n)?
+            .checked_add((c % i).checked_mul(n)? / i)?;
+        n -= 1;
+    }
+    Some(c)
+}
+
+/// Returns the indices of the `k`-combination of `0..n` with the given `rank`, in the
+/// lexicographic order of [`.combinations(k)`](crate::Itertools::combinations), or `None` if
+/// there are not more than `rank` such combinations.
+///
+/// This is the inverse of the `rank` method of [`Combinations`](crate::structs::Combinations).
+///
+/// ```
+/// use itertools::count::unrank_combination;
+///
+/// assert_eq!(unrank_combination(5, 3, 0), Some(vec![0, 1, 2]));
+/// assert_eq!(unrank_combination(5, 3, 6), Some(vec![1, 2, 3]));
+/// assert_eq!(unrank_combination(5, 3, 10), None);
+/// ```
+#[cfg(feature = "use_alloc")]
+pub fn unrank_combination(n: usize, k: usize, rank: u128) -> Option<Vec<usize>> {
+    if binomial_u128(n, k).map_or(false, |total| rank >= total) {
+        return None;
+    }
+    let mut indices = alloc::vec![0; k];
+    crate::combinations::unrank_into(n, rank, &mut indices);
+    Some(indices)
+}
+
+/// Returns the count of `k`-combinations with replacement of `n` elements, or `None` if it
+/// would overflow.
+///
+/// This is the length of
+/// [`.combinations_with_replacement(k)`](crate::Itertools::combinations_with_replacement)
+/// over `n` elements.
+///
+/// ```
+/// use itertools::count::multichoose;
+///
+/// assert_eq!(multichoose(3, 2), Some(6));
+/// assert_eq!(multichoose(0, 0), Some(1));
+/// assert_eq!(multichoose(0, 2), Some(0));
+/// ```
+pub fn multichoose(n: usize, k: usize) -> Option<usize> {
+    // With a "stars and bars" representation, choose k values with replacement from n values is
+    // like choosing k out of k + n − 1 positions (hence binomial(k + n - 1, k) possibilities)
+    // to place k stars and therefore n - 1 bars.
+    // Example (n=4, k=6): ***|*||** represents [0,0,0,1,3,3].
+    let positions = if n == 0 {
+        k.saturating_sub(1)
+    } else {
+        (n - 1).checked_add(k)?
+    };
+    binomial(positions, k)
+}
+
+/// Returns the count of `k`-permutations of `n` elements, that is `n * (n - 1) * ... * (n - k + 1)`,
+/// or `None` if it would overflow.
+///
+/// This is the length of [`.permutations(k)`](crate::Itertools::permutations) over `n` elements.
+///
+/// ```
+/// use itertools::count::falling_factorial;
+///
+/// assert_eq!(falling_factorial(5, 2), Some(20));
+/// assert_eq!(falling_factorial(5, 0), Some(1));
+/// assert_eq!(falling_factorial(2, 5), Some(0));
+/// ```
+pub fn falling_factorial(n: usize, k: usize) -> Option<usize> {
+    if n < k {
+        return Some(0);
+    }
+    (n - k + 1..=n).try_fold(1usize, |acc, i| acc.checked_mul(i))
+}
+
+/// Returns the count of subsets of `n` elements, that is `2^n`, or `None` if it would overflow.
+///
+/// This is the length of [`.powerset()`](crate::Itertools::powerset) over `n` elements.
+///
+/// ```
+/// use itertools::count::powerset_len;
+///
+/// assert_eq!(powerset_len(3), Some(8));
+/// assert_eq!(powerset_len(1000), None);
+/// ```
+pub fn powerset_len(n: usize) -> Option<usize> {
+    1usize.checked_shl(u32::try_from(n).ok()?)
+}
+
+/// Returns the count of items of the cartesian product of iterators of the given lengths,
+/// or `None` if it would overflow.
+///
+/// This is the length of
+/// [`.multi_cartesian_product()`](crate::Itertools::multi_cartesian_product) over
+/// iterators of these lengths.
+///
+/// ```
+/// use itertools::count::product_len;
+///
+/// assert_eq!(product_len([2, 3, 4].iter().copied()), Some(24));
+/// assert_eq!(product_len([2, 0, 4].iter().copied()), Some(0));
+/// assert_eq!(product_len([usize::MAX, 2].iter().copied()), None);
+/// assert_eq!(product_len([usize::MAX, 2, 0].iter().copied()), Some(0));
+/// ```
+pub fn product_len<I>(lens: I) -> Option<usize>
+where
+    I: IntoIterator<Item = usize>,
+{
+    let mut product = Some(1usize);
+    for len in lens {
+        // An empty factor makes the product empty even after an overflow.
+        if len == 0 {
+            return Some(0);
+        }
+        product = product.and_then(|product| product.checked_mul(len));
+    }
+    product
+}
+
+#[test]
+fn test_binomial() {
+    // With the first row: [1, 0, 0, ...] and the first column full of 1s, we check
+    // row by row the recurrence relation of binomials (which is an equivalent definition).
+    // For n >= 1 and k >= 1 we have:
+    //   binomial(n, k) == binomial(n - 1, k - 1) + binomial(n - 1, k)
+    const LIMIT: usize = 500;
+    let mut row = vec![Some(0); LIMIT + 1];
+    row[0] = Some(1);
+    for n in 0..=LIMIT {
+        for k in 0..=LIMIT {
+            assert_eq!(row[k], binomial(n, k));
+            assert_eq!(
+                row[k],
+                binomial_u128(n, k).and_then(|c| usize::try_from(c).ok())
+            );
+        }
+        row = std::iter::once(Some(1))
+            .chain((1..=LIMIT).map(|k| row[k - 1]?.checked_add(row[k]?)))
+            .collect();
+    }
+}
@@ -0,0 +1,266 @@
+use alloc::vec::Vec;
+use std::fmt;
+use std::iter::FusedIterator;
+
+use super::lazy_buffer::LazyBuffer;
+use crate::size_hint::{self, SizeHint};
+
+/// Loads `pool` until it holds the element at `index`, returns false if there is none.
+fn has<I: Iterator>(pool: &mut LazyBuffer<I>, index: usize) -> bool {
+    pool.prefill(index.saturating_add(1));
+    index < pool.len()
+}
+
+/// An iterator adaptor that iterates over the cartesian product of two iterators
+/// in Cantor diagonal order.
+///
+/// See [`.diagonal_product()`](crate::Itertools::diagonal_product) for more information.
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+pub struct DiagonalProduct<I: Iterator, J: Iterator> {
+    a: LazyBuffer<I>,
+    b: LazyBuffer<J>,
+    /// The indices of the last generated pair.
+    i: usize,
+    j: usize,
+    first: bool,
+    done: bool,
+    /// The count of generated pairs.
+    generated: usize,
+}
+
+impl<I, J> Clone for DiagonalProduct<I, J>
+where
+    I: Clone + Iterator,
+    I::Item: Clone,
+    J: Clone + Iterator,
+    J::Item: Clone,
+{
+    clone_fields!(a, b, i, j, first, done, generated);
+}
+
+impl<I, J> fmt::Debug for DiagonalProduct<I, J>
+where
+    I: Iterator + fmt::Debug,
+    I::Item: fmt::Debug,
+    J: Iterator + fmt::Debug,
+    J::Item: fmt::Debug,
+{
+    debug_fmt_fields!(DiagonalProduct, a, b, i, j, first, done, generated);
+}
+
+/// Create a new `DiagonalProduct` from two iterators.
+pub fn diagonal_product<I, J>(i: I, j: J) -> DiagonalProduct<I, J::IntoIter>
+where
+    I: Iterator,
+    J: IntoIterator,
+{
+    DiagonalProduct {
+        a: LazyBuffer::new(i),
+        b: LazyBuffer::new(j.into_iter()),
+        i: 0,
+        j: 0,
+        first: true,
+        done: false,
+        generated: 0,
+    }
+}
+
+impl<I, J> DiagonalProduct<I, J>
+where
+    I: Iterator,
+    J: Iterator,
+{
+    /// Moves to the next pair of indices, returns false if there is none.
+    fn advance(&mut self) -> bool {
+        if self.first {
+            self.first = false;
+            return has(&mut self.a, 0) && has(&mut self.b, 0);
+        }
+        // The next pair on the same diagonal...
+        if self.j > 0 && has(&mut self.a, self.i + 1) {
+            self.i += 1;
+            self.j -= 1;
+            return true;
+        }
+        // ...or the first pair of the next diagonal, with the greatest possible `j`.
+        let diagonal = self.i + self.j + 1;
+        self.b.prefill(diagonal.saturating_add(1));
+        let j = diagonal.min(self.b.len() - 1);
+        if has(&mut self.a, diagonal - j) {
+            self.i = diagonal - j;
+            self.j = j;
+            true
+        } else {
+            false
+        }
+    }
+}
+
+impl<I, J> Iterator for DiagonalProduct<I, J>
+where
+    I: Iterator,
+    I::Item: Clone,
+    J: Iterator,
+    J::Item: Clone,
+{
+    type Item = (I::Item, J::Item);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done || !self.advance() {
+            self.done = true;
+            return None;
+        }
+        self.generated = self.generated.saturating_add(1);
+        Some((self.a[self.i].clone(), self.b[self.j].clone()))
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        if self.done {
+            return (0, Some(0));
+        }
+        let total = size_hint::mul(self.a.size_hint(), self.b.size_hint());
+        size_hint::sub_scalar(total, self.generated)
+    }
+}
+
+impl<I, J> FusedIterator for DiagonalProduct<I, J>
+where
+    I: Iterator,
+    I::Item: Clone,
+    J: Iterator,
+    J::Item: Clone,
+{
+}
+
+/// An iterator adaptor that iterates over the cartesian product of multiple iterators
+/// of type `I` in Cantor diagonal order.
+///
+/// See [`.multi_diagonal_product()`](crate::Itertools::multi_diagonal_product)
+/// for more information.
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+pub struct MultiDiagonalProduct<I: Iterator> {
+    pools: Vec<LazyBuffer<I>>,
+    /// The indices of the last generated item.
+    indices: Vec<usize>,
+    first: bool,
+    done: bool,
+    /// The count of generated items.
+    generated: usize,
+}
+
+impl<I> Clone for MultiDiagonalProduct<I>
+where
+    I: Clone + Iterator,
+    I::Item: Clone,
+{
+    clone_fields!(pools, indices, first, done, generated);
+}
+
+impl<I> fmt::Debug for MultiDiagonalProduct<I>
+where
+    I: Iterator + fmt::Debug,
+    I::Item: fmt::Debug,
+{
+    debug_fmt_fields!(MultiDiagonalProduct, pools, indices, first, done, generated);
+}
+
+/// Create a new `MultiDiagonalProduct` from an iterator of iterators.
+pub fn multi_diagonal_product<H>(
+    iters: H,
+) -> MultiDiagonalProduct<<H::Item as IntoIterator>::IntoIter>
+where
+    H: Iterator,
+    H::Item: IntoIterator,
+{
+    let pools: Vec<_> = iters
+        .map(|iter| LazyBuffer::new(iter.into_iter()))
+        .collect();
+    MultiDiagonalProduct {
+        indices: alloc::vec![0; pools.len()],
+        pools,
+        first: true,
+        done: false,
+        generated: 0,
+    }
+}
+
+impl<I: Iterator> MultiDiagonalProduct<I> {
+    /// Sets `indices[start..]` to the smallest indices (in lexicographic order) whose sum is
+    /// `remaining`, returns false if there are not enough elements.
+    fn fill_from(&mut self, start: usize, mut remaining: usize) -> bool {
+        let Self { pools, indices, .. } = self;
+        // The last indices are as great as possible.
+        for (pool, index) in pools[start..].iter_mut().zip(&mut indices[start..]).rev() {
+            pool.prefill(remaining.saturating_add(1));
+            if pool.len() == 0 {
+                return false;
+            }
+            *index = remaining.min(pool.len() - 1);
+            remaining -= *index;
+        }
+        remaining == 0
+    }
+
+    /// Moves to the next indices, returns false if there are none.
+    fn advance(&mut self) -> bool {
+        if self.first {
+            self.first = false;
+            return self.fill_from(0, 0);
+        }
+        // Look for the next indices on the same diagonal: the last index which can be
+        // incremented while decrementing the sum of the ones to its right...
+        let mut suffix = 0;
+        for p in (0..self.indices.len()).rev() {
+            if suffix > 0 && has(&mut self.pools[p], self.indices[p] + 1) {
+                self.indices[p] += 1;
+                // ...which can hold one less, since they held `suffix`.
+                return self.fill_from(p + 1, suffix - 1);
+            }
+            suffix += self.indices[p];
+        }
+        // Otherwise, the first indices of the next diagonal.
+        self.fill_from(0, suffix + 1)
+    }
+}
+
+impl<I> Iterator for MultiDiagonalProduct<I>
+where
+    I: Iterator,
+    I::Item: Clone,
+{
+    type Item = Vec<I::Item>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done || !self.advance() {
+            self.done = true;
+            return None;
+        }
+        self.generated = self.generated.saturating_add(1);
+        Some(
+            self.pools
+                .iter()
+                .zip(&self.indices)
+                .map(|(pool, &index)| pool[index].clone())
+                .collect(),
+        )
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        if self.done {
+            return (0, Some(0));
+        }
+        let total = self
+            .pools
+            .iter()
+            .map(|pool| pool.size_hint())
+            .fold((1, Some(1)), size_hint::mul);
+        size_hint::sub_scalar(total, self.generated)
+    }
+}
+
+impl<I> FusedIterator for MultiDiagonalProduct<I>
+where
+    I: Iterator,
+    I::Item: Clone,
+{
+}
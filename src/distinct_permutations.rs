@@ -0,0 +1,208 @@
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use std::cmp::Ordering;
+use std::fmt;
+use std::iter::FusedIterator;
+
+use super::lazy_buffer::LazyBuffer;
+use crate::count::{binomial, falling_factorial};
+use crate::multiset_cmp::{MultisetByOrd, MultisetCmp};
+
+/// An iterator adaptor that iterates through all the distinct permutations of the
+/// elements from an iterator, in lexicographic order.
+///
+/// See [`.distinct_permutations()`](crate::Itertools::distinct_permutations) and
+/// [`.distinct_permutations_by()`](crate::Itertools::distinct_permutations_by) for
+/// more information.
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+pub struct DistinctPermutationsBy<I: Iterator, F> {
+    vals: LazyBuffer<I>,
+    state: DistinctPermutationState,
+    cmp: F,
+}
+
+/// An iterator adaptor that iterates through all the distinct permutations of the
+/// elements from an iterator, in lexicographic order.
+///
+/// See [`.distinct_permutations()`](crate::Itertools::distinct_permutations) for
+/// more information.
+pub type DistinctPermutations<I> = DistinctPermutationsBy<I, MultisetByOrd>;
+
+impl<I, F> Clone for DistinctPermutationsBy<I, F>
+where
+    I: Clone + Iterator,
+    I::Item: Clone,
+    F: Clone,
+{
+    clone_fields!(vals, state, cmp);
+}
+
+impl<I, F> fmt::Debug for DistinctPermutationsBy<I, F>
+where
+    I: Iterator + fmt::Debug,
+    I::Item: fmt::Debug,
+{
+    debug_fmt_fields!(DistinctPermutationsBy, vals, state);
+}
+
+#[derive(Clone, Debug)]
+enum DistinctPermutationState {
+    /// No permutation generated yet.
+    Start,
+    /// All values from the iterator are loaded, `indices` is the last generated permutation.
+    Loaded {
+        indices: Box<[usize]>,
+        /// The count of permutations after `indices`, or `None` if it would overflow.
+        remaining: Option<usize>,
+    },
+    /// No permutation left to generate.
+    End,
+}
+
+/// Create a new `DistinctPermutationsBy` from an iterator and a comparison function.
+pub fn distinct_permutations_by<I, F>(iter: I, cmp: F) -> DistinctPermutationsBy<I, F>
+where
+    I: Iterator,
+    F: MultisetCmp<I::Item>,
+{
+    DistinctPermutationsBy {
+        vals: LazyBuffer::new(iter),
+        state: DistinctPermutationState::Start,
+        cmp,
+    }
+}
+
+/// Create a new `DistinctPermutations` from an iterator.
+pub fn distinct_permutations<I>(iter: I) -> DistinctPermutations<I>
+where
+    I: Iterator,
+    I::Item: Ord,
+{
+    distinct_permutations_by(iter, MultisetByOrd)
+}
+
+impl<I, F> DistinctPermutationsBy<I, F>
+where
+    I: Iterator,
+    F: MultisetCmp<I::Item>,
+{
+    /// Loads all values and returns the smallest permutation with the total count of
+    /// distinct permutations.
+    fn load(&mut self) -> (Box<[usize]>, Option<usize>) {
+        let Self { vals, cmp, .. } = self;
+        vals.fill();
+        let mut indices: Box<[usize]> = (0..vals.len()).collect();
+        indices.sort_by(|&a, &b| cmp.cmp_items(&vals[a], &vals[b]));
+        // The multinomial coefficient of the multiplicities of the distinct values,
+        // computed as a product of binomial coefficients over the runs of equal values.
+        let n = indices.len();
+        let mut count = Some(1usize);
+        let mut start = 0;
+        for end in 1..=n {
+            if end == n
+                || cmp
+                    .cmp_items(&vals[indices[end - 1]], &vals[indices[end]])
+                    .is_ne()
+            {
+                count = count.and_then(|c| c.checked_mul(binomial(end, end - start)?));
+                start = end;
+            }
+        }
+        (indices, count)
+    }
+}
+
+impl<I, F> Iterator for DistinctPermutationsBy<I, F>
+where
+    I: Iterator,
+    I::Item: Clone,
+    F: MultisetCmp<I::Item>,
+{
+    type Item = Vec<I::Item>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.state {
+            DistinctPermutationState::Start => {
+                let (indices, count) = self.load();
+                let item = self.vals.get_at(&indices);
+                let remaining = count.map(|count| count - 1);
+                self.state = DistinctPermutationState::Loaded { indices, remaining };
+                Some(item)
+            }
+            DistinctPermutationState::Loaded {
+                ref mut indices,
+                ref mut remaining,
+            } => {
+                let Self { vals, cmp, .. } = self;
+                if advance(indices, |a, b| cmp.cmp_items(&vals[a], &vals[b])) {
+                    self.state = DistinctPermutationState::End;
+                    return None;
+                }
+                if let Some(remaining) = remaining {
+                    *remaining -= 1;
+                }
+                Some(vals.get_at(indices))
+            }
+            DistinctPermutationState::End => None,
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match self.state {
+            DistinctPermutationState::Start => {
+                // Between one permutation when all values are equal and `n!` when they are all distinct.
+                let (_, upp) = self.vals.size_hint();
+                let upp = upp.and_then(|n| falling_factorial(n, n));
+                (1, upp)
+            }
+            DistinctPermutationState::Loaded { remaining, .. } => {
+                (remaining.unwrap_or(usize::MAX), remaining)
+            }
+            DistinctPermutationState::End => (0, Some(0)),
+        }
+    }
+
+    fn count(mut self) -> usize {
+        match self.state {
+            DistinctPermutationState::Start => self.load().1.unwrap(),
+            DistinctPermutationState::Loaded { remaining, .. } => remaining.unwrap(),
+            DistinctPermutationState::End => 0,
+        }
+    }
+}
+
+impl<I, F> FusedIterator for DistinctPermutationsBy<I, F>
+where
+    I: Iterator,
+    I::Item: Clone,
+    F: MultisetCmp<I::Item>,
+{
+}
+
+/// Moves `indices` to the next permutation in lexicographic order according to `cmp`,
+/// skipping the permutations that only swap equal values.
+///
+/// Returns true if we've run out of permutations, false otherwise.
+fn advance<C>(indices: &mut [usize], mut cmp: C) -> bool
+where
+    C: FnMut(usize, usize) -> Ordering,
+{
+    let n = indices.len();
+    // Find the rightmost position that can be increased...
+    let i = match (1..n)
+        .rev()
+        .find(|&i| cmp(indices[i - 1], indices[i]) == Ordering::Less)
+    {
+        Some(i) => i - 1,
+        None => return true,
+    };
+    // ...swap it with the rightmost greater value on its right...
+    let j = (i + 1..n)
+        .rev()
+        .find(|&j| cmp(indices[i], indices[j]) == Ordering::Less)
+        .unwrap();
+    indices.swap(i, j);
+    // ...and reset the tail to its smallest arrangement.
+    indices[i + 1..].reverse();
+    false
+}
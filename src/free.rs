@@ -17,6 +17,10 @@ use crate::Itertools;
 
 pub use crate::adaptors::{interleave, put_back};
 #[cfg(feature = "use_alloc")]
+pub use crate::index_combinatorics::{combination_indices, permutation_indices, product_indices};
+#[cfg(feature = "use_alloc")]
+pub use crate::interleave_all::{interleave_all, interleave_all_shortest};
+#[cfg(feature = "use_alloc")]
 pub use crate::kmerge_impl::kmerge;
 pub use crate::merge_join::{merge, merge_join_by};
 #[cfg(feature = "use_alloc")]
@@ -1,7 +1,10 @@
-#![cfg(feature = "use_std")]
-
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
+#[cfg(feature = "use_std")]
 use core::hash::BuildHasher;
+#[cfg(feature = "use_std")]
 use std::collections::HashMap;
+#[cfg(feature = "use_std")]
 use std::hash::Hash;
 use std::iter::Iterator;
 
@@ -9,6 +12,7 @@ use std::iter::Iterator;
 ///
 /// See [`.into_group_map()`](crate::Itertools::into_group_map)
 /// for more information.
+#[cfg(feature = "use_std")]
 pub fn into_group_map_with_hasher<I, K, V, S>(iter: I, hash_builder: S) -> HashMap<K, Vec<V>, S>
 where
     I: Iterator<Item = (K, V)>,
@@ -24,6 +28,7 @@ where
     lookup
 }
 
+#[cfg(feature = "use_std")]
 pub fn into_group_map_by_with_hasher<I, K, V, F, S>(
     iter: I,
     mut f: F,
@@ -37,3 +42,30 @@ where
 {
     into_group_map_with_hasher(iter.map(|v| (f(&v), v)), hash_builder)
 }
+
+/// Return a `BTreeMap` of keys mapped to a list of their corresponding values.
+///
+/// See [`.into_group_btree_map()`](crate::Itertools::into_group_btree_map)
+/// for more information.
+pub fn into_group_btree_map<I, K, V>(iter: I) -> BTreeMap<K, Vec<V>>
+where
+    I: Iterator<Item = (K, V)>,
+    K: Ord,
+{
+    let mut lookup = BTreeMap::<K, Vec<V>>::new();
+
+    iter.for_each(|(key, val)| {
+        lookup.entry(key).or_default().push(val);
+    });
+
+    lookup
+}
+
+pub fn into_group_btree_map_by<I, K, V, F>(iter: I, mut f: F) -> BTreeMap<K, Vec<V>>
+where
+    I: Iterator<Item = V>,
+    K: Ord,
+    F: FnMut(&V) -> K,
+{
+    into_group_btree_map(iter.map(|v| (f(&v), v)))
+}
@@ -0,0 +1,90 @@
+use crate::grouping_map::{self, GroupingBackend, GroupingMap, MapForGrouping, MapValues};
+use alloc::collections::BTreeMap;
+use std::convert::Infallible;
+
+/// Creates a new `GroupingBTreeMap` from `iter`
+pub fn new<I, K, V>(iter: I) -> GroupingBTreeMap<I>
+where
+    I: Iterator<Item = (K, V)>,
+    K: Ord,
+{
+    grouping_map::new(iter, BTreeMapBackend)
+}
+
+/// `GroupingBTreeMapBy` is an intermediate struct for efficient group-and-fold operations.
+///
+/// See [`GroupingBTreeMap`] for more information.
+pub type GroupingBTreeMapBy<I, F> = GroupingBTreeMap<MapForGrouping<I, F>>;
+
+/// `GroupingBTreeMap` is a [`GroupingMap`] that gathers the groups in a `BTreeMap`.
+///
+/// It offers the same operations, but they return a `BTreeMap`: keys only need
+/// to be `Ord` and the groups are visited in key order.
+///
+/// ```
+/// use itertools::Itertools;
+///
+/// let lookup = vec![3, 1, 4, 1, 5, 9, 2, 6].into_iter()
+///     .into_grouping_btree_map_by(|&n| n % 3)
+///     .sum();
+///
+/// itertools::assert_equal(lookup, vec![(0, 3 + 9 + 6), (1, 1 + 4 + 1), (2, 5 + 2)]);
+/// ```
+pub type GroupingBTreeMap<I> = GroupingMap<I, BTreeMapBackend>;
+
+/// The second type parameter of a [`GroupingMap`] that gathers the groups in a `BTreeMap`.
+///
+/// See [`GroupingBTreeMap`] for more information.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct BTreeMapBackend;
+
+impl<K: Ord, V> GroupingBackend<K, V> for BTreeMapBackend {
+    type Map = BTreeMap<K, V>;
+    type Error = Infallible;
+    type Output = BTreeMap<K, V>;
+
+    fn new_map(self) -> Self::Map {
+        BTreeMap::new()
+    }
+
+    fn remove(map: &mut Self::Map, key: &K) -> Result<Option<V>, Self::Error> {
+        Ok(map.remove(key))
+    }
+
+    fn insert(map: &mut Self::Map, key: K, value: V) {
+        map.insert(key, value);
+    }
+
+    fn entry<F>(map: &mut Self::Map, key: K, default: F) -> Result<&mut V, Self::Error>
+    where
+        F: FnOnce() -> V,
+    {
+        Ok(map.entry(key).or_insert_with(default))
+    }
+
+    fn finish(map: Result<Self::Map, Self::Error>) -> Self::Output {
+        map.unwrap_or_else(|never| match never {})
+    }
+
+    fn for_each_mut<F>(output: &mut Self::Output, mut f: F)
+    where
+        F: FnMut(&K, &mut V),
+    {
+        output.iter_mut().for_each(|(key, val)| f(key, val));
+    }
+}
+
+impl<K: Ord, V, W> MapValues<K, V, W> for BTreeMapBackend {
+    fn map_values<F>(output: BTreeMap<K, V>, mut f: F) -> BTreeMap<K, W>
+    where
+        F: FnMut(&K, V) -> W,
+    {
+        output
+            .into_iter()
+            .map(|(key, val)| {
+                let val = f(&key, val);
+                (key, val)
+            })
+            .collect()
+    }
+}
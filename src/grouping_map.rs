@@ -1,13 +1,19 @@
-use crate::{
-    adaptors::map::{MapSpecialCase, MapSpecialCaseFn},
-    MinMaxResult,
-};
+use crate::adaptors::map::{MapSpecialCase, MapSpecialCaseFn};
+use crate::k_smallest;
+use crate::MinMaxResult;
+#[cfg(feature = "use_std")]
+use crate::ProcessResults;
+use alloc::vec::Vec;
+#[cfg(feature = "use_std")]
 use core::hash::BuildHasher;
-use std::collections::HashMap;
+use std::cmp::Ordering;
+#[cfg(feature = "use_std")]
+use std::collections::{hash_map::RandomState, HashMap, HashSet};
+use std::convert::Infallible;
+#[cfg(feature = "use_std")]
 use std::hash::Hash;
 use std::iter::Iterator;
 use std::ops::{Add, Mul};
-use std::{cmp::Ordering, collections::hash_map::RandomState};
 
 /// A wrapper to allow for an easy [`into_grouping_map_by`](crate::Itertools::into_grouping_map_by)
 pub type MapForGrouping<I, F> = MapSpecialCase<I, GroupingMapFn<F>>;
@@ -36,41 +42,201 @@ pub(crate) fn new_map_for_grouping<K, I: Iterator, F: FnMut(&I::Item) -> K>(
     }
 }
 
-/// Creates a new `GroupingMap` from `iter`
-pub fn new<I, K, V, S>(iter: I, hash_builder: S) -> GroupingMap<I, S>
+mod private_grouping_backend {
+    #[cfg(feature = "use_std")]
+    use core::hash::BuildHasher;
+
+    pub trait Sealed {}
+
+    #[cfg(feature = "use_std")]
+    impl<S: BuildHasher> Sealed for S {}
+    impl Sealed for crate::structs::BTreeMapBackend {}
+    impl Sealed for crate::structs::VecBackend {}
+}
+
+/// The kind of map in which a [`GroupingMap`] gathers the values of its groups,
+/// given by its second type parameter `S`.
+///
+/// Any `BuildHasher` gathers them in a `HashMap<K, V, S>`,
+/// [`BTreeMapBackend`](crate::structs::BTreeMapBackend) in a `BTreeMap<K, V>` and
+/// [`VecBackend`](crate::structs::VecBackend) in a `Vec<Option<V>>` indexed by key.
+/// The operations of a `GroupingMap` return the [`Output`](Self::Output) of its backend,
+/// which is one of these maps, wrapped in a `Result` for `VecBackend`.
+///
+/// This trait is sealed: it only appears in the bounds and return types of the
+/// `GroupingMap` operations, and can not be implemented outside of itertools.
+///
+/// ```
+/// use itertools::structs::GroupingMap;
+/// use itertools::traits::GroupingBackend;
+/// use itertools::Itertools;
+///
+/// fn sum_lengths<I, S>(groups: GroupingMap<I, S>) -> <S as GroupingBackend<char, usize>>::Output
+/// where
+///     I: Iterator<Item = (char, usize)>,
+///     S: GroupingBackend<char, usize>,
+/// {
+///     groups.sum()
+/// }
+///
+/// let words = ["apple", "bean", "avocado"];
+/// let grouped = || words.iter().map(|w| (w.chars().next().unwrap(), w.len()));
+/// let lookup = sum_lengths(grouped().into_grouping_map());
+/// assert_eq!(lookup[&'a'], 12);
+/// let lookup = sum_lengths(grouped().into_grouping_btree_map());
+/// itertools::assert_equal(lookup, vec![('a', 12), ('b', 4)]);
+/// ```
+pub trait GroupingBackend<K, V>: private_grouping_backend::Sealed {
+    /// The map being filled.
+    type Map;
+    /// The error that stops the grouping, for a key the map can not hold.
+    type Error;
+    /// What the operations of the `GroupingMap` return.
+    type Output;
+
+    /// Creates an empty map.
+    fn new_map(self) -> Self::Map;
+
+    /// Removes the value of `key` from `map` and returns it.
+    fn remove(map: &mut Self::Map, key: &K) -> Result<Option<V>, Self::Error>;
+
+    /// Inserts the value of `key`, which was removed just before.
+    fn insert(map: &mut Self::Map, key: K, value: V);
+
+    /// Returns the value of `key`, inserting `default()` first if there is none.
+    fn entry<F>(map: &mut Self::Map, key: K, default: F) -> Result<&mut V, Self::Error>
+    where
+        F: FnOnce() -> V;
+
+    /// Turns the filled map, or the error that stopped the grouping, into the output.
+    fn finish(map: Result<Self::Map, Self::Error>) -> Self::Output;
+
+    /// Calls `f` on every key and value of `output`.
+    fn for_each_mut<F>(output: &mut Self::Output, f: F)
+    where
+        F: FnMut(&K, &mut V);
+}
+
+/// A [`GroupingBackend`] able to turn its results for values `V` into results for values `W`.
+///
+/// It is needed by the operations of a [`GroupingMap`] that compute the result of each group
+/// from an intermediate accumulator, like [`mean`](GroupingMap::mean).
+/// Like `GroupingBackend`, this trait is sealed.
+pub trait MapValues<K, V, W>: GroupingBackend<K, V> + GroupingBackend<K, W> {
+    /// Applies `f` to the key and value of every group of `output`.
+    fn map_values<F>(
+        output: <Self as GroupingBackend<K, V>>::Output,
+        f: F,
+    ) -> <Self as GroupingBackend<K, W>>::Output
+    where
+        F: FnMut(&K, V) -> W;
+}
+
+/// What the operations of a [`GroupingMap`] with the backend `S` return.
+type Grouped<S, K, V> = <S as GroupingBackend<K, V>>::Output;
+
+#[cfg(feature = "use_std")]
+impl<K, V, S> GroupingBackend<K, V> for S
 where
-    I: Iterator<Item = (K, V)>,
     K: Hash + Eq,
     S: BuildHasher,
 {
-    GroupingMap { iter, hash_builder }
+    type Map = HashMap<K, V, S>;
+    type Error = Infallible;
+    type Output = HashMap<K, V, S>;
+
+    fn new_map(self) -> Self::Map {
+        HashMap::with_hasher(self)
+    }
+
+    fn remove(map: &mut Self::Map, key: &K) -> Result<Option<V>, Self::Error> {
+        Ok(map.remove(key))
+    }
+
+    fn insert(map: &mut Self::Map, key: K, value: V) {
+        map.insert(key, value);
+    }
+
+    fn entry<F>(map: &mut Self::Map, key: K, default: F) -> Result<&mut V, Self::Error>
+    where
+        F: FnOnce() -> V,
+    {
+        Ok(map.entry(key).or_insert_with(default))
+    }
+
+    fn finish(map: Result<Self::Map, Self::Error>) -> Self::Output {
+        map.unwrap_or_else(|never| match never {})
+    }
+
+    fn for_each_mut<F>(output: &mut Self::Output, mut f: F)
+    where
+        F: FnMut(&K, &mut V),
+    {
+        output.iter_mut().for_each(|(key, val)| f(key, val));
+    }
+}
+
+#[cfg(feature = "use_std")]
+impl<K, V, W, S> MapValues<K, V, W> for S
+where
+    K: Hash + Eq,
+    S: BuildHasher + Clone,
+{
+    fn map_values<F>(output: HashMap<K, V, S>, mut f: F) -> HashMap<K, W, S>
+    where
+        F: FnMut(&K, V) -> W,
+    {
+        let mut mapped = HashMap::with_capacity_and_hasher(output.len(), output.hasher().clone());
+        mapped.extend(output.into_iter().map(|(key, val)| {
+            let val = f(&key, val);
+            (key, val)
+        }));
+        mapped
+    }
+}
+
+/// Creates a new `GroupingMap` from `iter`
+pub fn new<I, S>(iter: I, backend: S) -> GroupingMap<I, S> {
+    GroupingMap { iter, backend }
 }
 
 /// `GroupingMapBy` is an intermediate struct for efficient group-and-fold operations.
 ///
 /// See [`GroupingMap`] for more information.
+#[cfg(feature = "use_std")]
 pub type GroupingMapBy<I, F, S = RandomState> = GroupingMap<MapForGrouping<I, F>, S>;
 
-/// `GroupingMap` is an intermediate struct for efficient group-and-fold operations.
-/// It groups elements by their key and at the same time fold each group
-/// using some aggregating operation.
-///
-/// No method on this struct performs temporary allocations.
-#[derive(Clone, Debug)]
-#[must_use = "GroupingMap is lazy and do nothing unless consumed"]
-pub struct GroupingMap<I, S = RandomState>
-where
-    S: BuildHasher,
-{
-    iter: I,
-    hash_builder: S,
+macro_rules! grouping_map_struct {
+    ($($backend:tt)*) => {
+        /// `GroupingMap` is an intermediate struct for efficient group-and-fold operations.
+        /// It groups elements by their key and at the same time fold each group
+        /// using some aggregating operation.
+        ///
+        /// The groups are gathered in a `HashMap` built with the `BuildHasher` `S`, or in
+        /// another kind of map for some other `S`: see
+        /// [`GroupingBTreeMap`](crate::structs::GroupingBTreeMap) to gather them in a `BTreeMap`
+        /// and [`GroupingVec`](crate::structs::GroupingVec) to store them in a `Vec` indexed by key.
+        /// The operations return the [`Output`](crate::traits::GroupingBackend::Output) of this
+        /// [`GroupingBackend`](crate::traits::GroupingBackend).
+        ///
+        /// No method on this struct performs temporary allocations.
+        #[derive(Clone, Debug)]
+        #[must_use = "GroupingMap is lazy and do nothing unless consumed"]
+        pub struct GroupingMap<I, $($backend)*> {
+            iter: I,
+            backend: S,
+        }
+    };
 }
 
+#[cfg(feature = "use_std")]
+grouping_map_struct!(S = RandomState);
+#[cfg(not(feature = "use_std"))]
+grouping_map_struct!(S);
+
 impl<I, K, V, S> GroupingMap<I, S>
 where
     I: Iterator<Item = (K, V)>,
-    K: Hash + Eq,
-    S: BuildHasher,
 {
     /// This is the generic way to perform any operation on a `GroupingMap`.
     /// It's suggested to use this method only to implement custom operations
@@ -78,7 +244,7 @@ where
     ///
     /// Groups elements from the `GroupingMap` source by key and applies `operation` to the elements
     /// of each group sequentially, passing the previously accumulated value, a reference to the key
-    /// and the current element as arguments, and stores the results in an `HashMap`.
+    /// and the current element as arguments, and stores the results in a map.
     ///
     /// The `operation` function is invoked on each element with the following parameters:
     ///  - the current value of the accumulator of the group if there is currently one;
@@ -88,7 +254,7 @@ where
     /// If `operation` returns `Some(element)` then the accumulator is updated with `element`,
     /// otherwise the previous accumulation is discarded.
     ///
-    /// Return a `HashMap` associating the key of each group with the result of aggregation of
+    /// Return a map associating the key of each group with the result of aggregation of
     /// that group's elements. If the aggregation of the last element of a group discards the
     /// accumulator then there won't be an entry associated to that group's key.
     ///
@@ -112,20 +278,15 @@ where
     /// assert_eq!(lookup[&3], 7);
     /// assert_eq!(lookup.len(), 3);      // The final keys are only 0, 1 and 2
     /// ```
-    pub fn aggregate<FO, R>(self, mut operation: FO) -> HashMap<K, R, S>
+    pub fn aggregate<FO, R>(self, mut operation: FO) -> Grouped<S, K, R>
     where
+        S: GroupingBackend<K, R>,
         FO: FnMut(Option<R>, &K, V) -> Option<R>,
     {
-        let mut destination_map = HashMap::with_hasher(self.hash_builder);
-
-        self.iter.for_each(|(key, val)| {
-            let acc = destination_map.remove(&key);
-            if let Some(op_res) = operation(acc, &key, val) {
-                destination_map.insert(key, op_res);
-            }
-        });
-
-        destination_map
+        match self.try_aggregate(|acc, key, val| Ok::<_, Infallible>(operation(acc, key, val))) {
+            Ok(destination_map) => destination_map,
+            Err(never) => match never {},
+        }
     }
 
     /// Groups elements from the `GroupingMap` source by key and applies `operation` to the elements
@@ -139,7 +300,7 @@ where
     ///  - a reference to the key of the group this element belongs to;
     ///  - the element from the source being accumulated.
     ///
-    /// Return a `HashMap` associating the key of each group with the result of folding that group's elements.
+    /// Return a map associating the key of each group with the result of folding that group's elements.
     ///
     /// ```
     /// use itertools::Itertools;
@@ -161,8 +322,9 @@ where
     /// assert_eq!(lookup[&2].acc, 2 + 5);
     /// assert_eq!(lookup.len(), 3);
     /// ```
-    pub fn fold_with<FI, FO, R>(self, mut init: FI, mut operation: FO) -> HashMap<K, R, S>
+    pub fn fold_with<FI, FO, R>(self, mut init: FI, mut operation: FO) -> Grouped<S, K, R>
     where
+        S: GroupingBackend<K, R>,
         FI: FnMut(&K, &V) -> R,
         FO: FnMut(R, &K, V) -> R,
     {
@@ -183,7 +345,7 @@ where
     ///  - a reference to the key of the group this element belongs to;
     ///  - the element from the source being accumulated.
     ///
-    /// Return a `HashMap` associating the key of each group with the result of folding that group's elements.
+    /// Return a map associating the key of each group with the result of folding that group's elements.
     ///
     /// ```
     /// use itertools::Itertools;
@@ -197,8 +359,9 @@ where
     /// assert_eq!(lookup[&2], 2 + 5);
     /// assert_eq!(lookup.len(), 3);
     /// ```
-    pub fn fold<FO, R>(self, init: R, operation: FO) -> HashMap<K, R, S>
+    pub fn fold<FO, R>(self, init: R, operation: FO) -> Grouped<S, K, R>
     where
+        S: GroupingBackend<K, R>,
         R: Clone,
         FO: FnMut(R, &K, V) -> R,
     {
@@ -216,7 +379,7 @@ where
     ///  - a reference to the key of the group this element belongs to;
     ///  - the element from the source being accumulated.
     ///
-    /// Return a `HashMap` associating the key of each group with the result of folding that group's elements.
+    /// Return a map associating the key of each group with the result of folding that group's elements.
     ///
     /// [`fold`]: GroupingMap::fold
     ///
@@ -232,8 +395,9 @@ where
     /// assert_eq!(lookup[&2], 2 + 5);
     /// assert_eq!(lookup.len(), 3);
     /// ```
-    pub fn reduce<FO>(self, mut operation: FO) -> HashMap<K, V, S>
+    pub fn reduce<FO>(self, mut operation: FO) -> Grouped<S, K, V>
     where
+        S: GroupingBackend<K, V>,
         FO: FnMut(V, &K, V) -> V,
     {
         self.aggregate(|acc, key, val| {
@@ -246,8 +410,9 @@ where
 
     /// See [`.reduce()`](GroupingMap::reduce).
     #[deprecated(note = "Use .reduce() instead", since = "0.13.0")]
-    pub fn fold_first<FO>(self, operation: FO) -> HashMap<K, V, S>
+    pub fn fold_first<FO>(self, operation: FO) -> Grouped<S, K, V>
     where
+        S: GroupingBackend<K, V>,
         FO: FnMut(V, &K, V) -> V,
     {
         self.reduce(operation)
@@ -256,7 +421,7 @@ where
     /// Groups elements from the `GroupingMap` source by key and collects the elements of each group in
     /// an instance of `C`. The iteration order is preserved when inserting elements.
     ///
-    /// Return a `HashMap` associating the key of each group with the collection containing that group's elements.
+    /// Return a map associating the key of each group with the collection containing that group's elements.
     ///
     /// ```
     /// use itertools::Itertools;
@@ -271,27 +436,25 @@ where
     /// assert_eq!(lookup[&2], vec![2, 5].into_iter().collect::<HashSet<_>>());
     /// assert_eq!(lookup.len(), 3);
     /// ```
-    pub fn collect<C>(self) -> HashMap<K, C, S>
+    pub fn collect<C>(mut self) -> Grouped<S, K, C>
     where
+        S: GroupingBackend<K, C>,
         C: Default + Extend<V>,
     {
-        let mut destination_map = HashMap::with_hasher(self.hash_builder);
+        let mut destination_map = S::new_map(self.backend);
 
-        self.iter.for_each(|(key, val)| {
-            destination_map
-                .entry(key)
-                .or_insert_with(C::default)
-                .extend(Some(val));
+        let res = self.iter.try_for_each(|(key, val)| {
+            S::entry(&mut destination_map, key, C::default).map(|acc| acc.extend(Some(val)))
         });
 
-        destination_map
+        S::finish(res.map(|()| destination_map))
     }
 
     /// Groups elements from the `GroupingMap` source by key and finds the maximum of each group.
     ///
     /// If several elements are equally maximum, the last element is picked.
     ///
-    /// Returns a `HashMap` associating the key of each group with the maximum of that group's elements.
+    /// Returns a map associating the key of each group with the maximum of that group's elements.
     ///
     /// ```
     /// use itertools::Itertools;
@@ -305,8 +468,9 @@ where
     /// assert_eq!(lookup[&2], 8);
     /// assert_eq!(lookup.len(), 3);
     /// ```
-    pub fn max(self) -> HashMap<K, V, S>
+    pub fn max(self) -> Grouped<S, K, V>
     where
+        S: GroupingBackend<K, V>,
         V: Ord,
     {
         self.max_by(|_, v1, v2| V::cmp(v1, v2))
@@ -317,7 +481,7 @@ where
     ///
     /// If several elements are equally maximum, the last element is picked.
     ///
-    /// Returns a `HashMap` associating the key of each group with the maximum of that group's elements.
+    /// Returns a map associating the key of each group with the maximum of that group's elements.
     ///
     /// ```
     /// use itertools::Itertools;
@@ -331,8 +495,9 @@ where
     /// assert_eq!(lookup[&2], 5);
     /// assert_eq!(lookup.len(), 3);
     /// ```
-    pub fn max_by<F>(self, mut compare: F) -> HashMap<K, V, S>
+    pub fn max_by<F>(self, mut compare: F) -> Grouped<S, K, V>
     where
+        S: GroupingBackend<K, V>,
         F: FnMut(&K, &V, &V) -> Ordering,
     {
         self.reduce(|acc, key, val| match compare(key, &acc, &val) {
@@ -346,7 +511,7 @@ where
     ///
     /// If several elements are equally maximum, the last element is picked.
     ///
-    /// Returns a `HashMap` associating the key of each group with the maximum of that group's elements.
+    /// Returns a map associating the key of each group with the maximum of that group's elements.
     ///
     /// ```
     /// use itertools::Itertools;
@@ -360,8 +525,9 @@ where
     /// assert_eq!(lookup[&2], 5);
     /// assert_eq!(lookup.len(), 3);
     /// ```
-    pub fn max_by_key<F, CK>(self, mut f: F) -> HashMap<K, V, S>
+    pub fn max_by_key<F, CK>(self, mut f: F) -> Grouped<S, K, V>
     where
+        S: GroupingBackend<K, V>,
         F: FnMut(&K, &V) -> CK,
         CK: Ord,
     {
@@ -372,7 +538,7 @@ where
     ///
     /// If several elements are equally minimum, the first element is picked.
     ///
-    /// Returns a `HashMap` associating the key of each group with the minimum of that group's elements.
+    /// Returns a map associating the key of each group with the minimum of that group's elements.
     ///
     /// ```
     /// use itertools::Itertools;
@@ -386,8 +552,9 @@ where
     /// assert_eq!(lookup[&2], 5);
     /// assert_eq!(lookup.len(), 3);
     /// ```
-    pub fn min(self) -> HashMap<K, V, S>
+    pub fn min(self) -> Grouped<S, K, V>
     where
+        S: GroupingBackend<K, V>,
         V: Ord,
     {
         self.min_by(|_, v1, v2| V::cmp(v1, v2))
@@ -398,7 +565,7 @@ where
     ///
     /// If several elements are equally minimum, the first element is picked.
     ///
-    /// Returns a `HashMap` associating the key of each group with the minimum of that group's elements.
+    /// Returns a map associating the key of each group with the minimum of that group's elements.
     ///
     /// ```
     /// use itertools::Itertools;
@@ -412,8 +579,9 @@ where
     /// assert_eq!(lookup[&2], 8);
     /// assert_eq!(lookup.len(), 3);
     /// ```
-    pub fn min_by<F>(self, mut compare: F) -> HashMap<K, V, S>
+    pub fn min_by<F>(self, mut compare: F) -> Grouped<S, K, V>
     where
+        S: GroupingBackend<K, V>,
         F: FnMut(&K, &V, &V) -> Ordering,
     {
         self.reduce(|acc, key, val| match compare(key, &acc, &val) {
@@ -427,7 +595,7 @@ where
     ///
     /// If several elements are equally minimum, the first element is picked.
     ///
-    /// Returns a `HashMap` associating the key of each group with the minimum of that group's elements.
+    /// Returns a map associating the key of each group with the minimum of that group's elements.
     ///
     /// ```
     /// use itertools::Itertools;
@@ -441,8 +609,9 @@ where
     /// assert_eq!(lookup[&2], 8);
     /// assert_eq!(lookup.len(), 3);
     /// ```
-    pub fn min_by_key<F, CK>(self, mut f: F) -> HashMap<K, V, S>
+    pub fn min_by_key<F, CK>(self, mut f: F) -> Grouped<S, K, V>
     where
+        S: GroupingBackend<K, V>,
         F: FnMut(&K, &V) -> CK,
         CK: Ord,
     {
@@ -461,7 +630,7 @@ where
     /// - It never produces a `MinMaxResult::NoElements`
     /// - It doesn't have any speedup
     ///
-    /// Returns a `HashMap` associating the key of each group with the minimum and maximum of that group's elements.
+    /// Returns a map associating the key of each group with the minimum and maximum of that group's elements.
     ///
     /// ```
     /// use itertools::Itertools;
@@ -476,8 +645,9 @@ where
     /// assert_eq!(lookup[&2], OneElement(5));
     /// assert_eq!(lookup.len(), 3);
     /// ```
-    pub fn minmax(self) -> HashMap<K, MinMaxResult<V>, S>
+    pub fn minmax(self) -> Grouped<S, K, MinMaxResult<V>>
     where
+        S: GroupingBackend<K, MinMaxResult<V>>,
         V: Ord,
     {
         self.minmax_by(|_, v1, v2| V::cmp(v1, v2))
@@ -491,7 +661,7 @@ where
     ///
     /// It has the same differences from the non-grouping version as `minmax`.
     ///
-    /// Returns a `HashMap` associating the key of each group with the minimum and maximum of that group's elements.
+    /// Returns a map associating the key of each group with the minimum and maximum of that group's elements.
     ///
     /// ```
     /// use itertools::Itertools;
@@ -506,8 +676,9 @@ where
     /// assert_eq!(lookup[&2], OneElement(5));
     /// assert_eq!(lookup.len(), 3);
     /// ```
-    pub fn minmax_by<F>(self, mut compare: F) -> HashMap<K, MinMaxResult<V>, S>
+    pub fn minmax_by<F>(self, mut compare: F) -> Grouped<S, K, MinMaxResult<V>>
     where
+        S: GroupingBackend<K, MinMaxResult<V>>,
         F: FnMut(&K, &V, &V) -> Ordering,
     {
         self.aggregate(|acc, key, val| {
@@ -542,7 +713,7 @@ where
     ///
     /// It has the same differences from the non-grouping version as `minmax`.
     ///
-    /// Returns a `HashMap` associating the key of each group with the minimum and maximum of that group's elements.
+    /// Returns a map associating the key of each group with the minimum and maximum of that group's elements.
     ///
     /// ```
     /// use itertools::Itertools;
@@ -557,8 +728,9 @@ where
     /// assert_eq!(lookup[&2], OneElement(5));
     /// assert_eq!(lookup.len(), 3);
     /// ```
-    pub fn minmax_by_key<F, CK>(self, mut f: F) -> HashMap<K, MinMaxResult<V>, S>
+    pub fn minmax_by_key<F, CK>(self, mut f: F) -> Grouped<S, K, MinMaxResult<V>>
     where
+        S: GroupingBackend<K, MinMaxResult<V>>,
         F: FnMut(&K, &V) -> CK,
         CK: Ord,
     {
@@ -570,7 +742,7 @@ where
     /// This is just a shorthand for `self.reduce(|acc, _, val| acc + val)`.
     /// It is more limited than `Iterator::sum` since it doesn't use the `Sum` trait.
     ///
-    /// Returns a `HashMap` associating the key of each group with the sum of that group's elements.
+    /// Returns a map associating the key of each group with the sum of that group's elements.
     ///
     /// ```
     /// use itertools::Itertools;
@@ -584,8 +756,9 @@ where
     /// assert_eq!(lookup[&2], 5 + 8);
     /// assert_eq!(lookup.len(), 3);
     /// ```
-    pub fn sum(self) -> HashMap<K, V, S>
+    pub fn sum(self) -> Grouped<S, K, V>
     where
+        S: GroupingBackend<K, V>,
         V: Add<V, Output = V>,
     {
         self.reduce(|acc, _, val| acc + val)
@@ -596,7 +769,7 @@ where
     /// This is just a shorthand for `self.reduce(|acc, _, val| acc * val)`.
     /// It is more limited than `Iterator::product` since it doesn't use the `Product` trait.
     ///
-    /// Returns a `HashMap` associating the key of each group with the product of that group's elements.
+    /// Returns a map associating the key of each group with the product of that group's elements.
     ///
     /// ```
     /// use itertools::Itertools;
@@ -610,10 +783,843 @@ where
     /// assert_eq!(lookup[&2], 5 * 8);
     /// assert_eq!(lookup.len(), 3);
     /// ```
-    pub fn product(self) -> HashMap<K, V, S>
+    pub fn product(self) -> Grouped<S, K, V>
     where
+        S: GroupingBackend<K, V>,
         V: Mul<V, Output = V>,
     {
         self.reduce(|acc, _, val| acc * val)
     }
+
+    /// Groups elements from the `GroupingMap` source by key and counts them.
+    ///
+    /// Returns a map associating the key of each group with the number of that group's elements.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// let lookup = vec![1, 3, 4, 5, 7, 8, 9, 12].into_iter()
+    ///     .into_grouping_map_by(|&n| n % 3)
+    ///     .count();
+    ///
+    /// assert_eq!(lookup[&0], 3);
+    /// assert_eq!(lookup[&1], 3);
+    /// assert_eq!(lookup[&2], 2);
+    /// assert_eq!(lookup.len(), 3);
+    /// ```
+    pub fn count(self) -> Grouped<S, K, usize>
+    where
+        S: GroupingBackend<K, usize>,
+    {
+        self.fold(0, |acc, _, _| acc + 1)
+    }
+
+    /// Groups elements from the `GroupingMap` source by key and computes their arithmetic mean.
+    ///
+    /// Returns a map associating the key of each group with the mean of that group's elements.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// let lookup = vec![1, 3, 4, 5, 7, 8, 9, 12].into_iter()
+    ///     .into_grouping_map_by(|&n| n % 3)
+    ///     .mean();
+    ///
+    /// assert_eq!(lookup[&0], 8.0);
+    /// assert_eq!(lookup[&1], 4.0);
+    /// assert_eq!(lookup[&2], 6.5);
+    /// assert_eq!(lookup.len(), 3);
+    /// ```
+    pub fn mean(self) -> Grouped<S, K, f64>
+    where
+        S: MapValues<K, (f64, usize), f64>,
+        V: Into<f64>,
+    {
+        self.mean_by(|_, val| val.into())
+    }
+
+    /// Groups elements from the `GroupingMap` source by key and computes the arithmetic mean
+    /// of the values `f` converts them to.
+    ///
+    /// The `f` function is invoked on each element with a reference to the key of its group
+    /// and the element. It allows to average values that are not `Into<f64>`, like `u64`.
+    ///
+    /// Returns a map associating the key of each group with the mean of that group's elements.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// let lookup = vec![1u64, 3, 4, 5, 7, 8, 9, 12].into_iter()
+    ///     .into_grouping_map_by(|&n| n % 3)
+    ///     .mean_by(|_key, val| val as f64);
+    ///
+    /// assert_eq!(lookup[&0], 8.0);
+    /// assert_eq!(lookup[&1], 4.0);
+    /// assert_eq!(lookup[&2], 6.5);
+    /// assert_eq!(lookup.len(), 3);
+    /// ```
+    pub fn mean_by<F>(self, mut f: F) -> Grouped<S, K, f64>
+    where
+        S: MapValues<K, (f64, usize), f64>,
+        F: FnMut(&K, V) -> f64,
+    {
+        let sums = self.fold((0.0_f64, 0_usize), |(sum, count), key, val| {
+            (sum + f(key, val), count + 1)
+        });
+        S::map_values(sums, |_, (sum, count)| sum / count as f64)
+    }
+
+    /// Groups elements from the `GroupingMap` source by key and keeps the first element of each group.
+    ///
+    /// Returns a map associating the key of each group with the first of that group's elements.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// let lookup = vec![1, 3, 4, 5, 7, 8, 9, 12].into_iter()
+    ///     .into_grouping_map_by(|&n| n % 3)
+    ///     .first();
+    ///
+    /// assert_eq!(lookup[&0], 3);
+    /// assert_eq!(lookup[&1], 1);
+    /// assert_eq!(lookup[&2], 5);
+    /// assert_eq!(lookup.len(), 3);
+    /// ```
+    pub fn first(self) -> Grouped<S, K, V>
+    where
+        S: GroupingBackend<K, V>,
+    {
+        self.reduce(|acc, _, _| acc)
+    }
+
+    /// Groups elements from the `GroupingMap` source by key and keeps the last element of each group.
+    ///
+    /// Returns a map associating the key of each group with the last of that group's elements.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// let lookup = vec![1, 3, 4, 5, 7, 8, 9, 12].into_iter()
+    ///     .into_grouping_map_by(|&n| n % 3)
+    ///     .last();
+    ///
+    /// assert_eq!(lookup[&0], 12);
+    /// assert_eq!(lookup[&1], 7);
+    /// assert_eq!(lookup[&2], 8);
+    /// assert_eq!(lookup.len(), 3);
+    /// ```
+    pub fn last(self) -> Grouped<S, K, V>
+    where
+        S: GroupingBackend<K, V>,
+    {
+        self.reduce(|_, _, val| val)
+    }
+
+    /// Groups elements from the `GroupingMap` source by key and keeps the `k` smallest elements
+    /// of each group, in ascending order.
+    ///
+    /// See [`Itertools::k_smallest`](crate::Itertools::k_smallest) for the non-grouping version.
+    /// The elements of each group are kept in a heap of at most `k` elements.
+    ///
+    /// Returns a map associating the key of each group with the `k` smallest of that group's elements.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// let lookup = vec![12, 3, 9, 1, 5, 7, 4, 8].into_iter()
+    ///     .into_grouping_map_by(|&n| n % 3)
+    ///     .k_smallest(2);
+    ///
+    /// assert_eq!(lookup[&0], vec![3, 9]);
+    /// assert_eq!(lookup[&1], vec![1, 4]);
+    /// assert_eq!(lookup[&2], vec![5, 8]);
+    /// assert_eq!(lookup.len(), 3);
+    /// ```
+    pub fn k_smallest(self, k: usize) -> Grouped<S, K, Vec<V>>
+    where
+        S: GroupingBackend<K, Vec<V>>,
+        V: Ord,
+    {
+        self.k_smallest_by(k, |_, v1, v2| V::cmp(v1, v2))
+    }
+
+    /// Groups elements from the `GroupingMap` source by key and keeps the `k` smallest elements
+    /// of each group with respect to the specified comparison function, in ascending order.
+    ///
+    /// Returns a map associating the key of each group with the `k` smallest of that group's elements.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// let lookup = vec![12, 3, 9, 1, 5, 7, 4, 8].into_iter()
+    ///     .into_grouping_map_by(|&n| n % 3)
+    ///     .k_smallest_by(2, |_key, x, y| y.cmp(x));
+    ///
+    /// assert_eq!(lookup[&0], vec![12, 9]);
+    /// assert_eq!(lookup[&1], vec![7, 4]);
+    /// assert_eq!(lookup[&2], vec![8, 5]);
+    /// assert_eq!(lookup.len(), 3);
+    /// ```
+    pub fn k_smallest_by<F>(self, k: usize, mut compare: F) -> Grouped<S, K, Vec<V>>
+    where
+        S: GroupingBackend<K, Vec<V>>,
+        F: FnMut(&K, &V, &V) -> Ordering,
+    {
+        let mut heaps = self.fold_with(
+            |_, _| Vec::new(),
+            |mut heap, key, val| {
+                let mut is_less_than = |a: &V, b: &V| compare(key, a, b) == Ordering::Less;
+                k_smallest::push_bounded(&mut heap, k, val, &mut is_less_than);
+                heap
+            },
+        );
+        S::for_each_mut(&mut heaps, |key, heap| {
+            let mut is_less_than = |a: &V, b: &V| compare(key, a, b) == Ordering::Less;
+            k_smallest::sort_bounded_heap(heap, k, &mut is_less_than);
+        });
+        heaps
+    }
+
+    /// Groups elements from the `GroupingMap` source by key and keeps the `k` elements of each
+    /// group that give the smallest values from the specified function, in ascending order.
+    ///
+    /// Returns a map associating the key of each group with the `k` smallest of that group's elements.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// let lookup = vec![12, 3, 9, 1, 5, 7, 4, 8].into_iter()
+    ///     .into_grouping_map_by(|&n| n % 3)
+    ///     .k_smallest_by_key(2, |_key, &val| val % 4);
+    ///
+    /// assert_eq!(lookup[&0], vec![12, 9]);
+    /// assert_eq!(lookup[&1], vec![4, 1]);
+    /// assert_eq!(lookup[&2], vec![8, 5]);
+    /// assert_eq!(lookup.len(), 3);
+    /// ```
+    pub fn k_smallest_by_key<F, CK>(self, k: usize, mut f: F) -> Grouped<S, K, Vec<V>>
+    where
+        S: GroupingBackend<K, Vec<V>>,
+        F: FnMut(&K, &V) -> CK,
+        CK: Ord,
+    {
+        self.k_smallest_by(k, |key, v1, v2| f(key, v1).cmp(&f(key, v2)))
+    }
+
+    /// Groups elements from the `GroupingMap` source by key and keeps the `k` largest elements
+    /// of each group, in descending order.
+    ///
+    /// See [`Itertools::k_largest`](crate::Itertools::k_largest) for the non-grouping version.
+    ///
+    /// Returns a map associating the key of each group with the `k` largest of that group's elements.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// let lookup = vec![12, 3, 9, 1, 5, 7, 4, 8].into_iter()
+    ///     .into_grouping_map_by(|&n| n % 3)
+    ///     .k_largest(2);
+    ///
+    /// assert_eq!(lookup[&0], vec![12, 9]);
+    /// assert_eq!(lookup[&1], vec![7, 4]);
+    /// assert_eq!(lookup[&2], vec![8, 5]);
+    /// assert_eq!(lookup.len(), 3);
+    /// ```
+    pub fn k_largest(self, k: usize) -> Grouped<S, K, Vec<V>>
+    where
+        S: GroupingBackend<K, Vec<V>>,
+        V: Ord,
+    {
+        self.k_largest_by(k, |_, v1, v2| V::cmp(v1, v2))
+    }
+
+    /// Groups elements from the `GroupingMap` source by key and keeps the `k` largest elements
+    /// of each group with respect to the specified comparison function, in descending order.
+    ///
+    /// Returns a map associating the key of each group with the `k` largest of that group's elements.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// let lookup = vec![12, 3, 9, 1, 5, 7, 4, 8].into_iter()
+    ///     .into_grouping_map_by(|&n| n % 3)
+    ///     .k_largest_by(2, |_key, x, y| y.cmp(x));
+    ///
+    /// assert_eq!(lookup[&0], vec![3, 9]);
+    /// assert_eq!(lookup[&1], vec![1, 4]);
+    /// assert_eq!(lookup[&2], vec![5, 8]);
+    /// assert_eq!(lookup.len(), 3);
+    /// ```
+    pub fn k_largest_by<F>(self, k: usize, mut compare: F) -> Grouped<S, K, Vec<V>>
+    where
+        S: GroupingBackend<K, Vec<V>>,
+        F: FnMut(&K, &V, &V) -> Ordering,
+    {
+        self.k_smallest_by(k, move |key, v1, v2| compare(key, v2, v1))
+    }
+
+    /// Groups elements from the `GroupingMap` source by key and keeps the `k` elements of each
+    /// group that give the largest values from the specified function, in descending order.
+    ///
+    /// Returns a map associating the key of each group with the `k` largest of that group's elements.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// let lookup = vec![12, 3, 9, 1, 5, 7, 4, 8].into_iter()
+    ///     .into_grouping_map_by(|&n| n % 3)
+    ///     .k_largest_by_key(2, |_key, &val| val % 4);
+    ///
+    /// assert_eq!(lookup[&0], vec![3, 9]);
+    /// assert_eq!(lookup[&1], vec![7, 1]);
+    /// assert_eq!(lookup[&2], vec![5, 8]);
+    /// assert_eq!(lookup.len(), 3);
+    /// ```
+    pub fn k_largest_by_key<F, CK>(self, k: usize, mut f: F) -> Grouped<S, K, Vec<V>>
+    where
+        S: GroupingBackend<K, Vec<V>>,
+        F: FnMut(&K, &V) -> CK,
+        CK: Ord,
+    {
+        self.k_largest_by(k, |key, v1, v2| f(key, v1).cmp(&f(key, v2)))
+    }
+
+    /// Groups elements from the `GroupingMap` source by key and keeps the first occurrence
+    /// of each distinct element of each group. The iteration order is preserved.
+    ///
+    /// See [`Itertools::unique`](crate::Itertools::unique) for the non-grouping version.
+    ///
+    /// Returns a map associating the key of each group with the distinct elements of that group.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// let lookup = vec![0, 1, 2, 3, 4, 5, 6, 2, 3, 6].into_iter()
+    ///     .into_grouping_map_by(|&n| n % 3)
+    ///     .unique();
+    ///
+    /// assert_eq!(lookup[&0], vec![0, 3, 6]);
+    /// assert_eq!(lookup[&1], vec![1, 4]);
+    /// assert_eq!(lookup[&2], vec![2, 5]);
+    /// assert_eq!(lookup.len(), 3);
+    /// ```
+    #[cfg(feature = "use_std")]
+    pub fn unique(self) -> Grouped<S, K, Vec<V>>
+    where
+        S: MapValues<K, (Vec<V>, HashSet<V>), Vec<V>>,
+        V: Clone + Eq + Hash,
+    {
+        let groups = self.fold_with(
+            |_, _| (Vec::new(), HashSet::new()),
+            |(mut elts, mut seen), _, val| {
+                if !seen.contains(&val) {
+                    seen.insert(val.clone());
+                    elts.push(val);
+                }
+                (elts, seen)
+            },
+        );
+        S::map_values(groups, |_, (elts, _)| elts)
+    }
+
+    /// Groups elements from the `GroupingMap` source by key and applies the fallible `operation`
+    /// to the elements of each group sequentially, like [`aggregate`](Self::aggregate).
+    ///
+    /// If `operation` returns `Ok(Some(element))` then the accumulator is updated with `element`,
+    /// if it returns `Ok(None)` the previous accumulation is discarded.
+    ///
+    /// Return the first error returned by `operation`, without consuming the source any further.
+    /// Otherwise, return a map associating the key of each group with the result of
+    /// aggregation of that group's elements.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// let lookup = vec![2, 8, 5, 7, 9, 4].into_iter()
+    ///     .into_grouping_map_by(|&n| n % 4)
+    ///     .try_aggregate(|acc, _key, val| {
+    ///         if val == 0 {
+    ///             Err("division by zero")
+    ///         } else {
+    ///             Ok(Some(acc.unwrap_or(0) + 100 / val))
+    ///         }
+    ///     });
+    ///
+    /// let lookup = lookup.unwrap();
+    /// assert_eq!(lookup[&0], 12 + 25);
+    /// assert_eq!(lookup[&1], 20 + 11);
+    /// assert_eq!(lookup[&2], 50);
+    /// assert_eq!(lookup[&3], 14);
+    ///
+    /// let mut data = vec![2, 0, 5].into_iter();
+    /// let lookup = data.by_ref()
+    ///     .into_grouping_map_by(|&n| n % 4)
+    ///     .try_aggregate(|acc, _key, val| {
+    ///         if val == 0 {
+    ///             Err("division by zero")
+    ///         } else {
+    ///             Ok(Some(acc.unwrap_or(0) + 100 / val))
+    ///         }
+    ///     });
+    ///
+    /// assert_eq!(lookup, Err("division by zero"));
+    /// assert_eq!(data.next(), Some(5));
+    /// ```
+    pub fn try_aggregate<FO, R, E>(mut self, mut operation: FO) -> Result<Grouped<S, K, R>, E>
+    where
+        S: GroupingBackend<K, R>,
+        FO: FnMut(Option<R>, &K, V) -> Result<Option<R>, E>,
+    {
+        let mut destination_map = S::new_map(self.backend);
+
+        // Stop at the first key the map can not hold, or at the first error of `operation`.
+        let res = self.iter.try_for_each(|(key, val)| {
+            let acc = S::remove(&mut destination_map, &key).map_err(Ok)?;
+            if let Some(op_res) = operation(acc, &key, val).map_err(Err)? {
+                S::insert(&mut destination_map, key, op_res);
+            }
+            Ok(())
+        });
+
+        match res {
+            Ok(()) => Ok(S::finish(Ok(destination_map))),
+            Err(Ok(key_error)) => Ok(S::finish(Err(key_error))),
+            Err(Err(err)) => Err(err),
+        }
+    }
+
+    /// Groups elements from the `GroupingMap` source by key and applies the fallible `operation`
+    /// to the elements of each group sequentially, like [`fold`](Self::fold).
+    ///
+    /// Return the first error returned by `operation`, without consuming the source any further.
+    /// Otherwise, return a map associating the key of each group with the result of
+    /// folding that group's elements.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// let lookup = vec![200, 1, 50, 2, 100].into_iter()
+    ///     .into_grouping_map_by(|&n| n % 2)
+    ///     .try_fold(0_u8, |acc, _key, val| acc.checked_add(val).ok_or(val));
+    ///
+    /// assert_eq!(lookup, Err(100));
+    ///
+    /// let lookup = vec![200, 1, 50, 2].into_iter()
+    ///     .into_grouping_map_by(|&n| n % 2)
+    ///     .try_fold(0_u8, |acc, _key, val| acc.checked_add(val).ok_or(val))
+    ///     .unwrap();
+    ///
+    /// assert_eq!(lookup[&0], 200 + 50 + 2);
+    /// assert_eq!(lookup[&1], 1);
+    /// ```
+    pub fn try_fold<FO, R, E>(self, init: R, mut operation: FO) -> Result<Grouped<S, K, R>, E>
+    where
+        S: GroupingBackend<K, R>,
+        R: Clone,
+        FO: FnMut(R, &K, V) -> Result<R, E>,
+    {
+        self.try_aggregate(|acc, key, val| {
+            let acc = acc.unwrap_or_else(|| init.clone());
+            operation(acc, key, val).map(Some)
+        })
+    }
+
+    /// Groups elements from the `GroupingMap` source by key and applies the fallible `operation`
+    /// to the elements of each group sequentially, like [`reduce`](Self::reduce).
+    ///
+    /// Return the first error returned by `operation`, without consuming the source any further.
+    /// Otherwise, return a map associating the key of each group with the result of
+    /// folding that group's elements.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// let lookup = vec![200_u8, 1, 50, 3].into_iter()
+    ///     .into_grouping_map_by(|&n| n % 2)
+    ///     .try_reduce(|acc, _key, val| acc.checked_add(val).ok_or("overflow"));
+    ///
+    /// let lookup = lookup.unwrap();
+    /// assert_eq!(lookup[&0], 200 + 50);
+    /// assert_eq!(lookup[&1], 1 + 3);
+    ///
+    /// let lookup = vec![200_u8, 1, 100].into_iter()
+    ///     .into_grouping_map_by(|&n| n % 2)
+    ///     .try_reduce(|acc, _key, val| acc.checked_add(val).ok_or("overflow"));
+    ///
+    /// assert_eq!(lookup, Err("overflow"));
+    /// ```
+    pub fn try_reduce<FO, E>(self, mut operation: FO) -> Result<Grouped<S, K, V>, E>
+    where
+        S: GroupingBackend<K, V>,
+        FO: FnMut(V, &K, V) -> Result<V, E>,
+    {
+        self.try_aggregate(|acc, key, val| match acc {
+            Some(acc) => operation(acc, key, val).map(Some),
+            None => Ok(Some(val)),
+        })
+    }
+}
+
+/// Creates a new `GroupingMapOk` from `iter`
+#[cfg(feature = "use_std")]
+pub fn new_ok<I, S>(iter: I, backend: S) -> GroupingMapOk<I, S> {
+    GroupingMapOk { iter, backend }
+}
+
+/// A wrapper to allow for an easy [`into_grouping_map_ok_by`](crate::Itertools::into_grouping_map_ok_by)
+#[cfg(feature = "use_std")]
+pub type MapOkForGrouping<I, F> = MapSpecialCase<I, GroupingMapOkFn<F>>;
+
+#[cfg(feature = "use_std")]
+#[derive(Clone)]
+pub struct GroupingMapOkFn<F>(F);
+
+#[cfg(feature = "use_std")]
+impl<F> std::fmt::Debug for GroupingMapOkFn<F> {
+    debug_fmt_fields!(GroupingMapOkFn,);
+}
+
+#[cfg(feature = "use_std")]
+impl<V, E, K, F: FnMut(&V) -> K> MapSpecialCaseFn<Result<V, E>> for GroupingMapOkFn<F> {
+    type Out = Result<(K, V), E>;
+    fn call(&mut self, r: Result<V, E>) -> Self::Out {
+        r.map(|v| ((self.0)(&v), v))
+    }
+}
+
+#[cfg(feature = "use_std")]
+pub(crate) fn new_map_ok_for_grouping<K, V, E, I, F>(
+    iter: I,
+    key_mapper: F,
+) -> MapOkForGrouping<I, F>
+where
+    I: Iterator<Item = Result<V, E>>,
+    F: FnMut(&V) -> K,
+{
+    MapSpecialCase {
+        iter,
+        f: GroupingMapOkFn(key_mapper),
+    }
+}
+
+/// Defines methods of `GroupingMapOk` that forward to the ones of `GroupingMap` with the same
+/// name, and return the first error of the source instead of their result, if any.
+#[cfg(feature = "use_std")]
+macro_rules! grouping_map_ok_forwards {
+    ($(
+        $(#[$attr:meta])*
+        fn $name:ident$(<$($gen:ident),*>)?(self $(, $arg:ident: $arg_ty:ty)*) -> $val:ty
+        where [$($bounds:tt)*];
+    )*) => {$(
+        $(#[$attr])*
+        #[doc = concat!(
+            "Groups the `Ok` elements from the `GroupingMapOk` source like [`GroupingMap::",
+            stringify!($name),
+            "`], or returns the first error of the source."
+        )]
+        pub fn $name$(<$($gen),*>)?(self $(, $arg: $arg_ty)*) -> Result<Grouped<S, K, $val>, E>
+        where
+            $($bounds)*
+        {
+            self.process(|map| map.$name($($arg),*))
+        }
+    )*};
+}
+
+/// `GroupingMapOkBy` is an intermediate struct for efficient group-and-fold operations.
+///
+/// See [`GroupingMapOk`] for more information.
+#[cfg(feature = "use_std")]
+pub type GroupingMapOkBy<I, F, S = RandomState> = GroupingMapOk<MapOkForGrouping<I, F>, S>;
+
+/// `GroupingMapOk` is an intermediate struct for efficient group-and-fold operations
+/// over an iterator of `Result<(K, V), E>`.
+///
+/// It offers the operations of [`GroupingMap`] over the `Ok` elements, and returns the first
+/// error of the source instead, if any. The source is not consumed after its first error.
+/// Other operations can be performed with [`process`](Self::process).
+///
+/// ```
+/// use itertools::Itertools;
+///
+/// fn parse(s: &str) -> Result<(&str, u32), &str> {
+///     let (key, val) = s.split_once('=').ok_or(s)?;
+///     Ok((key, val.parse().map_err(|_| s)?))
+/// }
+///
+/// let lookup = vec!["a=1", "b=2", "a=3"].into_iter()
+///     .map(parse)
+///     .into_grouping_map_ok()
+///     .sum();
+///
+/// let lookup = lookup.unwrap();
+/// assert_eq!(lookup["a"], 1 + 3);
+/// assert_eq!(lookup["b"], 2);
+///
+/// let lookup = vec!["a=1", "b", "a=3"].into_iter()
+///     .map(parse)
+///     .into_grouping_map_ok()
+///     .sum();
+///
+/// assert_eq!(lookup, Err("b"));
+/// ```
+#[cfg(feature = "use_std")]
+#[derive(Clone, Debug)]
+#[must_use = "GroupingMapOk is lazy and do nothing unless consumed"]
+pub struct GroupingMapOk<I, S = RandomState> {
+    iter: I,
+    backend: S,
+}
+
+#[cfg(feature = "use_std")]
+impl<I, K, V, E, S> GroupingMapOk<I, S>
+where
+    I: Iterator<Item = Result<(K, V), E>>,
+{
+    /// Applies `processor` to a [`GroupingMap`] over the `Ok` elements of the source,
+    /// and returns its result, or the first error of the source.
+    ///
+    /// The `GroupingMap` stops at the first error, so that the result of `processor`
+    /// is discarded if there is any. This allows the operations that `GroupingMapOk` does
+    /// not offer, like fallible ones whose errors are not of the type of the source errors.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// #[derive(Debug, PartialEq)]
+    /// struct DivisionByZero;
+    ///
+    /// let sum_quotients = |data: Vec<Result<u32, &'static str>>| {
+    ///     data.into_iter()
+    ///         .into_grouping_map_ok_by(|&n| n % 4)
+    ///         .process(|map| {
+    ///             map.try_fold(0, |acc, _key, val| {
+    ///                 100u32.checked_div(val).map(|q| acc + q).ok_or(DivisionByZero)
+    ///             })
+    ///         })
+    /// };
+    ///
+    /// let lookup = sum_quotients(vec![Ok(2), Ok(5), Ok(4)]).unwrap().unwrap();
+    /// assert_eq!(lookup[&0], 25);
+    /// assert_eq!(lookup[&1], 20);
+    /// assert_eq!(lookup[&2], 50);
+    ///
+    /// assert_eq!(sum_quotients(vec![Ok(2), Ok(0), Err("bad")]), Ok(Err(DivisionByZero)));
+    /// assert_eq!(sum_quotients(vec![Err("bad"), Ok(0)]), Err("bad"));
+    /// ```
+    pub fn process<FP, R>(self, processor: FP) -> Result<R, E>
+    where
+        FP: FnOnce(GroupingMap<ProcessResults<'_, I, E>, S>) -> R,
+    {
+        let backend = self.backend;
+        crate::process_results(self.iter, |iter| processor(new(iter, backend)))
+    }
+    grouping_map_ok_forwards! {
+        fn aggregate<FO, R>(self, operation: FO) -> R
+        where [
+            S: GroupingBackend<K, R>,
+            FO: FnMut(Option<R>, &K, V) -> Option<R>,
+        ];
+
+        fn fold_with<FI, FO, R>(self, init: FI, operation: FO) -> R
+        where [
+            S: GroupingBackend<K, R>,
+            FI: FnMut(&K, &V) -> R,
+            FO: FnMut(R, &K, V) -> R,
+        ];
+
+        fn fold<FO, R>(self, init: R, operation: FO) -> R
+        where [
+            S: GroupingBackend<K, R>,
+            R: Clone,
+            FO: FnMut(R, &K, V) -> R,
+        ];
+
+        fn reduce<FO>(self, operation: FO) -> V
+        where [
+            S: GroupingBackend<K, V>,
+            FO: FnMut(V, &K, V) -> V,
+        ];
+
+        fn collect<C>(self) -> C
+        where [
+            S: GroupingBackend<K, C>,
+            C: Default + Extend<V>,
+        ];
+
+        fn max(self) -> V
+        where [
+            S: GroupingBackend<K, V>,
+            V: Ord,
+        ];
+
+        fn max_by<F>(self, compare: F) -> V
+        where [
+            S: GroupingBackend<K, V>,
+            F: FnMut(&K, &V, &V) -> Ordering,
+        ];
+
+        fn max_by_key<F, CK>(self, f: F) -> V
+        where [
+            S: GroupingBackend<K, V>,
+            F: FnMut(&K, &V) -> CK,
+            CK: Ord,
+        ];
+
+        fn min(self) -> V
+        where [
+            S: GroupingBackend<K, V>,
+            V: Ord,
+        ];
+
+        fn min_by<F>(self, compare: F) -> V
+        where [
+            S: GroupingBackend<K, V>,
+            F: FnMut(&K, &V, &V) -> Ordering,
+        ];
+
+        fn min_by_key<F, CK>(self, f: F) -> V
+        where [
+            S: GroupingBackend<K, V>,
+            F: FnMut(&K, &V) -> CK,
+            CK: Ord,
+        ];
+
+        fn minmax(self) -> MinMaxResult<V>
+        where [
+            S: GroupingBackend<K, MinMaxResult<V>>,
+            V: Ord,
+        ];
+
+        fn minmax_by<F>(self, compare: F) -> MinMaxResult<V>
+        where [
+            S: GroupingBackend<K, MinMaxResult<V>>,
+            F: FnMut(&K, &V, &V) -> Ordering,
+        ];
+
+        fn minmax_by_key<F, CK>(self, f: F) -> MinMaxResult<V>
+        where [
+            S: GroupingBackend<K, MinMaxResult<V>>,
+            F: FnMut(&K, &V) -> CK,
+            CK: Ord,
+        ];
+
+        fn sum(self) -> V
+        where [
+            S: GroupingBackend<K, V>,
+            V: Add<V, Output = V>,
+        ];
+
+        fn product(self) -> V
+        where [
+            S: GroupingBackend<K, V>,
+            V: Mul<V, Output = V>,
+        ];
+
+        fn count(self) -> usize
+        where [
+            S: GroupingBackend<K, usize>,
+        ];
+
+        fn mean(self) -> f64
+        where [
+            S: MapValues<K, (f64, usize), f64>,
+            V: Into<f64>,
+        ];
+
+        fn mean_by<F>(self, f: F) -> f64
+        where [
+            S: MapValues<K, (f64, usize), f64>,
+            F: FnMut(&K, V) -> f64,
+        ];
+
+        fn first(self) -> V
+        where [
+            S: GroupingBackend<K, V>,
+        ];
+
+        fn last(self) -> V
+        where [
+            S: GroupingBackend<K, V>,
+        ];
+
+        fn k_smallest(self, k: usize) -> Vec<V>
+        where [
+            S: GroupingBackend<K, Vec<V>>,
+            V: Ord,
+        ];
+
+        fn k_smallest_by<F>(self, k: usize, compare: F) -> Vec<V>
+        where [
+            S: GroupingBackend<K, Vec<V>>,
+            F: FnMut(&K, &V, &V) -> Ordering,
+        ];
+
+        fn k_smallest_by_key<F, CK>(self, k: usize, f: F) -> Vec<V>
+        where [
+            S: GroupingBackend<K, Vec<V>>,
+            F: FnMut(&K, &V) -> CK,
+            CK: Ord,
+        ];
+
+        fn k_largest(self, k: usize) -> Vec<V>
+        where [
+            S: GroupingBackend<K, Vec<V>>,
+            V: Ord,
+        ];
+
+        fn k_largest_by<F>(self, k: usize, compare: F) -> Vec<V>
+        where [
+            S: GroupingBackend<K, Vec<V>>,
+            F: FnMut(&K, &V, &V) -> Ordering,
+        ];
+
+        fn k_largest_by_key<F, CK>(self, k: usize, f: F) -> Vec<V>
+        where [
+            S: GroupingBackend<K, Vec<V>>,
+            F: FnMut(&K, &V) -> CK,
+            CK: Ord,
+        ];
+
+        #[cfg(feature = "use_std")]
+        fn unique(self) -> Vec<V>
+        where [
+            S: MapValues<K, (Vec<V>, HashSet<V>), Vec<V>>,
+            V: Clone + Eq + Hash,
+        ];
+    }
+
+    /// Groups the `Ok` elements from the `GroupingMapOk` source like
+    /// [`GroupingMap::try_aggregate`], or returns the first error of either the source
+    /// or `operation`.
+    pub fn try_aggregate<FO, R>(self, operation: FO) -> Result<Grouped<S, K, R>, E>
+    where
+        S: GroupingBackend<K, R>,
+        FO: FnMut(Option<R>, &K, V) -> Result<Option<R>, E>,
+    {
+        self.process(|map| map.try_aggregate(operation))?
+    }
+
+    /// Groups the `Ok` elements from the `GroupingMapOk` source like
+    /// [`GroupingMap::try_fold`], or returns the first error of either the source
+    /// or `operation`.
+    pub fn try_fold<FO, R>(self, init: R, operation: FO) -> Result<Grouped<S, K, R>, E>
+    where
+        S: GroupingBackend<K, R>,
+        R: Clone,
+        FO: FnMut(R, &K, V) -> Result<R, E>,
+    {
+        self.process(|map| map.try_fold(init, operation))?
+    }
+
+    /// Groups the `Ok` elements from the `GroupingMapOk` source like
+    /// [`GroupingMap::try_reduce`], or returns the first error of either the source
+    /// or `operation`.
+    pub fn try_reduce<FO>(self, operation: FO) -> Result<Grouped<S, K, V>, E>
+    where
+        S: GroupingBackend<K, V>,
+        FO: FnMut(V, &K, V) -> Result<V, E>,
+    {
+        self.process(|map| map.try_reduce(operation))?
+    }
 }
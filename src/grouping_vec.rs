@@ -0,0 +1,144 @@
+use crate::grouping_map::{self, GroupingBackend, GroupingMap, MapForGrouping, MapValues};
+use alloc::vec::Vec;
+#[cfg(feature = "use_std")]
+use std::error::Error;
+use std::fmt;
+use std::iter::Iterator;
+
+/// Creates a new `GroupingVec` from `iter`
+pub fn new<I, V>(iter: I, num_keys: usize) -> GroupingVec<I>
+where
+    I: Iterator<Item = (usize, V)>,
+{
+    grouping_map::new(iter, VecBackend { num_keys })
+}
+
+/// Error returned by [`GroupingVec`] operations and
+/// [`Itertools::counts_dense`](crate::Itertools::counts_dense)
+/// when a group key is not less than the number of keys.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct KeyOutOfRangeError {
+    /// The offending key.
+    pub key: usize,
+    /// The number of keys, all valid keys are less than it.
+    pub num_keys: usize,
+}
+
+impl fmt::Display for KeyOutOfRangeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "group key {} is out of range for {} keys",
+            self.key, self.num_keys
+        )
+    }
+}
+
+#[cfg(feature = "use_std")]
+impl Error for KeyOutOfRangeError {}
+
+/// Return a mutable reference to the bucket of `key`.
+pub(crate) fn bucket<T>(buckets: &mut [T], key: usize) -> Result<&mut T, KeyOutOfRangeError> {
+    let num_keys = buckets.len();
+    buckets
+        .get_mut(key)
+        .ok_or(KeyOutOfRangeError { key, num_keys })
+}
+
+/// `GroupingVecBy` is an intermediate struct for efficient group-and-fold operations.
+///
+/// See [`GroupingVec`] for more information.
+pub type GroupingVecBy<I, F> = GroupingVec<MapForGrouping<I, F>>;
+
+/// `GroupingVec` is a [`GroupingMap`] over small integer keys.
+///
+/// It offers the same operations, but the keys are `usize` in `0..num_keys` and the
+/// results are stored in a `Vec` of length `num_keys` indexed by key, so that no hashing
+/// is involved. The result for a key without any element is `None`.
+///
+/// Every operation stops consuming the source at the first key out of range,
+/// and returns a [`KeyOutOfRangeError`].
+///
+/// ```
+/// use itertools::Itertools;
+///
+/// let lookup = vec![1, 3, 4, 5, 7, 8, 9, 12].into_iter()
+///     .into_grouping_vec(4, |&n| n % 3)
+///     .sum();
+///
+/// assert_eq!(lookup, Ok(vec![Some(3 + 9 + 12), Some(1 + 4 + 7), Some(5 + 8), None]));
+///
+/// let err = (0..10).into_grouping_vec(4, |&n| n).collect::<Vec<_>>().unwrap_err();
+/// assert_eq!((err.key, err.num_keys), (4, 4));
+/// ```
+pub type GroupingVec<I> = GroupingMap<I, VecBackend>;
+
+/// The second type parameter of a [`GroupingMap`] that stores the groups in a `Vec`
+/// indexed by key.
+///
+/// See [`GroupingVec`] for more information.
+#[derive(Clone, Copy, Debug)]
+pub struct VecBackend {
+    num_keys: usize,
+}
+
+impl<V> GroupingBackend<usize, V> for VecBackend {
+    type Map = Vec<Option<V>>;
+    type Error = KeyOutOfRangeError;
+    type Output = Result<Vec<Option<V>>, KeyOutOfRangeError>;
+
+    fn new_map(self) -> Self::Map {
+        let mut map = Vec::with_capacity(self.num_keys);
+        map.resize_with(self.num_keys, || None);
+        map
+    }
+
+    fn remove(map: &mut Self::Map, key: &usize) -> Result<Option<V>, Self::Error> {
+        bucket(map, *key).map(Option::take)
+    }
+
+    fn insert(map: &mut Self::Map, key: usize, value: V) {
+        map[key] = Some(value);
+    }
+
+    fn entry<F>(map: &mut Self::Map, key: usize, default: F) -> Result<&mut V, Self::Error>
+    where
+        F: FnOnce() -> V,
+    {
+        bucket(map, key).map(|acc| acc.get_or_insert_with(default))
+    }
+
+    fn finish(map: Result<Self::Map, Self::Error>) -> Self::Output {
+        map
+    }
+
+    fn for_each_mut<F>(output: &mut Self::Output, mut f: F)
+    where
+        F: FnMut(&usize, &mut V),
+    {
+        if let Ok(map) = output {
+            for (key, val) in map.iter_mut().enumerate() {
+                if let Some(val) = val {
+                    f(&key, val);
+                }
+            }
+        }
+    }
+}
+
+impl<V, W> MapValues<usize, V, W> for VecBackend {
+    fn map_values<F>(
+        output: Result<Vec<Option<V>>, KeyOutOfRangeError>,
+        mut f: F,
+    ) -> Result<Vec<Option<W>>, KeyOutOfRangeError>
+    where
+        F: FnMut(&usize, V) -> W,
+    {
+        output.map(|map| {
+            map.into_iter()
+                .enumerate()
+                .map(|(key, val)| val.map(|val| f(&key, val)))
+                .collect()
+        })
+    }
+}
@@ -0,0 +1,283 @@
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use std::iter::FusedIterator;
+
+use crate::combinations::increment_indices;
+use crate::count::{binomial, falling_factorial, product_len};
+use crate::size_hint::SizeHint;
+
+/// An iterator over the `k`-length combinations of the indices `0..n`.
+///
+/// See [`combination_indices`] for more information.
+///
+/// Its [`len`](ExactSizeIterator::len) is only meaningful when the count of combinations,
+/// given by [`count::binomial`](crate::count::binomial), fits in a `usize`: it panics otherwise.
+#[derive(Clone, Debug)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct CombinationIndices {
+    indices: Vec<usize>,
+    n: usize,
+    first: bool,
+    /// The count of remaining combinations, or `None` if it would overflow.
+    remaining: Option<usize>,
+}
+
+/// Return an iterator over the `k`-length combinations of the indices `0..n`,
+/// in lexicographic order.
+///
+/// This is the same sequence as `(0..n).combinations(k)` but without buffering
+/// the indices: only the last generated combination is kept.
+///
+/// ```
+/// use itertools::combination_indices;
+///
+/// itertools::assert_equal(combination_indices(4, 2), vec![
+///     vec![0, 1],
+///     vec![0, 2],
+///     vec![0, 3],
+///     vec![1, 2],
+///     vec![1, 3],
+///     vec![2, 3],
+/// ]);
+/// assert_eq!(combination_indices(50, 3).len(), 19600);
+/// ```
+pub fn combination_indices(n: usize, k: usize) -> CombinationIndices {
+    CombinationIndices {
+        indices: (0..k).collect(),
+        n,
+        first: true,
+        remaining: binomial(n, k),
+    }
+}
+
+impl CombinationIndices {
+    /// Moves to the next combination, returns false if there is none.
+    pub(crate) fn advance(&mut self) -> bool {
+        if self.remaining == Some(0) {
+            return false;
+        }
+        if self.first {
+            self.first = false;
+        } else if increment_indices(&mut self.indices, self.n) {
+            self.remaining = Some(0);
+            return false;
+        }
+        if let Some(remaining) = self.remaining.as_mut() {
+            *remaining -= 1;
+        }
+        true
+    }
+
+    /// Returns the last generated combination.
+    pub(crate) fn current(&self) -> &[usize] {
+        &self.indices
+    }
+}
+
+impl Iterator for CombinationIndices {
+    type Item = Vec<usize>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.advance().then(|| self.indices.clone())
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        (self.remaining.unwrap_or(usize::MAX), self.remaining)
+    }
+}
+
+impl ExactSizeIterator for CombinationIndices {}
+
+impl FusedIterator for CombinationIndices {}
+
+/// An iterator over the `k`-length permutations of the indices `0..n`.
+///
+/// See [`permutation_indices`] for more information.
+///
+/// As for [`CombinationIndices`], `len` panics when the count of permutations does not
+/// fit in a `usize`.
+#[derive(Clone, Debug)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct PermutationIndices {
+    indices: Vec<usize>,
+    n: usize,
+    first: bool,
+    /// The count of remaining permutations, or `None` if it would overflow.
+    remaining: Option<usize>,
+}
+
+/// Return an iterator over the `k`-length permutations of the indices `0..n`,
+/// in lexicographic order.
+///
+/// This is the same sequence as `(0..n).permutations(k)` but without buffering
+/// the indices: only the last generated permutation is kept.
+///
+/// ```
+/// use itertools::permutation_indices;
+///
+/// itertools::assert_equal(permutation_indices(3, 2), vec![
+///     vec![0, 1],
+///     vec![0, 2],
+///     vec![1, 0],
+///     vec![1, 2],
+///     vec![2, 0],
+///     vec![2, 1],
+/// ]);
+/// assert_eq!(permutation_indices(10, 4).len(), 5040);
+/// ```
+pub fn permutation_indices(n: usize, k: usize) -> PermutationIndices {
+    PermutationIndices {
+        indices: (0..k).collect(),
+        n,
+        first: true,
+        remaining: falling_factorial(n, k),
+    }
+}
+
+impl PermutationIndices {
+    /// Moves to the next permutation, returns false if there is none.
+    fn advance(&mut self) -> bool {
+        if self.remaining == Some(0) {
+            return false;
+        }
+        if self.first {
+            self.first = false;
+        } else if advance_permutation(&mut self.indices, self.n) {
+            self.remaining = Some(0);
+            return false;
+        }
+        if let Some(remaining) = self.remaining.as_mut() {
+            *remaining -= 1;
+        }
+        true
+    }
+}
+
+impl Iterator for PermutationIndices {
+    type Item = Vec<usize>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.advance().then(|| self.indices.clone())
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        (self.remaining.unwrap_or(usize::MAX), self.remaining)
+    }
+}
+
+impl ExactSizeIterator for PermutationIndices {}
+
+impl FusedIterator for PermutationIndices {}
+
+/// Moves `indices` to the next `k`-permutation of `0..n` in lexicographic order,
+/// without any other memory than the permutation itself.
+///
+/// Returns true if there is no next permutation, false otherwise.
+fn advance_permutation(indices: &mut [usize], n: usize) -> bool {
+    let k = indices.len();
+    // Scan from the end, looking for an index that can be replaced by a greater unused one...
+    for i in (0..k).rev() {
+        let used = &indices[..i];
+        if let Some(index) = (indices[i] + 1..n).find(|index| !used.contains(index)) {
+            indices[i] = index;
+            // ...and pick the smallest unused indices for the ones to its right.
+            let mut next = 0;
+            for j in i + 1..k {
+                while indices[..j].contains(&next) {
+                    next += 1;
+                }
+                indices[j] = next;
+                next += 1;
+            }
+            return false;
+        }
+    }
+    true
+}
+
+/// An iterator over the tuples of indices `[i_0, i_1, ...]` with `i_j` in `0..dims[j]`.
+///
+/// See [`product_indices`] for more information.
+///
+/// As for [`CombinationIndices`], `len` panics when the count of tuples, given by
+/// [`count::product_len`](crate::count::product_len), does not fit in a `usize`.
+#[derive(Clone, Debug)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct ProductIndices {
+    indices: Vec<usize>,
+    dims: Box<[usize]>,
+    first: bool,
+    /// The count of remaining tuples, or `None` if it would overflow.
+    remaining: Option<usize>,
+}
+
+/// Return an iterator over the tuples of indices `[i_0, i_1, ...]` with each `i_j`
+/// in `0..dims[j]`, in lexicographic order (the last index changes fastest).
+///
+/// This is the same sequence as the
+/// [`.multi_cartesian_product()`](crate::Itertools::multi_cartesian_product) of the
+/// ranges `0..dims[j]`.
+///
+/// ```
+/// use itertools::product_indices;
+///
+/// itertools::assert_equal(product_indices(&[2, 3]), vec![
+///     vec![0, 0],
+///     vec![0, 1],
+///     vec![0, 2],
+///     vec![1, 0],
+///     vec![1, 1],
+///     vec![1, 2],
+/// ]);
+/// assert_eq!(product_indices(&[4, 5, 6]).len(), 120);
+/// ```
+pub fn product_indices(dims: &[usize]) -> ProductIndices {
+    ProductIndices {
+        indices: alloc::vec![0; dims.len()],
+        dims: dims.into(),
+        first: true,
+        remaining: product_len(dims.iter().copied()),
+    }
+}
+
+impl Iterator for ProductIndices {
+    type Item = Vec<usize>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == Some(0) {
+            return None;
+        }
+        if self.first {
+            self.first = false;
+        } else if advance_product(&mut self.indices, &self.dims) {
+            self.remaining = Some(0);
+            return None;
+        }
+        if let Some(remaining) = self.remaining.as_mut() {
+            *remaining -= 1;
+        }
+        Some(self.indices.clone())
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        (self.remaining.unwrap_or(usize::MAX), self.remaining)
+    }
+}
+
+impl ExactSizeIterator for ProductIndices {}
+
+impl FusedIterator for ProductIndices {}
+
+/// Moves `indices` to the next tuple like an odometer with the given `dims`.
+///
+/// Returns true if there is no next tuple, false otherwise.
+fn advance_product(indices: &mut [usize], dims: &[usize]) -> bool {
+    for (index, &dim) in indices.iter_mut().zip(dims).rev() {
+        *index += 1;
+        if *index < dim {
+            return false;
+        }
+        *index = 0;
+    }
+    true
+}
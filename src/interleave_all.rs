@@ -0,0 +1,154 @@
+use alloc::vec::Vec;
+use std::iter::FusedIterator;
+
+use crate::size_hint::{self, SizeHint};
+
+/// An iterator adaptor that alternates elements from an arbitrary number of base
+/// iterators in a round-robin fashion until all of them run out.
+///
+/// Iterator element type is `I::Item`.
+///
+/// See [`.interleave_all()`](crate::Itertools::interleave_all) for more information.
+#[derive(Clone, Debug)]
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+pub struct InterleaveAll<I> {
+    /// The iterators which have not run out yet.
+    iters: Vec<I>,
+    /// The index of the iterator whose turn it is.
+    next: usize,
+}
+
+/// Create an iterator that alternates elements from the contained iterators in a
+/// round-robin fashion until all of them run out.
+///
+/// [`IntoIterator`] enabled version of [`Itertools::interleave_all`](crate::Itertools::interleave_all).
+///
+/// ```
+/// use itertools::interleave_all;
+///
+/// let it = interleave_all(vec![vec![1, 2, 3], vec![4], vec![5, 6]]);
+/// itertools::assert_equal(it, vec![1, 4, 5, 2, 6, 3]);
+/// ```
+pub fn interleave_all<I>(iterable: I) -> InterleaveAll<<I::Item as IntoIterator>::IntoIter>
+where
+    I: IntoIterator,
+    I::Item: IntoIterator,
+{
+    InterleaveAll {
+        iters: iterable.into_iter().map(IntoIterator::into_iter).collect(),
+        next: 0,
+    }
+}
+
+impl<I: Iterator> Iterator for InterleaveAll<I> {
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while !self.iters.is_empty() {
+            if self.next >= self.iters.len() {
+                self.next = 0;
+            }
+            match self.iters[self.next].next() {
+                Some(elt) => {
+                    self.next += 1;
+                    return Some(elt);
+                }
+                None => {
+                    // The following iterators keep their turn.
+                    self.iters.remove(self.next);
+                }
+            }
+        }
+        None
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        self.iters
+            .iter()
+            .map(Iterator::size_hint)
+            .fold((0, Some(0)), size_hint::add)
+    }
+}
+
+impl<I: Iterator> FusedIterator for InterleaveAll<I> {}
+
+/// An iterator adaptor that alternates elements from an arbitrary number of base
+/// iterators in a round-robin fashion until one of them runs out.
+///
+/// Iterator element type is `I::Item`.
+///
+/// See [`.interleave_all_shortest()`](crate::Itertools::interleave_all_shortest)
+/// for more information.
+#[derive(Clone, Debug)]
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+pub struct InterleaveAllShortest<I> {
+    /// The iterators, or none of them once one has run out.
+    iters: Vec<I>,
+    /// The index of the iterator whose turn it is.
+    next: usize,
+}
+
+/// Create an iterator that alternates elements from the contained iterators in a
+/// round-robin fashion until one of them runs out.
+///
+/// [`IntoIterator`] enabled version of
+/// [`Itertools::interleave_all_shortest`](crate::Itertools::interleave_all_shortest).
+///
+/// ```
+/// use itertools::interleave_all_shortest;
+///
+/// let it = interleave_all_shortest(vec![vec![1, 2, 3], vec![4, 5], vec![6]]);
+/// itertools::assert_equal(it, vec![1, 4, 6, 2, 5]);
+/// ```
+pub fn interleave_all_shortest<I>(
+    iterable: I,
+) -> InterleaveAllShortest<<I::Item as IntoIterator>::IntoIter>
+where
+    I: IntoIterator,
+    I::Item: IntoIterator,
+{
+    InterleaveAllShortest {
+        iters: iterable.into_iter().map(IntoIterator::into_iter).collect(),
+        next: 0,
+    }
+}
+
+impl<I: Iterator> Iterator for InterleaveAllShortest<I> {
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let elt = self.iters.get_mut(self.next)?.next();
+        if elt.is_some() {
+            self.next = (self.next + 1) % self.iters.len();
+        } else {
+            self.iters.clear();
+        }
+        elt
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        let n = self.iters.len();
+        if n == 0 {
+            return (0, Some(0));
+        }
+        // If the iterator at the offset `r` from the current one is the first to run out, after
+        // `len` elements, then `len` rounds of `n` elements and `r` more elements are generated.
+        let rotated = self.iters[self.next..]
+            .iter()
+            .chain(&self.iters[..self.next]);
+        let generated =
+            |len: usize, r: usize| len.checked_mul(n).and_then(|count| count.checked_add(r));
+        let mut low = usize::MAX;
+        let mut upp = None;
+        for (r, iter) in rotated.enumerate() {
+            let (l, u) = iter.size_hint();
+            low = low.min(generated(l, r).unwrap_or(usize::MAX));
+            if let Some(u) = u.and_then(|u| generated(u, r)) {
+                upp = Some(upp.map_or(u, |upp: usize| upp.min(u)));
+            }
+        }
+        (low, upp)
+    }
+}
+
+impl<I: Iterator> FusedIterator for InterleaveAllShortest<I> {}
@@ -49,6 +49,20 @@ where
             self.buffer.extend(self.it.by_ref().take(delta));
         }
     }
+
+    pub fn fill(&mut self) {
+        self.buffer.extend(self.it.by_ref());
+    }
+
+    /// Returns true if the source is known to have at most `steps` elements left,
+    /// so that [`fill`](Self::fill) is not more expensive than taking `steps` steps.
+    pub fn can_fill_within(&self, steps: usize) -> bool {
+        self.it.size_hint().1.map_or(false, |hi| hi <= steps)
+    }
+
+    pub fn buffer_mut(&mut self) -> &mut Vec<I::Item> {
+        &mut self.buffer
+    }
 }
 
 impl<I> LazyBuffer<I>
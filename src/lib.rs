@@ -47,6 +47,11 @@
 //!   - Enabled by default.
 //!   - Enables any item that depend on allocations (like `chunk_by`,
 //!     `kmerge`, `join` and many more).
+//! - `rand`
+//!   - Disabled by default, implies `use_alloc`.
+//!   - Enables the `random` module, whose functions take a random number generator
+//!     from the `rand_core` crate (like `sample`, `shuffled` and `random_combination`).
+//!     `sample_weighted` also needs `use_std`, for its floating point maths.
 //!
 //! ## Rust Version
 //!
@@ -59,7 +64,11 @@ extern crate core as std;
 extern crate alloc;
 
 #[cfg(feature = "use_alloc")]
-use alloc::{collections::VecDeque, string::String, vec::Vec};
+use alloc::{
+    collections::{BTreeMap, VecDeque},
+    string::String,
+    vec::Vec,
+};
 
 pub use either::Either;
 
@@ -99,13 +108,27 @@ pub mod structs {
         FilterOk, Interleave, InterleaveShortest, MapInto, MapOk, Positions, Product, PutBack,
         TakeWhileRef, TupleCombinations, Update, WhileSome,
     };
+    pub use crate::aggregate_runs::{
+        AggregateRuns, RunAggregate, RunAggregates, RunByKey, RunByOrd, RunCollect, RunCount,
+        RunFold, RunFoldWith, RunMaxBy, RunMinBy, RunMinMaxBy, RunProduct, RunReduce, RunSum,
+    };
     pub use crate::all_equal_value_err::AllEqualValueError;
     pub use crate::array_impl::{ArrayWindows, CircularArrayWindows};
     #[cfg(feature = "use_alloc")]
+    pub use crate::cartesian_power::{ArrayCartesianPower, CartesianPower};
+    #[cfg(feature = "use_alloc")]
+    pub use crate::chunk_by_owned::{ChunkByOwned, ChunksOwned};
+    #[cfg(feature = "use_alloc")]
     pub use crate::combinations::{ArrayCombinations, Combinations};
     #[cfg(feature = "use_alloc")]
+    pub use crate::combinations_pruned::CombinationsPruned;
+    #[cfg(feature = "use_alloc")]
     pub use crate::combinations_with_replacement::CombinationsWithReplacement;
     pub use crate::cons_tuples_impl::ConsTuples;
+    #[cfg(feature = "use_alloc")]
+    pub use crate::diagonal_product::{DiagonalProduct, MultiDiagonalProduct};
+    #[cfg(feature = "use_alloc")]
+    pub use crate::distinct_permutations::{DistinctPermutations, DistinctPermutationsBy};
     #[cfg(feature = "use_std")]
     pub use crate::duplicates_impl::{Duplicates, DuplicatesBy};
     pub use crate::exactly_one_err::ExactlyOneError;
@@ -116,14 +139,36 @@ pub mod structs {
     pub use crate::groupbylazy::GroupBy;
     #[cfg(feature = "use_alloc")]
     pub use crate::groupbylazy::{Chunk, ChunkBy, Chunks, Group, Groups, IntoChunks};
+    #[cfg(feature = "use_alloc")]
+    pub use crate::grouping_btree_map::{BTreeMapBackend, GroupingBTreeMap, GroupingBTreeMapBy};
+    #[cfg(feature = "use_alloc")]
+    pub use crate::grouping_map::GroupingMap;
     #[cfg(feature = "use_std")]
-    pub use crate::grouping_map::{GroupingMap, GroupingMapBy};
+    pub use crate::grouping_map::{GroupingMapBy, GroupingMapOk, GroupingMapOkBy};
+    #[cfg(feature = "use_alloc")]
+    pub use crate::grouping_vec::{GroupingVec, GroupingVecBy, KeyOutOfRangeError, VecBackend};
+    #[cfg(feature = "use_alloc")]
+    pub use crate::index_combinatorics::{CombinationIndices, PermutationIndices, ProductIndices};
+    #[cfg(feature = "use_alloc")]
+    pub use crate::interleave_all::{InterleaveAll, InterleaveAllShortest};
     pub use crate::intersperse::{Intersperse, IntersperseWith};
     #[cfg(feature = "use_alloc")]
     pub use crate::kmerge_impl::{KMerge, KMergeBy};
     pub use crate::merge_join::{Merge, MergeBy, MergeJoinBy};
     #[cfg(feature = "use_alloc")]
+    pub use crate::minimal_change::{PermutationSwaps, PermutationsMinimalChange};
+    #[cfg(feature = "use_alloc")]
     pub use crate::multipeek_impl::MultiPeek;
+    #[cfg(feature = "use_std")]
+    pub use crate::multiset_cmp::MultisetByHash;
+    #[cfg(feature = "use_alloc")]
+    pub use crate::multiset_cmp::{MultisetByKey, MultisetByOrd};
+    #[cfg(feature = "use_std")]
+    pub use crate::multiset_combinations::MultisetCombinationsHashed;
+    #[cfg(feature = "use_alloc")]
+    pub use crate::multiset_combinations::{
+        MultisetCombinations, MultisetCombinationsBy, MultisetCombinationsByKey,
+    };
     pub use crate::pad_tail::PadUsing;
     #[cfg(feature = "use_alloc")]
     pub use crate::peek_nth::PeekNth;
@@ -132,12 +177,20 @@ pub mod structs {
     pub use crate::permutations::Permutations;
     #[cfg(feature = "use_alloc")]
     pub use crate::powerset::Powerset;
+    #[cfg(feature = "use_alloc")]
+    pub use crate::powerset_gray::{PowersetGray, PowersetMasks};
     pub use crate::process_results_impl::ProcessResults;
     #[cfg(feature = "use_alloc")]
     pub use crate::put_back_n_impl::PutBackN;
     #[cfg(feature = "use_alloc")]
     pub use crate::rciter_impl::RcIter;
     pub use crate::repeatn::RepeatN;
+    #[cfg(feature = "use_alloc")]
+    pub use crate::set_partitions::SetPartitions;
+    #[cfg(feature = "use_alloc")]
+    pub use crate::slice_combinatorics::{SliceCombinations, SlicePermutations, SlicePowerset};
+    #[cfg(feature = "use_alloc")]
+    pub use crate::sources::{Compositions, IntegerPartitions};
     #[allow(deprecated)]
     pub use crate::sources::{Iterate, Unfold};
     pub use crate::take_while_inclusive::TakeWhileInclusive;
@@ -154,7 +207,14 @@ pub mod structs {
 
 /// Traits helpful for using certain `Itertools` methods in generic contexts.
 pub mod traits {
+    pub use crate::aggregate_runs::{RunAggregator, RunCmp};
+    #[cfg(feature = "use_alloc")]
+    pub use crate::grouping_map::{GroupingBackend, MapValues};
     pub use crate::iter_index::IteratorIndex;
+    #[cfg(feature = "use_alloc")]
+    pub use crate::multiset_cmp::{MultisetCmp, MultisetGrouping};
+    #[cfg(feature = "use_alloc")]
+    pub use crate::powerset_gray::BitMask;
     pub use crate::tuple_impl::HomogeneousTuple;
 }
 
@@ -166,10 +226,18 @@ pub use crate::diff::diff_with;
 pub use crate::diff::Diff;
 #[cfg(feature = "use_alloc")]
 pub use crate::kmerge_impl::kmerge_by;
+#[cfg(feature = "use_alloc")]
+pub use crate::minimal_change::permutation_swaps;
 pub use crate::minmax::MinMaxResult;
 pub use crate::peeking_take_while::PeekingNext;
+#[cfg(feature = "use_alloc")]
+pub use crate::powerset_gray::SubsetDelta;
 pub use crate::process_results_impl::process_results;
 pub use crate::repeatn::repeat_n;
+#[cfg(feature = "use_alloc")]
+pub use crate::slice_combinatorics::SliceCombinatorics;
+#[cfg(feature = "use_alloc")]
+pub use crate::sources::{compositions, integer_partitions};
 #[allow(deprecated)]
 pub use crate::sources::{iterate, unfold};
 #[allow(deprecated)]
@@ -185,14 +253,26 @@ pub use crate::either_or_both::EitherOrBoth;
 pub mod free;
 #[doc(inline)]
 pub use crate::free::*;
+mod aggregate_runs;
 mod all_equal_value_err;
 #[cfg(feature = "use_alloc")]
+mod cartesian_power;
+#[cfg(feature = "use_alloc")]
+mod chunk_by_owned;
+#[cfg(feature = "use_alloc")]
 mod combinations;
 #[cfg(feature = "use_alloc")]
+mod combinations_pruned;
+#[cfg(feature = "use_alloc")]
 mod combinations_with_replacement;
 mod concat_impl;
 mod cons_tuples_impl;
+pub mod count;
+#[cfg(feature = "use_alloc")]
+mod diagonal_product;
 mod diff;
+#[cfg(feature = "use_alloc")]
+mod distinct_permutations;
 #[cfg(feature = "use_std")]
 mod duplicates_impl;
 mod exactly_one_err;
@@ -204,8 +284,16 @@ mod format;
 mod group_map;
 #[cfg(feature = "use_alloc")]
 mod groupbylazy;
-#[cfg(feature = "use_std")]
+#[cfg(feature = "use_alloc")]
+mod grouping_btree_map;
+#[cfg(feature = "use_alloc")]
 mod grouping_map;
+#[cfg(feature = "use_alloc")]
+mod grouping_vec;
+#[cfg(feature = "use_alloc")]
+mod index_combinatorics;
+#[cfg(feature = "use_alloc")]
+mod interleave_all;
 mod intersperse;
 mod iter_index;
 #[cfg(feature = "use_alloc")]
@@ -215,9 +303,15 @@ mod kmerge_impl;
 #[cfg(feature = "use_alloc")]
 mod lazy_buffer;
 mod merge_join;
+#[cfg(feature = "use_alloc")]
+mod minimal_change;
 mod minmax;
 #[cfg(feature = "use_alloc")]
 mod multipeek_impl;
+#[cfg(feature = "use_alloc")]
+mod multiset_cmp;
+#[cfg(feature = "use_alloc")]
+mod multiset_combinations;
 mod next_array;
 mod pad_tail;
 #[cfg(feature = "use_alloc")]
@@ -227,13 +321,21 @@ mod peeking_take_while;
 mod permutations;
 #[cfg(feature = "use_alloc")]
 mod powerset;
+#[cfg(feature = "use_alloc")]
+mod powerset_gray;
 mod process_results_impl;
 #[cfg(feature = "use_alloc")]
 mod put_back_n_impl;
+#[cfg(feature = "rand")]
+pub mod random;
 #[cfg(feature = "use_alloc")]
 mod rciter_impl;
 mod repeatn;
+#[cfg(feature = "use_alloc")]
+mod set_partitions;
 mod size_hint;
+#[cfg(feature = "use_alloc")]
+mod slice_combinatorics;
 mod sources;
 mod take_while_inclusive;
 #[cfg(feature = "use_alloc")]
@@ -496,6 +598,55 @@ pub trait Itertools: Iterator {
         adaptors::interleave_shortest(self, other.into_iter())
     }
 
+    /// Alternate elements from all the iterators of an iterator of iterators in a
+    /// round-robin fashion, until all of them have run out.
+    ///
+    /// Unlike nested [`.interleave()`](Itertools::interleave) calls, each iterator gets its
+    /// turn once per round. The iterators which have run out are removed from the rounds.
+    ///
+    /// Iterator element type is `<Self::Item as IntoIterator>::Item`.
+    ///
+    /// This iterator is *fused*.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// let it = vec![1..4, 10..12, 20..21].into_iter().interleave_all();
+    /// itertools::assert_equal(it, vec![1, 10, 20, 2, 11, 3]);
+    /// ```
+    #[cfg(feature = "use_alloc")]
+    fn interleave_all(self) -> InterleaveAll<<Self::Item as IntoIterator>::IntoIter>
+    where
+        Self: Sized,
+        Self::Item: IntoIterator,
+    {
+        interleave_all(self)
+    }
+
+    /// Alternate elements from all the iterators of an iterator of iterators in a
+    /// round-robin fashion, until one of them has run out.
+    ///
+    /// Iterator element type is `<Self::Item as IntoIterator>::Item`.
+    ///
+    /// This iterator is *fused*.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// let it = vec![1..4, 10..12, 20..30].into_iter().interleave_all_shortest();
+    /// itertools::assert_equal(it, vec![1, 10, 20, 2, 11, 21, 3]);
+    /// ```
+    #[cfg(feature = "use_alloc")]
+    fn interleave_all_shortest(
+        self,
+    ) -> InterleaveAllShortest<<Self::Item as IntoIterator>::IntoIter>
+    where
+        Self: Sized,
+        Self::Item: IntoIterator,
+    {
+        interleave_all_shortest(self)
+    }
+
     /// An iterator adaptor to insert a particular value
     /// between each element of the adapted iterator.
     ///
@@ -790,6 +941,91 @@ pub trait Itertools: Iterator {
         groupbylazy::new_chunks(self, size)
     }
 
+    /// Return an iterator adaptor that groups consecutive elements that map to
+    /// the same key (“runs”) into owned vectors.
+    ///
+    /// Unlike [`.chunk_by()`](Itertools::chunk_by), this is an iterator itself and
+    /// the groups do not borrow from it: it is `Send` when the base iterator,
+    /// its elements, the keys and the key function are.
+    ///
+    /// Iterator element type is `(K, Vec<Self::Item>)`: the group's key and its
+    /// elements.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// let data = vec![1, 3, -2, -2, 1, 0, 1, 2];
+    /// let chunks = data.into_iter().chunk_by_owned(|elt| *elt >= 0);
+    /// itertools::assert_equal(
+    ///     chunks,
+    ///     vec![(true, vec![1, 3]), (false, vec![-2, -2]), (true, vec![1, 0, 1, 2])],
+    /// );
+    /// ```
+    #[cfg(feature = "use_alloc")]
+    fn chunk_by_owned<K, F>(self, key: F) -> ChunkByOwned<K, Self, F>
+    where
+        Self: Sized,
+        F: FnMut(&Self::Item) -> K,
+        K: PartialEq,
+    {
+        chunk_by_owned::chunk_by_owned(self, key)
+    }
+
+    /// Return an iterator adaptor that yields owned chunks of `size` elements.
+    /// The last chunk will be shorter if there aren't enough elements.
+    ///
+    /// Unlike [`.chunks()`](Itertools::chunks), this is an iterator itself and
+    /// the chunks do not borrow from it: it is `Send` when the base iterator
+    /// and its elements are.
+    ///
+    /// Iterator element type is `Vec<Self::Item>`.
+    ///
+    /// **Panics** if `size` is 0.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// let chunks = (1..=8).chunks_owned(3);
+    /// itertools::assert_equal(chunks, vec![vec![1, 2, 3], vec![4, 5, 6], vec![7, 8]]);
+    /// ```
+    #[cfg(feature = "use_alloc")]
+    fn chunks_owned(self, size: usize) -> ChunksOwned<Self>
+    where
+        Self: Sized,
+    {
+        assert!(size != 0);
+        chunk_by_owned::chunks_owned(self, size)
+    }
+
+    /// Return a builder that aggregates the runs of consecutive elements that
+    /// map to the same key, without buffering the elements of a run.
+    ///
+    /// This is meant for inputs that are already sorted (or otherwise
+    /// clustered) by key: each operation of [`AggregateRuns`] returns an
+    /// iterator adaptor that yields `(key, result)` as soon as a run ends,
+    /// keeping only the accumulator of the current run. It offers the same
+    /// operations as [`GroupingMap`], but a key
+    /// that appears in several runs is yielded once per run.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// let sales = [("apple", 3), ("apple", 2), ("kiwi", 5), ("pear", 1), ("pear", 4)];
+    /// let totals = sales.iter()
+    ///     .aggregate_runs(|&&(fruit, _)| fruit)
+    ///     .fold(0, |acc, _fruit, &(_, n)| acc + n);
+    ///
+    /// itertools::assert_equal(totals, vec![("apple", 5), ("kiwi", 5), ("pear", 5)]);
+    /// ```
+    fn aggregate_runs<K, F>(self, key: F) -> AggregateRuns<Self, F>
+    where
+        Self: Sized,
+        F: FnMut(&Self::Item) -> K,
+        K: PartialEq,
+    {
+        aggregate_runs::new(self, key)
+    }
+
     /// Return an iterator over all contiguous windows producing tuples of
     /// a specific size (up to 12).
     ///
@@ -1421,6 +1657,17 @@ pub trait Itertools: Iterator {
     /// assert_eq!(nullary_cartesian_product.next(), Some(vec![]));
     /// assert_eq!(nullary_cartesian_product.next(), None);
     /// ```
+    ///
+    /// When the subiterators have exact size hints, like ranges or slices, `nth` jumps
+    /// directly to the requested item and the product can be iterated from the back.
+    /// See also [`MultiProduct::rank_of`].
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    /// let mut grid = vec![0..10, 0..10, 0..10].into_iter().multi_cartesian_product();
+    /// assert_eq!(grid.nth(123), Some(vec![1, 2, 3]));
+    /// assert_eq!(grid.next_back(), Some(vec![9, 9, 9]));
+    /// ```
     #[cfg(feature = "use_alloc")]
     fn multi_cartesian_product(self) -> MultiProduct<<Self::Item as IntoIterator>::IntoIter>
     where
@@ -1432,6 +1679,80 @@ pub trait Itertools: Iterator {
         adaptors::multi_cartesian_product(self)
     }
 
+    /// Return an iterator adaptor that iterates over the cartesian product of
+    /// the element sets of two iterators `self` and `J`, in Cantor diagonal order.
+    ///
+    /// The pairs are generated by increasing sum of the positions of their elements in
+    /// their iterators, so that each pair is reached in finite time even if both iterators
+    /// are infinite, unlike with [`.cartesian_product()`](Itertools::cartesian_product).
+    /// Pairs with the same sum are ordered by the position of their first element.
+    ///
+    /// The elements of both iterators are buffered lazily, as they are needed.
+    ///
+    /// Iterator element type is `(Self::Item, J::Item)`.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// let it = (0..).diagonal_product("abc".chars());
+    /// itertools::assert_equal(it.take(7), vec![
+    ///     (0, 'a'),
+    ///     (0, 'b'),
+    ///     (1, 'a'),
+    ///     (0, 'c'),
+    ///     (1, 'b'),
+    ///     (2, 'a'),
+    ///     (1, 'c'),
+    /// ]);
+    /// ```
+    #[cfg(feature = "use_alloc")]
+    fn diagonal_product<J>(self, other: J) -> DiagonalProduct<Self, J::IntoIter>
+    where
+        Self: Sized,
+        Self::Item: Clone,
+        J: IntoIterator,
+        J::Item: Clone,
+    {
+        diagonal_product::diagonal_product(self, other)
+    }
+
+    /// Return an iterator adaptor that iterates over the cartesian product of
+    /// all subiterators returned by meta-iterator `self`, in Cantor diagonal order.
+    ///
+    /// This is like [`.multi_cartesian_product()`](Itertools::multi_cartesian_product),
+    /// but the items are generated by increasing sum of the positions of their elements
+    /// in their iterators, so that each item is reached in finite time even if the
+    /// subiterators are infinite. Items with the same sum are in lexicographic order of
+    /// these positions. See also [`.diagonal_product()`](Itertools::diagonal_product).
+    ///
+    /// The elements of the subiterators are buffered lazily, as they are needed, so the
+    /// subiterators do not need to be `Clone`.
+    ///
+    /// The iterator element type is `Vec<T>`, where `T` is the iterator element
+    /// of the subiterators.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// let it = (0..3).map(|_| 0..).multi_diagonal_product();
+    /// itertools::assert_equal(it.take(5), vec![
+    ///     vec![0, 0, 0],
+    ///     vec![0, 0, 1],
+    ///     vec![0, 1, 0],
+    ///     vec![1, 0, 0],
+    ///     vec![0, 0, 2],
+    /// ]);
+    /// ```
+    #[cfg(feature = "use_alloc")]
+    fn multi_diagonal_product(self) -> MultiDiagonalProduct<<Self::Item as IntoIterator>::IntoIter>
+    where
+        Self: Sized,
+        Self::Item: IntoIterator,
+        <Self::Item as IntoIterator>::Item: Clone,
+    {
+        diagonal_product::multi_diagonal_product(self)
+    }
+
     /// Return an iterator adaptor that uses the passed-in closure to
     /// optionally merge together consecutive elements.
     ///
@@ -2038,6 +2359,30 @@ pub trait Itertools: Iterator {
     ///     vec![2, 2],
     /// ]);
     /// ```
+    ///
+    /// Note: Iterating from the back first collects the entire source iterator.
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// let it = (1..5).combinations(3).rev();
+    /// itertools::assert_equal(it, vec![
+    ///     vec![2, 3, 4],
+    ///     vec![1, 3, 4],
+    ///     vec![1, 2, 4],
+    ///     vec![1, 2, 3],
+    /// ]);
+    /// ```
+    ///
+    /// Note: `nth(n)` only jumps directly to the target combination when the upper bound of the
+    /// `size_hint` of the not yet collected part of the source is at most `n`: it then collects
+    /// the source entirely to know the length of the pool. Otherwise, for example with an
+    /// unbounded source or when `n` is small, it steps through the combinations one by one.
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// let mut it = (0..10).combinations(3);
+    /// assert_eq!(it.nth(100), Some(vec![4, 5, 6]));
+    /// ```
     #[cfg(feature = "use_alloc")]
     fn combinations(self, k: usize) -> Combinations<Self>
     where
@@ -2047,6 +2392,131 @@ pub trait Itertools: Iterator {
         combinations::combinations(self, k)
     }
 
+    /// Return an iterator adaptor that iterates over the `k`-length combinations of
+    /// the elements from an iterator whose prefixes are all accepted by `f`.
+    ///
+    /// This is a backtracking search: as soon as `f` returns `false` for a prefix, the
+    /// combinations starting with it are skipped without being generated. Combinations are
+    /// generated in the same lexicographic order as [`.combinations(k)`](Itertools::combinations).
+    ///
+    /// `f` is called on each non-empty prefix which can be completed into a `k`-length
+    /// combination, so it is called on the full combinations too.
+    ///
+    /// Iterator element type is `Vec<Self::Item>`. The iterator produces a new `Vec` per iteration,
+    /// and clones the iterator elements.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// // Combinations whose sum does not exceed 7.
+    /// let it = (1..6).combinations_pruned(3, |prefix| prefix.iter().sum::<i32>() <= 7);
+    /// itertools::assert_equal(it, vec![
+    ///     vec![1, 2, 3],
+    ///     vec![1, 2, 4],
+    /// ]);
+    /// ```
+    #[cfg(feature = "use_alloc")]
+    fn combinations_pruned<F>(self, k: usize, f: F) -> CombinationsPruned<Self, F>
+    where
+        Self: Sized,
+        Self::Item: Clone,
+        F: FnMut(&[Self::Item]) -> bool,
+    {
+        combinations_pruned::combinations_pruned(self, k, f)
+    }
+
+    /// Return an iterator adaptor that iterates over the distinct `k`-length combinations
+    /// of the elements from an iterator, where equal elements are not distinguished.
+    ///
+    /// Each combination is sorted, and combinations are generated in lexicographic order.
+    ///
+    /// Iterator element type is `Vec<Self::Item>`. The iterator produces a new `Vec` per iteration,
+    /// and clones the iterator elements.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// let it = "abcab".chars().multiset_combinations(2);
+    /// itertools::assert_equal(it, vec![
+    ///     vec!['a', 'a'],
+    ///     vec!['a', 'b'],
+    ///     vec!['a', 'c'],
+    ///     vec!['b', 'b'],
+    ///     vec!['b', 'c'],
+    /// ]);
+    /// ```
+    ///
+    /// Note: The source iterator is entirely collected by the first call to `next`.
+    #[cfg(feature = "use_alloc")]
+    fn multiset_combinations(self, k: usize) -> MultisetCombinations<Self>
+    where
+        Self: Sized,
+        Self::Item: Clone + Ord,
+    {
+        multiset_combinations::multiset_combinations(self, k)
+    }
+
+    /// Return an iterator adaptor that iterates over the distinct `k`-length combinations
+    /// of the elements from an iterator, where elements with equal keys are not distinguished.
+    ///
+    /// Elements are sorted by key, and for each key the first elements from the iterator
+    /// are picked. See [`.multiset_combinations()`](Itertools::multiset_combinations)
+    /// for more information.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// let it = vec![3, -1, 1, -3].into_iter().multiset_combinations_by_key(2, |x: &i32| x.abs());
+    /// itertools::assert_equal(it, vec![
+    ///     vec![-1, 1],
+    ///     vec![-1, 3],
+    ///     vec![3, -3],
+    /// ]);
+    /// ```
+    #[cfg(feature = "use_alloc")]
+    fn multiset_combinations_by_key<K, F>(
+        self,
+        k: usize,
+        key: F,
+    ) -> MultisetCombinationsByKey<Self, F>
+    where
+        Self: Sized,
+        Self::Item: Clone,
+        K: Ord,
+        F: FnMut(&Self::Item) -> K,
+    {
+        multiset_combinations::multiset_combinations_by_key(self, k, key)
+    }
+
+    /// Return an iterator adaptor that iterates over the distinct `k`-length combinations
+    /// of the elements from an iterator, where equal elements are not distinguished.
+    ///
+    /// Equal elements are gathered by hashing instead of sorting, so they only need to be
+    /// `Hash + Eq`. The elements of each combination, and the combinations themselves, are
+    /// ordered by the first appearance of each element in the iterator.
+    /// See [`.multiset_combinations()`](Itertools::multiset_combinations) for more information.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// let it = "cabca".chars().multiset_combinations_hashed(2);
+    /// itertools::assert_equal(it, vec![
+    ///     vec!['c', 'c'],
+    ///     vec!['c', 'a'],
+    ///     vec!['c', 'b'],
+    ///     vec!['a', 'a'],
+    ///     vec!['a', 'b'],
+    /// ]);
+    /// ```
+    #[cfg(feature = "use_std")]
+    fn multiset_combinations_hashed(self, k: usize) -> MultisetCombinationsHashed<Self>
+    where
+        Self: Sized,
+        Self::Item: Clone + Hash + Eq,
+    {
+        multiset_combinations::multiset_combinations_hashed(self, k)
+    }
+
     /// Return an iterator that iterates over the `k`-length combinations of
     /// the elements from an iterator, with replacement.
     ///
@@ -2056,137 +2526,486 @@ pub trait Itertools: Iterator {
     /// ```
     /// use itertools::Itertools;
     ///
-    /// let it = (1..4).combinations_with_replacement(2);
-    /// itertools::assert_equal(it, vec![
-    ///     vec![1, 1],
+    /// let it = (1..4).combinations_with_replacement(2);
+    /// itertools::assert_equal(it, vec![
+    ///     vec![1, 1],
+    ///     vec![1, 2],
+    ///     vec![1, 3],
+    ///     vec![2, 2],
+    ///     vec![2, 3],
+    ///     vec![3, 3],
+    /// ]);
+    /// ```
+    #[cfg(feature = "use_alloc")]
+    fn combinations_with_replacement(self, k: usize) -> CombinationsWithReplacement<Self>
+    where
+        Self: Sized,
+        Self::Item: Clone,
+    {
+        combinations_with_replacement::combinations_with_replacement(self, k)
+    }
+    /// Return an iterator that iterates over the `k`-length combinations of
+    /// the elements from an iterator, with replacement.
+    ///
+    /// Iterator element type is [Self::Item; K]. The iterator produces a new
+    /// array per iteration, and clones the iterator elements.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// let it = (1..4).array_combinations_with_replacement::<2>();
+    /// itertools::assert_equal(it, vec![
+    ///     [1, 1],
+    ///     [1, 2],
+    ///     [1, 3],
+    ///     [2, 2],
+    ///     [2, 3],
+    ///     [3, 3],
+    /// ]);
+    /// ```
+    #[cfg(feature = "use_alloc")]
+    fn array_combinations_with_replacement<const K: usize>(
+        self,
+    ) -> ArrayCombinationsWithReplacement<Self, K>
+    where
+        Self: Sized,
+        Self::Item: Clone,
+    {
+        combinations_with_replacement::array_combinations_with_replacement(self)
+    }
+
+    /// Return an iterator adaptor that iterates over the `k`-length tuples of the
+    /// elements from an iterator, with replacement: the `k`-th cartesian power of the
+    /// iterator with itself.
+    ///
+    /// Iterator element type is `Vec<Self::Item>`. The iterator produces a new `Vec` per
+    /// iteration, and clones the iterator elements.
+    ///
+    /// The tuples are generated in odometer order, the last element changing fastest.
+    /// Unlike repeating the iterator `k` times with
+    /// [`.multi_cartesian_product()`](Itertools::multi_cartesian_product), the source
+    /// iterator is only consumed once, and its elements are buffered as they are needed.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// let it = "ab".chars().cartesian_power(3);
+    /// itertools::assert_equal(it, vec![
+    ///     vec!['a', 'a', 'a'],
+    ///     vec!['a', 'a', 'b'],
+    ///     vec!['a', 'b', 'a'],
+    ///     vec!['a', 'b', 'b'],
+    ///     vec!['b', 'a', 'a'],
+    ///     vec!['b', 'a', 'b'],
+    ///     vec!['b', 'b', 'a'],
+    ///     vec!['b', 'b', 'b'],
+    /// ]);
+    ///
+    /// // Jumps straight to a tuple once the source is known to be finite.
+    /// assert_eq!((0..10).cartesian_power(6).nth(123_456), Some(vec![1, 2, 3, 4, 5, 6]));
+    /// ```
+    ///
+    /// Note: If the source iterator has a finite upper bound in its `size_hint`, it is
+    /// entirely collected by `nth` to compute the target tuple directly.
+    #[cfg(feature = "use_alloc")]
+    fn cartesian_power(self, k: usize) -> CartesianPower<Self>
+    where
+        Self: Sized,
+        Self::Item: Clone,
+    {
+        cartesian_power::cartesian_power(self, k)
+    }
+
+    /// Return an iterator adaptor that iterates over the `K`-length tuples of the
+    /// elements from an iterator, with replacement.
+    ///
+    /// Iterator element type is `[Self::Item; K]`. The iterator produces a new
+    /// array per iteration, and clones the iterator elements.
+    ///
+    /// See [`.cartesian_power()`](Itertools::cartesian_power) for more information.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// let it = (0..2).array_cartesian_power::<2>();
+    /// itertools::assert_equal(it, vec![[0, 0], [0, 1], [1, 0], [1, 1]]);
+    /// ```
+    #[cfg(feature = "use_alloc")]
+    fn array_cartesian_power<const K: usize>(self) -> ArrayCartesianPower<Self, K>
+    where
+        Self: Sized,
+        Self::Item: Clone,
+    {
+        cartesian_power::array_cartesian_power(self)
+    }
+    /// Return an iterator adaptor that iterates over all k-permutations of the
+    /// elements from an iterator.
+    ///
+    /// Iterator element type is `Vec<Self::Item>` with length `k`. The iterator
+    /// produces a new `Vec` per iteration, and clones the iterator elements.
+    ///
+    /// If `k` is greater than the length of the input iterator, the resultant
+    /// iterator adaptor will be empty.
+    ///
+    /// If you are looking for permutations with replacements,
+    /// use `repeat_n(iter, k).multi_cartesian_product()` instead.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// let perms = (5..8).permutations(2);
+    /// itertools::assert_equal(perms, vec![
+    ///     vec![5, 6],
+    ///     vec![5, 7],
+    ///     vec![6, 5],
+    ///     vec![6, 7],
+    ///     vec![7, 5],
+    ///     vec![7, 6],
+    /// ]);
+    /// ```
+    ///
+    /// Note: Permutations does not take into account the equality of the iterated values.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// let it = vec![2, 2].into_iter().permutations(2);
+    /// itertools::assert_equal(it, vec![
+    ///     vec![2, 2], // Note: these are the same
+    ///     vec![2, 2], // Note: these are the same
+    /// ]);
+    /// ```
+    ///
+    /// Note: The source iterator is collected lazily, and will not be
+    /// re-iterated if the permutations adaptor is completed and re-iterated.
+    /// Iterating from the back first collects the entire source iterator.
+    #[cfg(feature = "use_alloc")]
+    fn permutations(self, k: usize) -> Permutations<Self>
+    where
+        Self: Sized,
+        Self::Item: Clone,
+    {
+        permutations::permutations(self, k)
+    }
+
+    /// Return an iterator adaptor that iterates over all permutations of the elements
+    /// from an iterator, such that consecutive permutations only differ by a swap of
+    /// two adjacent elements.
+    ///
+    /// Iterator element type is `(Vec<Self::Item>, Option<(usize, usize)>)`: each
+    /// permutation comes with the positions `(i, i + 1)` swapped to produce it from the
+    /// previous one, or `None` for the first permutation which is the source order.
+    /// The iterator produces a new `Vec` per iteration, and clones the iterator elements.
+    ///
+    /// Permutations are generated in Steinhaus–Johnson–Trotter order. Use
+    /// [`permutation_swaps`] to only get the swaps and permute a slice in place instead.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// let perms = (1..4).permutations_minimal_change();
+    /// itertools::assert_equal(perms, vec![
+    ///     (vec![1, 2, 3], None),
+    ///     (vec![1, 3, 2], Some((1, 2))),
+    ///     (vec![3, 1, 2], Some((0, 1))),
+    ///     (vec![3, 2, 1], Some((1, 2))),
+    ///     (vec![2, 3, 1], Some((0, 1))),
+    ///     (vec![2, 1, 3], Some((1, 2))),
+    /// ]);
+    /// ```
+    ///
+    /// Note: The source iterator is entirely collected by the first call to `next`.
+    #[cfg(feature = "use_alloc")]
+    fn permutations_minimal_change(self) -> PermutationsMinimalChange<Self>
+    where
+        Self: Sized,
+        Self::Item: Clone,
+    {
+        minimal_change::permutations_minimal_change(self)
+    }
+
+    /// Return an iterator adaptor that iterates over all distinct permutations of the
+    /// elements from an iterator, in lexicographic order.
+    ///
+    /// Unlike [`.permutations()`](Itertools::permutations), equal elements are not
+    /// distinguished, so each distinct arrangement is generated exactly once.
+    ///
+    /// Iterator element type is `Vec<Self::Item>` with the length of the input iterator.
+    /// The iterator produces a new `Vec` per iteration, and clones the iterator elements.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// let perms = vec![2, 1, 1].into_iter().distinct_permutations();
+    /// itertools::assert_equal(perms, vec![
+    ///     vec![1, 1, 2],
+    ///     vec![1, 2, 1],
+    ///     vec![2, 1, 1],
+    /// ]);
+    /// ```
+    ///
+    /// Note: The source iterator is entirely collected by the first call to `next`.
+    #[cfg(feature = "use_alloc")]
+    fn distinct_permutations(self) -> DistinctPermutations<Self>
+    where
+        Self: Sized,
+        Self::Item: Clone + Ord,
+    {
+        distinct_permutations::distinct_permutations(self)
+    }
+
+    /// Return an iterator adaptor that iterates over all distinct permutations of the
+    /// elements from an iterator, in lexicographic order according to the given
+    /// comparison function.
+    ///
+    /// Elements that compare equal are not distinguished.
+    /// See [`.distinct_permutations()`](Itertools::distinct_permutations) for more information.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// // Reversed lexicographic order
+    /// let perms = vec![1, 2, 2].into_iter().distinct_permutations_by(|a, b| b.cmp(a));
+    /// itertools::assert_equal(perms, vec![
+    ///     vec![2, 2, 1],
+    ///     vec![2, 1, 2],
+    ///     vec![1, 2, 2],
+    /// ]);
+    /// ```
+    #[cfg(feature = "use_alloc")]
+    fn distinct_permutations_by<F>(self, cmp: F) -> DistinctPermutationsBy<Self, F>
+    where
+        Self: Sized,
+        Self::Item: Clone,
+        F: FnMut(&Self::Item, &Self::Item) -> Ordering,
+    {
+        distinct_permutations::distinct_permutations_by(self, cmp)
+    }
+
+    /// Return an iterator that iterates through the powerset of the elements from an
+    /// iterator.
+    ///
+    /// Iterator element type is `Vec<Self::Item>`. The iterator produces a new `Vec`
+    /// per iteration, and clones the iterator elements.
+    ///
+    /// The powerset of a set contains all subsets including the empty set and the full
+    /// input set. A powerset has length _2^n_ where _n_ is the length of the input
+    /// set.
+    ///
+    /// Each `Vec` produced by this iterator represents a subset of the elements
+    /// produced by the source iterator.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// let sets = (1..4).powerset().collect::<Vec<_>>();
+    /// itertools::assert_equal(sets, vec![
+    ///     vec![],
+    ///     vec![1],
+    ///     vec![2],
+    ///     vec![3],
+    ///     vec![1, 2],
+    ///     vec![1, 3],
+    ///     vec![2, 3],
+    ///     vec![1, 2, 3],
+    /// ]);
+    /// ```
+    ///
+    /// Note: Iterating from the back first collects the entire source iterator.
+    #[cfg(feature = "use_alloc")]
+    fn powerset(self) -> Powerset<Self>
+    where
+        Self: Sized,
+        Self::Item: Clone,
+    {
+        powerset::powerset(self)
+    }
+
+    /// Return an iterator that iterates through the subsets of the elements from an
+    /// iterator whose sizes are in the given range, in the same order as
+    /// [`.powerset()`](Itertools::powerset).
+    ///
+    /// This is like chaining [`.combinations(k)`](Itertools::combinations) for each `k`
+    /// in the range, but the elements of the source iterator are only buffered once.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// let sets = (1..5).powerset_range(2..=3).collect::<Vec<_>>();
+    /// itertools::assert_equal(sets, vec![
     ///     vec![1, 2],
     ///     vec![1, 3],
-    ///     vec![2, 2],
+    ///     vec![1, 4],
     ///     vec![2, 3],
-    ///     vec![3, 3],
+    ///     vec![2, 4],
+    ///     vec![3, 4],
+    ///     vec![1, 2, 3],
+    ///     vec![1, 2, 4],
+    ///     vec![1, 3, 4],
+    ///     vec![2, 3, 4],
     /// ]);
+    /// assert_eq!((1..5).powerset_range(..2).count(), 5);
+    /// assert_eq!((1..5).powerset_range(3..).rev().next(), Some(vec![1, 2, 3, 4]));
     /// ```
     #[cfg(feature = "use_alloc")]
-    fn combinations_with_replacement(self, k: usize) -> CombinationsWithReplacement<Self>
+    fn powerset_range<R>(self, range: R) -> Powerset<Self>
     where
         Self: Sized,
         Self::Item: Clone,
+        R: std::ops::RangeBounds<usize>,
     {
-        combinations_with_replacement::combinations_with_replacement(self, k)
+        powerset::powerset_range(self, range)
     }
-    /// Return an iterator that iterates over the `k`-length combinations of
-    /// the elements from an iterator, with replacement.
+
+    /// Return an iterator that walks through the powerset of the elements from an
+    /// iterator in binary-reflected Gray code order, yielding only the element that
+    /// changes between consecutive subsets.
     ///
-    /// Iterator element type is [Self::Item; K]. The iterator produces a new
-    /// array per iteration, and clones the iterator elements.
+    /// Iterator element type is [`SubsetDelta<Self::Item>`](SubsetDelta): starting from
+    /// the empty set, each subset is obtained from the previous one by adding or removing
+    /// a single element, and all the _2^n_ subsets are reached after _2^n - 1_ deltas.
+    /// This avoids allocating a `Vec` per subset, which makes it suited to subset-sum
+    /// style searches where the state can be updated incrementally.
+    ///
+    /// The `j`-th element is only pulled from the source iterator after the _2^j_ subsets
+    /// of the previous elements have been visited.
     ///
     /// ```
     /// use itertools::Itertools;
+    /// use itertools::SubsetDelta::{Added, Removed};
     ///
-    /// let it = (1..4).array_combinations_with_replacement::<2>();
-    /// itertools::assert_equal(it, vec![
-    ///     [1, 1],
-    ///     [1, 2],
-    ///     [1, 3],
-    ///     [2, 2],
-    ///     [2, 3],
-    ///     [3, 3],
+    /// let deltas = (1..4).powerset_gray();
+    /// itertools::assert_equal(deltas, vec![
+    ///     Added(1),   // {1}
+    ///     Added(2),   // {1, 2}
+    ///     Removed(1), // {2}
+    ///     Added(3),   // {2, 3}
+    ///     Added(1),   // {1, 2, 3}
+    ///     Removed(2), // {1, 3}
+    ///     Removed(1), // {3}
     /// ]);
+    ///
+    /// // Find the subsets summing to 6.
+    /// let mut sum = 0;
+    /// let mut found = 0;
+    /// for delta in [1, 2, 3, 4, 5].iter().powerset_gray() {
+    ///     match delta {
+    ///         Added(x) => sum += x,
+    ///         Removed(x) => sum -= x,
+    ///     }
+    ///     found += usize::from(sum == 6);
+    /// }
+    /// assert_eq!(found, 3); // {1, 2, 3}, {2, 4}, {1, 5}
     /// ```
     #[cfg(feature = "use_alloc")]
-    fn array_combinations_with_replacement<const K: usize>(
-        self,
-    ) -> ArrayCombinationsWithReplacement<Self, K>
+    fn powerset_gray(self) -> PowersetGray<Self>
     where
         Self: Sized,
         Self::Item: Clone,
     {
-        combinations_with_replacement::array_combinations_with_replacement(self)
+        powerset_gray::powerset_gray(self)
     }
-    /// Return an iterator adaptor that iterates over all k-permutations of the
-    /// elements from an iterator.
-    ///
-    /// Iterator element type is `Vec<Self::Item>` with length `k`. The iterator
-    /// produces a new `Vec` per iteration, and clones the iterator elements.
+
+    /// Return an iterator over the bitmasks of all the subsets of the elements from an
+    /// iterator, where the bit `j` is set when the `j`-th element is in the subset.
     ///
-    /// If `k` is greater than the length of the input iterator, the resultant
-    /// iterator adaptor will be empty.
+    /// Iterator element type is `M`, any unsigned integer type (`u64`, `u128`, ...).
+    /// The _2^n_ masks are generated in increasing order, from `0` to _2^n - 1_, and the
+    /// elements themselves are neither stored nor cloned: only their count matters.
     ///
-    /// If you are looking for permutations with replacements,
-    /// use `repeat_n(iter, k).multi_cartesian_product()` instead.
+    /// The `j`-th element is only pulled from the source iterator when the mask _2^j_
+    /// is reached.
     ///
     /// ```
     /// use itertools::Itertools;
     ///
-    /// let perms = (5..8).permutations(2);
-    /// itertools::assert_equal(perms, vec![
-    ///     vec![5, 6],
-    ///     vec![5, 7],
-    ///     vec![6, 5],
-    ///     vec![6, 7],
-    ///     vec![7, 5],
-    ///     vec![7, 6],
-    /// ]);
+    /// let items = ["a", "b", "c"];
+    /// let masks: Vec<u64> = items.iter().powerset_masks().collect();
+    /// assert_eq!(masks, (0..8).collect::<Vec<_>>());
+    ///
+    /// let subsets = items.iter().powerset_masks::<u8>().map(|mask| {
+    ///     (0..items.len())
+    ///         .filter(|j| mask >> j & 1 == 1)
+    ///         .map(|j| items[j])
+    ///         .collect::<String>()
+    /// });
+    /// itertools::assert_equal(subsets, ["", "a", "b", "ab", "c", "ac", "bc", "abc"]);
     /// ```
     ///
-    /// Note: Permutations does not take into account the equality of the iterated values.
+    /// # Panics
+    ///
+    /// Panics if the iterator has more elements than there are bits in `M`,
+    /// once all the representable masks have been generated.
+    #[cfg(feature = "use_alloc")]
+    fn powerset_masks<M>(self) -> PowersetMasks<Self, M>
+    where
+        Self: Sized,
+        M: traits::BitMask,
+    {
+        powerset_gray::powerset_masks(self)
+    }
+
+    /// Return an iterator adaptor that iterates over all the ways to partition the
+    /// elements from an iterator into non-empty blocks.
+    ///
+    /// Iterator element type is `Vec<Vec<Self::Item>>`: the blocks are ordered by their
+    /// first element, and the elements keep their order within each block.
+    /// The iterator produces new `Vec`s per iteration, and clones the iterator elements.
+    ///
+    /// The partitions are generated in lexicographic order of their restricted growth
+    /// string, that is the sequence of the block index of each element.
+    /// There are _B(n)_ partitions where _B_ is the Bell number sequence
+    /// (1, 1, 2, 5, 15, 52, 203, ...).
     ///
     /// ```
     /// use itertools::Itertools;
     ///
-    /// let it = vec![2, 2].into_iter().permutations(2);
-    /// itertools::assert_equal(it, vec![
-    ///     vec![2, 2], // Note: these are the same
-    ///     vec![2, 2], // Note: these are the same
+    /// let partitions = (1..4).set_partitions();
+    /// itertools::assert_equal(partitions, vec![
+    ///     vec![vec![1, 2, 3]],
+    ///     vec![vec![1, 2], vec![3]],
+    ///     vec![vec![1, 3], vec![2]],
+    ///     vec![vec![1], vec![2, 3]],
+    ///     vec![vec![1], vec![2], vec![3]],
     /// ]);
     /// ```
     ///
-    /// Note: The source iterator is collected lazily, and will not be
-    /// re-iterated if the permutations adaptor is completed and re-iterated.
+    /// Note: The source iterator is entirely collected by the first call to `next`.
     #[cfg(feature = "use_alloc")]
-    fn permutations(self, k: usize) -> Permutations<Self>
+    fn set_partitions(self) -> SetPartitions<Self>
     where
         Self: Sized,
         Self::Item: Clone,
     {
-        permutations::permutations(self, k)
+        set_partitions::set_partitions(self, None)
     }
 
-    /// Return an iterator that iterates through the powerset of the elements from an
-    /// iterator.
-    ///
-    /// Iterator element type is `Vec<Self::Item>`. The iterator produces a new `Vec`
-    /// per iteration, and clones the iterator elements.
-    ///
-    /// The powerset of a set contains all subsets including the empty set and the full
-    /// input set. A powerset has length _2^n_ where _n_ is the length of the input
-    /// set.
+    /// Return an iterator adaptor that iterates over all the ways to partition the
+    /// elements from an iterator into exactly `k` non-empty blocks.
     ///
-    /// Each `Vec` produced by this iterator represents a subset of the elements
-    /// produced by the source iterator.
+    /// There are _S(n, k)_ such partitions, the Stirling number of the second kind.
+    /// See [`.set_partitions()`](Itertools::set_partitions) for more information.
     ///
     /// ```
     /// use itertools::Itertools;
     ///
-    /// let sets = (1..4).powerset().collect::<Vec<_>>();
-    /// itertools::assert_equal(sets, vec![
-    ///     vec![],
-    ///     vec![1],
-    ///     vec![2],
-    ///     vec![3],
-    ///     vec![1, 2],
-    ///     vec![1, 3],
-    ///     vec![2, 3],
-    ///     vec![1, 2, 3],
+    /// let partitions = (1..5).set_partitions_k(3);
+    /// itertools::assert_equal(partitions, vec![
+    ///     vec![vec![1, 2], vec![3], vec![4]],
+    ///     vec![vec![1, 3], vec![2], vec![4]],
+    ///     vec![vec![1], vec![2, 3], vec![4]],
+    ///     vec![vec![1, 4], vec![2], vec![3]],
+    ///     vec![vec![1], vec![2, 4], vec![3]],
+    ///     vec![vec![1], vec![2], vec![3, 4]],
     /// ]);
     /// ```
     #[cfg(feature = "use_alloc")]
-    fn powerset(self) -> Powerset<Self>
+    fn set_partitions_k(self, k: usize) -> SetPartitions<Self>
     where
         Self: Sized,
         Self::Item: Clone,
     {
-        powerset::powerset(self)
+        set_partitions::set_partitions(self, Some(k))
     }
 
     /// Return an iterator adaptor that pads the sequence to a minimum length of
@@ -4190,6 +5009,57 @@ pub trait Itertools: Iterator {
         grouping_map::new(self, hash_builder)
     }
 
+    /// Constructs a `GroupingMapOk` to be used later with one of the efficient
+    /// group-and-fold operations it allows to perform, over an iterator of results.
+    ///
+    /// The input iterator must yield item in the form of `Result<(K, V), E>`. The operations
+    /// group and fold the `Ok` elements like a [`GroupingMap`], and return the first error
+    /// instead, if any.
+    ///
+    /// See [`GroupingMapOk`] for more information.
+    #[cfg(feature = "use_std")]
+    fn into_grouping_map_ok<K, V, E>(self) -> GroupingMapOk<Self>
+    where
+        Self: Iterator<Item = Result<(K, V), E>> + Sized,
+        K: Hash + Eq,
+    {
+        grouping_map::new_ok(self, RandomState::new())
+    }
+
+    /// Constructs a `GroupingMapOk` to be used later with one of the efficient
+    /// group-and-fold operations it allows to perform, using the specified hash builder for
+    /// hashing the keys.
+    /// See [.into_grouping_map_ok()](crate::Itertools::into_grouping_map_ok) for more information.
+    #[cfg(feature = "use_std")]
+    fn into_grouping_map_ok_with_hasher<K, V, E, S>(self, hash_builder: S) -> GroupingMapOk<Self, S>
+    where
+        Self: Iterator<Item = Result<(K, V), E>> + Sized,
+        K: Hash + Eq,
+        S: BuildHasher,
+    {
+        grouping_map::new_ok(self, hash_builder)
+    }
+
+    /// Constructs a `GroupingMapOk` to be used later with one of the efficient
+    /// group-and-fold operations it allows to perform, over an iterator of results.
+    ///
+    /// The `Ok` values from this iterator will be used as values for the folding operation
+    /// while the keys will be obtained from them by calling `key_mapper`.
+    ///
+    /// See [`GroupingMapOk`] for more information.
+    #[cfg(feature = "use_std")]
+    fn into_grouping_map_ok_by<K, V, E, F>(self, key_mapper: F) -> GroupingMapOkBy<Self, F>
+    where
+        Self: Iterator<Item = Result<V, E>> + Sized,
+        K: Hash + Eq,
+        F: FnMut(&V) -> K,
+    {
+        grouping_map::new_ok(
+            grouping_map::new_map_ok_for_grouping(self, key_mapper),
+            RandomState::new(),
+        )
+    }
+
     /// Constructs a `GroupingMap` to be used later with one of the efficient
     /// group-and-fold operations it allows to perform.
     ///
@@ -4233,6 +5103,134 @@ pub trait Itertools: Iterator {
         )
     }
 
+    /// Return a `BTreeMap` of keys mapped to `Vec`s of values. Keys and values
+    /// are taken from `(Key, Value)` tuple pairs yielded by the input iterator.
+    ///
+    /// This is the ordered counterpart of [`.into_group_map()`](Itertools::into_group_map):
+    /// keys only need to be `Ord` and the map iterates over them in order.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// let data = vec![(3, 13), (0, 10), (2, 12), (0, 20), (3, 33), (2, 42)];
+    /// let lookup = data.into_iter().into_group_btree_map();
+    ///
+    /// itertools::assert_equal(
+    ///     lookup,
+    ///     vec![(0, vec![10, 20]), (2, vec![12, 42]), (3, vec![13, 33])],
+    /// );
+    /// ```
+    #[cfg(feature = "use_alloc")]
+    fn into_group_btree_map<K, V>(self) -> BTreeMap<K, Vec<V>>
+    where
+        Self: Iterator<Item = (K, V)> + Sized,
+        K: Ord,
+    {
+        group_map::into_group_btree_map(self)
+    }
+
+    /// Return a `BTreeMap` of keys mapped to `Vec`s of values. The key is specified
+    /// in the closure. The values are taken from the input iterator.
+    ///
+    /// This is the ordered counterpart of [`.into_group_map_by()`](Itertools::into_group_map_by).
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// let lookup = vec!["bb", "a", "ccc", "dd", "e"].into_iter().into_group_btree_map_by(|s| s.len());
+    ///
+    /// itertools::assert_equal(
+    ///     lookup,
+    ///     vec![(1, vec!["a", "e"]), (2, vec!["bb", "dd"]), (3, vec!["ccc"])],
+    /// );
+    /// ```
+    #[cfg(feature = "use_alloc")]
+    fn into_group_btree_map_by<K, V, F>(self, f: F) -> BTreeMap<K, Vec<V>>
+    where
+        Self: Iterator<Item = V> + Sized,
+        K: Ord,
+        F: FnMut(&V) -> K,
+    {
+        group_map::into_group_btree_map_by(self, f)
+    }
+
+    /// Constructs a `GroupingBTreeMap` to be used later with one of the efficient
+    /// group-and-fold operations it allows to perform.
+    ///
+    /// The input iterator must yield item in the form of `(K, V)` where the
+    /// value of type `K` will be used as key to identify the groups and the
+    /// value of type `V` as value for the folding operation.
+    ///
+    /// This is the ordered counterpart of [`.into_grouping_map()`](Itertools::into_grouping_map).
+    ///
+    /// See [`GroupingBTreeMap`] for more information
+    /// on what operations are available.
+    #[cfg(feature = "use_alloc")]
+    fn into_grouping_btree_map<K, V>(self) -> GroupingBTreeMap<Self>
+    where
+        Self: Iterator<Item = (K, V)> + Sized,
+        K: Ord,
+    {
+        grouping_btree_map::new(self)
+    }
+
+    /// Constructs a `GroupingBTreeMap` to be used later with one of the efficient
+    /// group-and-fold operations it allows to perform.
+    ///
+    /// The values from this iterator will be used as values for the folding operation
+    /// while the keys will be obtained from the values by calling `key_mapper`.
+    ///
+    /// This is the ordered counterpart of [`.into_grouping_map_by()`](Itertools::into_grouping_map_by).
+    ///
+    /// See [`GroupingBTreeMap`] for more information
+    /// on what operations are available.
+    #[cfg(feature = "use_alloc")]
+    fn into_grouping_btree_map_by<K, V, F>(self, key_mapper: F) -> GroupingBTreeMapBy<Self, F>
+    where
+        Self: Iterator<Item = V> + Sized,
+        K: Ord,
+        F: FnMut(&V) -> K,
+    {
+        grouping_btree_map::new(grouping_map::new_map_for_grouping(self, key_mapper))
+    }
+
+    /// Constructs a `GroupingVec` to be used later with one of the efficient
+    /// group-and-fold operations it allows to perform, when the keys are small integers.
+    ///
+    /// The values from this iterator will be used as values for the folding operation
+    /// while the keys will be obtained from the values by calling `key_mapper`.
+    /// The keys must be less than `num_keys`: the results are stored in a `Vec`
+    /// of length `num_keys` indexed by key, without any hashing.
+    ///
+    /// See [`GroupingVec`] for more information
+    /// on what operations are available.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// let words = ["apple", "bob", "cat", "dinosaur", "eel"];
+    /// let lookup = words.iter().into_grouping_vec(10, |w| w.len()).max();
+    /// assert_eq!(
+    ///     lookup,
+    ///     Ok(vec![None, None, None, Some(&"eel"), None, Some(&"apple"), None, None, Some(&"dinosaur"), None]),
+    /// );
+    ///
+    /// // "dinosaur" has a key out of range.
+    /// let lookup = words.iter().into_grouping_vec(6, |w| w.len()).max();
+    /// assert_eq!(lookup.unwrap_err().key, 8);
+    /// ```
+    #[cfg(feature = "use_alloc")]
+    fn into_grouping_vec<V, F>(self, num_keys: usize, key_mapper: F) -> GroupingVecBy<Self, F>
+    where
+        Self: Iterator<Item = V> + Sized,
+        F: FnMut(&V) -> usize,
+    {
+        grouping_vec::new(
+            grouping_map::new_map_for_grouping(self, key_mapper),
+            num_keys,
+        )
+    }
+
     /// Return all minimum elements of an iterator.
     ///
     /// # Examples
@@ -5072,6 +6070,78 @@ pub trait Itertools: Iterator {
         self.map(f).counts_with_hasher(hash_builder)
     }
 
+    /// Collect the items in this iterator and return a `BTreeMap` which
+    /// contains each item that appears in the iterator and the number
+    /// of times it appears.
+    ///
+    /// This is the ordered counterpart of [`.counts()`](Itertools::counts).
+    ///
+    /// # Examples
+    /// ```
+    /// # use itertools::Itertools;
+    /// let counts = [5, 1, 3, 1, 3, 1].iter().counts_ordered();
+    /// itertools::assert_equal(counts, vec![(&1, 3), (&3, 2), (&5, 1)]);
+    /// ```
+    #[cfg(feature = "use_alloc")]
+    fn counts_ordered(self) -> BTreeMap<Self::Item, usize>
+    where
+        Self: Sized,
+        Self::Item: Ord,
+    {
+        let mut counts = BTreeMap::new();
+        self.for_each(|item| *counts.entry(item).or_default() += 1);
+        counts
+    }
+
+    /// Collect the items in this iterator and return a `BTreeMap` which
+    /// contains each item that appears in the iterator and the number
+    /// of times it appears,
+    /// determining identity using a keying function.
+    ///
+    /// This is the ordered counterpart of [`.counts_by()`](Itertools::counts_by).
+    ///
+    /// ```
+    /// # use itertools::Itertools;
+    /// let lengths = ["ab", "c", "de", "fgh", "i"].iter().counts_ordered_by(|s| s.len());
+    /// itertools::assert_equal(lengths, vec![(1, 2), (2, 2), (3, 1)]);
+    /// ```
+    #[cfg(feature = "use_alloc")]
+    fn counts_ordered_by<K, F>(self, f: F) -> BTreeMap<K, usize>
+    where
+        Self: Sized,
+        K: Ord,
+        F: FnMut(Self::Item) -> K,
+    {
+        self.map(f).counts_ordered()
+    }
+
+    /// Count the keys yielded by this iterator into a `Vec` of length `num_keys`
+    /// holding the number of times each key in `0..num_keys` appears.
+    ///
+    /// This is a faster alternative to [`.counts()`](Itertools::counts) for small integer keys.
+    ///
+    /// Return a [`KeyOutOfRangeError`] as soon as a key is not less than `num_keys`.
+    ///
+    /// ```
+    /// # use itertools::Itertools;
+    /// let counts = [1, 1, 1, 3, 3, 5].iter().map(|&n| n as usize).counts_dense(6);
+    /// assert_eq!(counts, Ok(vec![0, 3, 0, 2, 0, 1]));
+    ///
+    /// let err = [1, 1, 1, 3, 3, 5].iter().map(|&n| n as usize).counts_dense(4).unwrap_err();
+    /// assert_eq!(err.to_string(), "group key 5 is out of range for 4 keys");
+    /// ```
+    #[cfg(feature = "use_alloc")]
+    fn counts_dense(self, num_keys: usize) -> Result<Vec<usize>, KeyOutOfRangeError>
+    where
+        Self: Iterator<Item = usize> + Sized,
+    {
+        let mut counts = alloc::vec![0; num_keys];
+        for key in self {
+            *grouping_vec::bucket(&mut counts, key)? += 1;
+        }
+        Ok(counts)
+    }
+
     /// Converts an iterator of tuples into a tuple of containers.
     ///
     /// It consumes an entire iterator of n-ary tuples, producing `n` collections, one for each
@@ -0,0 +1,193 @@
+use alloc::vec::Vec;
+use std::fmt;
+use std::iter::FusedIterator;
+
+use super::lazy_buffer::LazyBuffer;
+use crate::count::falling_factorial;
+use crate::size_hint::SizeHint;
+
+/// An iterator over the adjacent transpositions that walk through all the permutations
+/// of `n` elements, in Steinhaus–Johnson–Trotter order ("plain changes").
+///
+/// See [`permutation_swaps`] for more information.
+#[derive(Clone, Debug)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct PermutationSwaps {
+    /// Inversion counts: `counts[j]` is the number of values smaller than `j` on its right.
+    counts: Vec<usize>,
+    /// Directions: whether `counts[j]` is increasing.
+    forward: Vec<bool>,
+    /// The count of remaining swaps, or `None` if it would overflow.
+    remaining: Option<usize>,
+}
+
+/// Return an iterator over the adjacent transpositions `(i, i + 1)` that successively
+/// walk through all the `n!` permutations of `n` elements, starting from the identity.
+///
+/// This is the Steinhaus–Johnson–Trotter order, also known as "plain changes":
+/// each permutation differs from the previous one by a single swap of adjacent
+/// elements, and `n! - 1` swaps are generated.
+///
+/// The iterator only uses `O(n)` memory and performs `O(1)` amortized work per swap,
+/// so callers can permute their own slice in place without cloning elements.
+///
+/// ```
+/// use itertools::permutation_swaps;
+///
+/// let mut v = ['a', 'b', 'c'];
+/// let mut perms = vec![v];
+/// for (i, j) in permutation_swaps(v.len()) {
+///     v.swap(i, j);
+///     perms.push(v);
+/// }
+/// assert_eq!(perms, [
+///     ['a', 'b', 'c'],
+///     ['a', 'c', 'b'],
+///     ['c', 'a', 'b'],
+///     ['c', 'b', 'a'],
+///     ['b', 'c', 'a'],
+///     ['b', 'a', 'c'],
+/// ]);
+/// ```
+pub fn permutation_swaps(n: usize) -> PermutationSwaps {
+    let remaining = falling_factorial(n, n);
+    PermutationSwaps {
+        counts: alloc::vec![0; n],
+        forward: alloc::vec![true; n],
+        remaining: remaining.map(|count| count - 1),
+    }
+}
+
+impl Iterator for PermutationSwaps {
+    type Item = (usize, usize);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // Algorithm P from Knuth's TAOCP 7.2.1.2, with 0-based positions.
+        let Self {
+            counts,
+            forward,
+            remaining,
+        } = self;
+        // Number of larger values that are stuck at their leftmost position.
+        let mut offset = 0;
+        for j in (0..counts.len()).rev() {
+            let count = counts[j];
+            if forward[j] && count < j {
+                counts[j] += 1;
+                if let Some(remaining) = remaining.as_mut() {
+                    *remaining -= 1;
+                }
+                let i = j - count - 1 + offset;
+                return Some((i, i + 1));
+            } else if !forward[j] && count > 0 {
+                counts[j] -= 1;
+                if let Some(remaining) = remaining.as_mut() {
+                    *remaining -= 1;
+                }
+                let i = j - count + offset;
+                return Some((i, i + 1));
+            }
+            // The value `j` can't move further, it now goes the other way.
+            if forward[j] {
+                offset += 1;
+            }
+            forward[j] = !forward[j];
+        }
+        // Every value is stuck: we are done, forget the state to stay exhausted.
+        counts.clear();
+        forward.clear();
+        *remaining = Some(0);
+        None
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        (self.remaining.unwrap_or(usize::MAX), self.remaining)
+    }
+}
+
+impl FusedIterator for PermutationSwaps {}
+
+/// An iterator adaptor that iterates through all the permutations of the elements
+/// from an iterator, with a single transposition of adjacent elements between
+/// consecutive permutations.
+///
+/// See [`.permutations_minimal_change()`](crate::Itertools::permutations_minimal_change)
+/// for more information.
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+pub struct PermutationsMinimalChange<I: Iterator> {
+    vals: LazyBuffer<I>,
+    /// The current permutation and the swaps leading to the next ones, once loaded.
+    state: Option<(Vec<usize>, PermutationSwaps)>,
+}
+
+impl<I> Clone for PermutationsMinimalChange<I>
+where
+    I: Clone + Iterator,
+    I::Item: Clone,
+{
+    clone_fields!(vals, state);
+}
+
+impl<I> fmt::Debug for PermutationsMinimalChange<I>
+where
+    I: Iterator + fmt::Debug,
+    I::Item: fmt::Debug,
+{
+    debug_fmt_fields!(PermutationsMinimalChange, vals, state);
+}
+
+pub fn permutations_minimal_change<I: Iterator>(iter: I) -> PermutationsMinimalChange<I> {
+    PermutationsMinimalChange {
+        vals: LazyBuffer::new(iter),
+        state: None,
+    }
+}
+
+impl<I> Iterator for PermutationsMinimalChange<I>
+where
+    I: Iterator,
+    I::Item: Clone,
+{
+    type Item = (Vec<I::Item>, Option<(usize, usize)>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let Self { vals, state } = self;
+        match state {
+            None => {
+                vals.fill();
+                let n = vals.len();
+                let indices = state
+                    .insert(((0..n).collect(), permutation_swaps(n)))
+                    .0
+                    .as_slice();
+                Some((vals.get_at(indices), None))
+            }
+            Some((indices, swaps)) => {
+                let (i, j) = swaps.next()?;
+                indices.swap(i, j);
+                Some((vals.get_at(indices), Some((i, j))))
+            }
+        }
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        match &self.state {
+            None => {
+                let factorial = |n| falling_factorial(n, n);
+                let (low, upp) = self.vals.size_hint();
+                (
+                    factorial(low).unwrap_or(usize::MAX),
+                    upp.and_then(factorial),
+                )
+            }
+            Some((_, swaps)) => swaps.size_hint(),
+        }
+    }
+}
+
+impl<I> FusedIterator for PermutationsMinimalChange<I>
+where
+    I: Iterator,
+    I::Item: Clone,
+{
+}
@@ -0,0 +1,120 @@
+use alloc::vec::Vec;
+use std::cmp::Ordering;
+use std::fmt;
+#[cfg(feature = "use_std")]
+use std::hash::Hash;
+#[cfg(feature = "use_std")]
+use std::mem;
+
+#[cfg(feature = "use_std")]
+use crate::HashMap;
+
+/// A way of comparing elements, to sort them and tell equal ones apart, for use with
+/// [`DistinctPermutationsBy`](crate::structs::DistinctPermutationsBy) and
+/// [`MultisetCombinationsBy`](crate::structs::MultisetCombinationsBy).
+///
+/// It is implemented by `FnMut(&T, &T) -> Ordering` closures, [`MultisetByOrd`] and
+/// [`MultisetByKey`].
+pub trait MultisetCmp<T> {
+    /// Compares `a` and `b`, two elements of the multiset.
+    fn cmp_items(&mut self, a: &T, b: &T) -> Ordering;
+}
+
+/// Compares the elements themselves.
+#[derive(Clone, Copy, Debug)]
+pub struct MultisetByOrd;
+
+impl<T: Ord> MultisetCmp<T> for MultisetByOrd {
+    fn cmp_items(&mut self, a: &T, b: &T) -> Ordering {
+        a.cmp(b)
+    }
+}
+
+/// Compares the keys given by a function of the elements.
+#[derive(Clone)]
+pub struct MultisetByKey<F>(pub(crate) F);
+
+impl<F> fmt::Debug for MultisetByKey<F> {
+    debug_fmt_fields!(MultisetByKey,);
+}
+
+impl<T, K, F> MultisetCmp<T> for MultisetByKey<F>
+where
+    K: Ord,
+    F: FnMut(&T) -> K,
+{
+    fn cmp_items(&mut self, a: &T, b: &T) -> Ordering {
+        (self.0)(a).cmp(&(self.0)(b))
+    }
+}
+
+impl<T, F: FnMut(&T, &T) -> Ordering> MultisetCmp<T> for F {
+    fn cmp_items(&mut self, a: &T, b: &T) -> Ordering {
+        self(a, b)
+    }
+}
+
+/// A way of gathering equal elements, for use with
+/// [`MultisetCombinationsBy`](crate::structs::MultisetCombinationsBy).
+///
+/// It is implemented by [`MultisetByOrd`] and [`MultisetByKey`], which sort the elements,
+/// and by [`MultisetByHash`].
+pub trait MultisetGrouping<T> {
+    /// Reorders `items` so that equal elements are contiguous, keeping the order of
+    /// the source among equal elements.
+    fn group(&mut self, items: &mut Vec<T>);
+    /// Returns true if `a` and `b` are equal elements.
+    fn eq_items(&mut self, a: &T, b: &T) -> bool;
+}
+
+macro_rules! impl_grouping_by_sort {
+    () => {
+        fn group(&mut self, items: &mut Vec<T>) {
+            // The sort is stable.
+            items.sort_by(|a, b| self.cmp_items(a, b));
+        }
+
+        fn eq_items(&mut self, a: &T, b: &T) -> bool {
+            self.cmp_items(a, b).is_eq()
+        }
+    };
+}
+
+impl<T: Ord> MultisetGrouping<T> for MultisetByOrd {
+    impl_grouping_by_sort!();
+}
+
+impl<T, K, F> MultisetGrouping<T> for MultisetByKey<F>
+where
+    K: Ord,
+    F: FnMut(&T) -> K,
+{
+    impl_grouping_by_sort!();
+}
+
+/// Gathers equal elements by hashing, in the order of their first appearance.
+#[cfg(feature = "use_std")]
+#[derive(Clone, Copy, Debug)]
+pub struct MultisetByHash;
+
+#[cfg(feature = "use_std")]
+impl<T: Hash + Eq> MultisetGrouping<T> for MultisetByHash {
+    fn group(&mut self, items: &mut Vec<T>) {
+        let mut firsts = HashMap::new();
+        let runs: Vec<usize> = items
+            .iter()
+            .map(|item| {
+                let next_run = firsts.len();
+                *firsts.entry(item).or_insert(next_run)
+            })
+            .collect();
+        drop(firsts);
+        let mut tagged: Vec<_> = runs.into_iter().zip(mem::take(items)).collect();
+        tagged.sort_by_key(|&(run, _)| run);
+        items.extend(tagged.into_iter().map(|(_, item)| item));
+    }
+
+    fn eq_items(&mut self, a: &T, b: &T) -> bool {
+        a == b
+    }
+}
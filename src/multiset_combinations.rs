@@ -0,0 +1,255 @@
+use alloc::vec::Vec;
+use std::fmt;
+#[cfg(feature = "use_std")]
+use std::hash::Hash;
+use std::iter::FusedIterator;
+
+use super::combinations::PoolIndex;
+use super::lazy_buffer::LazyBuffer;
+use crate::count::binomial;
+#[cfg(feature = "use_std")]
+use crate::multiset_cmp::MultisetByHash;
+use crate::multiset_cmp::{MultisetByKey, MultisetByOrd, MultisetGrouping};
+
+/// An iterator to iterate through all the distinct `k`-length combinations of the
+/// elements from an iterator, where equal elements are not distinguished.
+///
+/// See [`.multiset_combinations()`](crate::Itertools::multiset_combinations),
+/// [`.multiset_combinations_by_key()`](crate::Itertools::multiset_combinations_by_key) and
+/// [`.multiset_combinations_hashed()`](crate::Itertools::multiset_combinations_hashed)
+/// for more information.
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+pub struct MultisetCombinationsBy<I: Iterator, C> {
+    indices: Vec<usize>,
+    pool: LazyBuffer<I>,
+    /// For each element of the sorted pool, the end of its run of equal elements.
+    /// Empty until the pool is loaded.
+    group_ends: Vec<usize>,
+    first: bool,
+    /// The count of combinations after the current one, or `None` if it would overflow.
+    remaining: Option<usize>,
+    cmp: C,
+}
+
+/// An iterator to iterate through all the distinct `k`-length combinations of the
+/// elements from an iterator, where equal elements are not distinguished.
+///
+/// See [`.multiset_combinations()`](crate::Itertools::multiset_combinations) for more information.
+pub type MultisetCombinations<I> = MultisetCombinationsBy<I, MultisetByOrd>;
+
+/// An iterator to iterate through all the distinct `k`-length combinations of the
+/// elements from an iterator, where elements with equal keys are not distinguished.
+///
+/// See [`.multiset_combinations_by_key()`](crate::Itertools::multiset_combinations_by_key)
+/// for more information.
+pub type MultisetCombinationsByKey<I, F> = MultisetCombinationsBy<I, MultisetByKey<F>>;
+
+/// An iterator to iterate through all the distinct `k`-length combinations of the
+/// elements from an iterator, where equal elements are not distinguished.
+///
+/// See [`.multiset_combinations_hashed()`](crate::Itertools::multiset_combinations_hashed)
+/// for more information.
+#[cfg(feature = "use_std")]
+pub type MultisetCombinationsHashed<I> = MultisetCombinationsBy<I, MultisetByHash>;
+
+impl<I, C> Clone for MultisetCombinationsBy<I, C>
+where
+    I: Iterator + Clone,
+    I::Item: Clone,
+    C: Clone,
+{
+    clone_fields!(indices, pool, group_ends, first, remaining, cmp);
+}
+
+impl<I, C> fmt::Debug for MultisetCombinationsBy<I, C>
+where
+    I: Iterator + fmt::Debug,
+    I::Item: fmt::Debug,
+{
+    debug_fmt_fields!(MultisetCombinationsBy, indices, pool, first, remaining);
+}
+
+/// Create a new `MultisetCombinations` from an iterator.
+pub fn multiset_combinations<I>(iter: I, k: usize) -> MultisetCombinations<I>
+where
+    I: Iterator,
+    I::Item: Ord,
+{
+    MultisetCombinationsBy::new(iter, k, MultisetByOrd)
+}
+
+/// Create a new `MultisetCombinationsByKey` from an iterator.
+pub fn multiset_combinations_by_key<I, K, F>(
+    iter: I,
+    k: usize,
+    f: F,
+) -> MultisetCombinationsByKey<I, F>
+where
+    I: Iterator,
+    K: Ord,
+    F: FnMut(&I::Item) -> K,
+{
+    MultisetCombinationsBy::new(iter, k, MultisetByKey(f))
+}
+
+/// Create a new `MultisetCombinationsHashed` from an iterator.
+#[cfg(feature = "use_std")]
+pub fn multiset_combinations_hashed<I>(iter: I, k: usize) -> MultisetCombinationsHashed<I>
+where
+    I: Iterator,
+    I::Item: Hash + Eq,
+{
+    MultisetCombinationsBy::new(iter, k, MultisetByHash)
+}
+
+impl<I, C> MultisetCombinationsBy<I, C>
+where
+    I: Iterator,
+    C: MultisetGrouping<I::Item>,
+{
+    fn new(iter: I, k: usize, cmp: C) -> Self {
+        Self {
+            indices: (0..k).collect(),
+            pool: LazyBuffer::new(iter),
+            group_ends: Vec::new(),
+            first: true,
+            remaining: None,
+            cmp,
+        }
+    }
+
+    /// Returns the length of a combination produced by this iterator.
+    #[inline]
+    pub fn k(&self) -> usize {
+        self.indices.len()
+    }
+
+    /// Loads and groups the whole pool so that equal elements are contiguous, and
+    /// returns the total count of combinations.
+    fn load(&mut self) -> Option<usize> {
+        let Self {
+            pool,
+            group_ends,
+            cmp,
+            ..
+        } = self;
+        pool.fill();
+        // The grouping is stable so the first elements of each run are the first ones of the source.
+        cmp.group(pool.buffer_mut());
+        let n = pool.len();
+        *group_ends = alloc::vec![n; n];
+        for i in (1..n).rev() {
+            if cmp.eq_items(&pool[i - 1], &pool[i]) {
+                group_ends[i - 1] = group_ends[i];
+            } else {
+                group_ends[i - 1] = i;
+            }
+        }
+        // The count is the coefficient of `x^k` in the product over the runs of equal elements
+        // of `1 + x + ... + x^run_length`.
+        let k = self.indices.len();
+        let mut coefs = alloc::vec![Some(0usize); k + 1];
+        coefs[0] = Some(1);
+        let mut start = 0;
+        while start < n {
+            let end = self.group_ends[start];
+            for j in (1..=k).rev() {
+                let coef = coefs[j].and_then(|coef| {
+                    (1..=(end - start).min(j))
+                        .try_fold(coef, |sum, t| sum.checked_add(coefs[j - t]?))
+                });
+                coefs[j] = coef;
+            }
+            start = end;
+        }
+        coefs[k]
+    }
+
+    /// Initialises the iterator by loading the pool. Returns true if there are no
+    /// combinations, false otherwise.
+    fn init(&mut self) -> bool {
+        self.first = false;
+        let count = self.load();
+        self.remaining = count.map(|count| count.saturating_sub(1));
+        self.k() > self.pool.len()
+    }
+
+    /// Increments indices representing the combination to advance to the next
+    /// (in lexicographic order of the sorted elements) distinct combination.
+    /// Within each run of equal elements, the first ones are always picked.
+    ///
+    /// Returns true if we've run out of combinations, false otherwise.
+    fn increment_indices(&mut self) -> bool {
+        let n = self.pool.len();
+        let k = self.indices.len();
+        if k == 0 || k > n {
+            return true; // Done
+        }
+        // Scan from the end, looking for an index that can move to the next run
+        for i in (0..k).rev() {
+            let next = self.group_ends[self.indices[i]];
+            if next + (k - 1 - i) < n {
+                // Pick the next elements for the indices to its right
+                for (j, index) in self.indices.iter_mut().enumerate().skip(i) {
+                    *index = next + j - i;
+                }
+                if let Some(remaining) = self.remaining.as_mut() {
+                    *remaining -= 1;
+                }
+                return false;
+            }
+        }
+        true
+    }
+}
+
+impl<I, C> Iterator for MultisetCombinationsBy<I, C>
+where
+    I: Iterator,
+    I::Item: Clone,
+    C: MultisetGrouping<I::Item>,
+{
+    type Item = Vec<I::Item>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let done = if self.first {
+            self.init()
+        } else {
+            self.increment_indices()
+        };
+        if done {
+            self.remaining = Some(0);
+            return None;
+        }
+        Some(self.indices.extract_item(&self.pool))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.first {
+            // Between one combination when all elements are equal, and as many as
+            // there are combinations when they are all distinct.
+            let k = self.k();
+            let (low, upp) = self.pool.size_hint();
+            let low = usize::from(low >= k);
+            (low, upp.and_then(|n| binomial(n, k)))
+        } else {
+            (self.remaining.unwrap_or(usize::MAX), self.remaining)
+        }
+    }
+
+    fn count(mut self) -> usize {
+        if self.first {
+            self.load().unwrap()
+        } else {
+            self.remaining.unwrap()
+        }
+    }
+}
+
+impl<I, C> FusedIterator for MultisetCombinationsBy<I, C>
+where
+    I: Iterator,
+    I::Item: Clone,
+    C: MultisetGrouping<I::Item>,
+{
+}
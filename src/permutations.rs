@@ -4,7 +4,9 @@ use std::fmt;
 use std::iter::once;
 use std::iter::FusedIterator;
 
+use super::combinations::split_evenly;
 use super::lazy_buffer::LazyBuffer;
+use crate::count::falling_factorial;
 use crate::size_hint::{self, SizeHint};
 
 /// An iterator adaptor that iterates through all the `k`-permutations of the
@@ -16,6 +18,9 @@ use crate::size_hint::{self, SizeHint};
 pub struct Permutations<I: Iterator> {
     vals: LazyBuffer<I>,
     state: PermutationState,
+    /// The indices of the last permutation generated from the back, if any, followed by
+    /// the unused indices in increasing order.
+    back: Option<Box<[usize]>>,
 }
 
 impl<I> Clone for Permutations<I>
@@ -23,7 +28,7 @@ where
     I: Clone + Iterator,
     I::Item: Clone,
 {
-    clone_fields!(vals, state);
+    clone_fields!(vals, state, back);
 }
 
 #[derive(Clone, Debug)]
@@ -46,13 +51,14 @@ where
     I: Iterator + fmt::Debug,
     I::Item: fmt::Debug,
 {
-    debug_fmt_fields!(Permutations, vals, state);
+    debug_fmt_fields!(Permutations, vals, state, back);
 }
 
 pub fn permutations<I: Iterator>(iter: I, k: usize) -> Permutations<I> {
     Permutations {
         vals: LazyBuffer::new(iter),
         state: PermutationState::Start { k },
+        back: None,
     }
 }
 
@@ -64,7 +70,118 @@ where
     type Item = Vec<I::Item>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        let Self { vals, state } = self;
+        let item = self.next_front()?;
+        if let Some(back) = &self.back {
+            if self.state.reached(back) {
+                self.state = PermutationState::End;
+                return None;
+            }
+        }
+        Some(item)
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        if n > 0 && self.vals.can_fill_within(n) {
+            // Load the source entirely so that `n` is known, then jump directly to the
+            // n-th next permutation by unranking it. Otherwise, stepping only loads the
+            // values it needs.
+            self.vals.fill();
+            let len = self.vals.len();
+            if let Some(target) = self.state.rank_of_next(len, n) {
+                return self.jump_to(target);
+            }
+        }
+        for _ in 0..n {
+            self.next()?;
+        }
+        self.next()
+    }
+
+    fn count(self) -> usize {
+        let Self { vals, state, back } = self;
+        let n = vals.count();
+        let count = state.size_hint_for(n).1.unwrap();
+        match (back, state.k()) {
+            (Some(back), Some(k)) => count.saturating_sub(count_after(&back, k).unwrap() + 1),
+            _ => count,
+        }
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        let (mut low, mut upp) = self.vals.size_hint();
+        low = self.state.size_hint_for(low).0;
+        upp = upp.and_then(|n| self.state.size_hint_for(n).1);
+        match (&self.back, self.state.k()) {
+            (Some(back), Some(k)) => {
+                let generated = count_after(back, k).map_or(usize::MAX, |count| count + 1);
+                size_hint::sub_scalar((low, upp), generated)
+            }
+            _ => (low, upp),
+        }
+    }
+}
+
+impl<I> DoubleEndedIterator for Permutations<I>
+where
+    I: Iterator,
+    I::Item: Clone,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let k = self.state.k()?;
+        // The last permutations can only be known once the source is exhausted.
+        self.vals.fill();
+        let n = self.vals.len();
+        if k > n {
+            self.state = PermutationState::End;
+            return None;
+        }
+        let back = match &mut self.back {
+            Some(back) => {
+                if retreat(back, k) {
+                    self.state = PermutationState::End;
+                    return None;
+                }
+                back
+            }
+            None => {
+                let mut back: Box<[_]> = (0..n).rev().collect();
+                back[k..].reverse();
+                self.back.insert(back)
+            }
+        };
+        if self.state.reached(back) {
+            self.state = PermutationState::End;
+            return None;
+        }
+        Some(self.vals.get_at(&back[0..k]))
+    }
+
+    fn rfold<B, F>(mut self, init: B, mut f: F) -> B
+    where
+        F: FnMut(B, Self::Item) -> B,
+    {
+        let mut acc = match self.next_back() {
+            Some(item) => f(init, item),
+            None => return init,
+        };
+        let Self { vals, state, back } = self;
+        // `next_back` has loaded all values and initialized `back`.
+        let mut back = back.unwrap();
+        let k = state.k().unwrap();
+        while !retreat(&mut back, k) && !state.reached(&back) {
+            acc = f(acc, vals.get_at(&back[0..k]));
+        }
+        acc
+    }
+}
+
+impl<I> Permutations<I>
+where
+    I: Iterator,
+    I::Item: Clone,
+{
+    fn next_front(&mut self) -> Option<Vec<I::Item>> {
+        let Self { vals, state, .. } = self;
         match state {
             PermutationState::Start { k: 0 } => {
                 *state = PermutationState::End;
@@ -72,7 +189,7 @@ where
             }
             &mut PermutationState::Start { k } => {
                 vals.prefill(k);
-                if vals.len() != k {
+                if vals.len() < k {
                     *state = PermutationState::End;
                     return None;
                 }
@@ -80,7 +197,8 @@ where
                 Some(vals[0..k].to_vec())
             }
             PermutationState::Buffered { ref k, min_n } => {
-                if vals.get_next() {
+                // Values may already be loaded by `next_back`.
+                if *min_n < vals.len() || vals.get_next() {
                     let item = (0..*k - 1)
                         .chain(once(*min_n))
                         .map(|i| vals[i].clone())
@@ -115,18 +233,117 @@ where
             PermutationState::End => None,
         }
     }
+}
 
-    fn count(self) -> usize {
-        let Self { vals, state } = self;
-        let n = vals.count();
-        state.size_hint_for(n).1.unwrap()
+impl<I> Permutations<I>
+where
+    I: Iterator,
+    I::Item: Clone,
+{
+    /// Moves to the permutation with the given rank in lexicographic order, knowing that
+    /// all values are loaded, and returns it.
+    fn jump_to(&mut self, mut rank: u128) -> Option<Vec<I::Item>> {
+        let k = self.state.k()?;
+        let n = self.vals.len();
+        if k > n {
+            self.state = PermutationState::End;
+            return None;
+        }
+        // The Lehmer code of the permutation: the position of each index among the
+        // ones not used before it, written in mixed radix `n, n - 1, ...`.
+        let mut digits = alloc::vec![0; k];
+        for i in (0..k).rev() {
+            let radix = (n - i) as u128;
+            digits[i] = (rank % radix) as usize;
+            rank /= radix;
+        }
+        if rank > 0 {
+            // There are not enough permutations.
+            self.state = PermutationState::End;
+            return None;
+        }
+        // The state of `advance` at this permutation: the unused indices follow
+        // the permutation in increasing order.
+        let mut unused: Vec<usize> = (0..n).collect();
+        let mut indices: Vec<usize> = digits.iter().map(|&d| unused.remove(d)).collect();
+        indices.extend(unused);
+        let item = self.vals.get_at(&indices[0..k]);
+        let cycles = digits
+            .iter()
+            .enumerate()
+            .map(|(i, &d)| n - 1 - i - d)
+            .collect();
+        self.state = PermutationState::Loaded {
+            indices: indices.into_boxed_slice(),
+            cycles,
+        };
+        if let Some(back) = &self.back {
+            if self.state.reached(back) {
+                self.state = PermutationState::End;
+                return None;
+            }
+        }
+        Some(item)
     }
+}
 
-    fn size_hint(&self) -> SizeHint {
-        let (mut low, mut upp) = self.vals.size_hint();
-        low = self.state.size_hint_for(low).0;
-        upp = upp.and_then(|n| self.state.size_hint_for(n).1);
-        (low, upp)
+impl<I> Permutations<I>
+where
+    I: Iterator + Clone,
+    I::Item: Clone,
+{
+    /// Splits the remaining permutations into the first `rank` ones and the others.
+    ///
+    /// The two iterators generate disjoint contiguous ranges of permutations which together
+    /// cover the ones this iterator would have generated, so that they can be consumed
+    /// independently (on different threads for example). The first one is shorter than
+    /// `rank` if there are not enough remaining permutations.
+    ///
+    /// The whole source iterator is loaded.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// let (head, tail) = (0..3).permutations(2).split_at(4);
+    /// itertools::assert_equal(head, vec![vec![0, 1], vec![0, 2], vec![1, 0], vec![1, 2]]);
+    /// itertools::assert_equal(tail, vec![vec![2, 0], vec![2, 1]]);
+    /// ```
+    pub fn split_at(mut self, rank: usize) -> (Self, Self) {
+        self.vals.fill();
+        let mut tail = self.clone();
+        if rank > 0 && tail.nth(rank - 1).is_none() {
+            return (self, tail);
+        }
+        // The head stops right before the first permutation of the tail.
+        let mut probe = tail.clone();
+        if probe.next().is_some() {
+            let mut back = probe.state.current().unwrap_or_default();
+            let unused: Vec<_> = (0..self.vals.len()).filter(|i| !back.contains(i)).collect();
+            back.extend(unused);
+            self.back = Some(back.into_boxed_slice());
+        }
+        (self, tail)
+    }
+
+    /// Splits the remaining permutations into `parts` contiguous ranges whose lengths differ
+    /// by at most one, see [`split_at`](Self::split_at).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `parts` is zero or if the count of remaining permutations overflows a `usize`.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// let parts = (0..4).permutations(3).split_into(5);
+    /// let lens: Vec<_> = parts.iter().map(|part| part.clone().count()).collect();
+    /// assert_eq!(lens, vec![5, 5, 5, 5, 4]);
+    /// itertools::assert_equal(parts.into_iter().flatten(), (0..4).permutations(3));
+    /// ```
+    pub fn split_into(mut self, parts: usize) -> Vec<Self> {
+        self.vals.fill();
+        let len = self.size_hint().1.expect("too many permutations");
+        split_evenly(self, len, parts, Self::split_at)
     }
 }
 
@@ -137,7 +354,10 @@ where
 {
 }
 
-fn advance(indices: &mut [usize], cycles: &mut [usize]) -> bool {
+/// Moves `indices` to the next `k`-permutation in lexicographic order, where `k = cycles.len()`.
+///
+/// Returns true if there is no next permutation, false otherwise.
+pub(crate) fn advance(indices: &mut [usize], cycles: &mut [usize]) -> bool {
     let n = indices.len();
     let k = cycles.len();
     // NOTE: if `cycles` are only zeros, then we reached the last permutation.
@@ -155,12 +375,102 @@ fn advance(indices: &mut [usize], cycles: &mut [usize]) -> bool {
     true
 }
 
+/// Moves `indices` back to the previous `k`-permutation in lexicographic order, given that
+/// `indices[k..]` holds the unused indices in increasing order.
+///
+/// Returns true if there is no previous permutation, false otherwise.
+fn retreat(indices: &mut [usize], k: usize) -> bool {
+    // `indices` is the smallest full permutation starting with the current `k`-permutation,
+    // so the previous full permutation starts with the previous `k`-permutation.
+    let n = indices.len();
+    let i = match (1..n).rev().find(|&i| indices[i - 1] > indices[i]) {
+        Some(i) => i - 1,
+        None => return true,
+    };
+    let j = (i + 1..n).rev().find(|&j| indices[j] < indices[i]).unwrap();
+    indices.swap(i, j);
+    // The previous full permutation ends with the largest arrangement of its tail...
+    indices[i + 1..].reverse();
+    // ...but we want the unused indices in increasing order.
+    indices[k..].reverse();
+    false
+}
+
+/// Returns the number of `k`-permutations after the one starting `indices`
+/// (followed by the unused indices), or None if it would overflow.
+fn count_after(indices: &[usize], k: usize) -> Option<usize> {
+    let n = indices.len();
+    (0..k).try_fold(0usize, |acc, i| {
+        // Candidates for position `i` are the indices not used before it.
+        let greater = indices[i + 1..].iter().filter(|&&j| j > indices[i]).count();
+        acc.checked_mul(n - i)?.checked_add(greater)
+    })
+}
+
 impl PermutationState {
+    fn k(&self) -> Option<usize> {
+        match *self {
+            Self::Start { k } | Self::Buffered { k, .. } => Some(k),
+            Self::Loaded { ref cycles, .. } => Some(cycles.len()),
+            Self::End => None,
+        }
+    }
+
+    /// Returns the indices of the last permutation generated from the front, if any.
+    fn current(&self) -> Option<Vec<usize>> {
+        match *self {
+            Self::Start { .. } | Self::End => None,
+            Self::Buffered { k, min_n } => Some((0..k - 1).chain(once(min_n - 1)).collect()),
+            Self::Loaded {
+                ref indices,
+                ref cycles,
+            } => Some(indices[0..cycles.len()].to_vec()),
+        }
+    }
+
+    /// Returns the rank in lexicographic order of the permutation `steps` after the next one,
+    /// given that there are `n` values, or `None` if it does not fit in a `u128` or if
+    /// there is no permutation left.
+    fn rank_of_next(&self, n: usize, steps: usize) -> Option<u128> {
+        let current = match *self {
+            Self::Start { .. } => return Some(steps as u128),
+            Self::Buffered { k, min_n } => (min_n - k) as u128,
+            Self::Loaded {
+                ref indices,
+                ref cycles,
+            } => cycles.iter().enumerate().try_fold(0u128, |acc, (i, &c)| {
+                let digit = (indices.len() - 1 - i - c) as u128;
+                acc.checked_mul((n - i) as u128)?.checked_add(digit)
+            })?,
+            Self::End => return None,
+        };
+        current.checked_add(1)?.checked_add(steps as u128)
+    }
+
+    /// Returns true if the last permutation generated from the front is not before
+    /// the permutation starting `back`.
+    fn reached(&self, back: &[usize]) -> bool {
+        match *self {
+            Self::Start { .. } => false,
+            Self::Buffered { k, min_n } => (0..k - 1)
+                .chain(once(min_n - 1))
+                .ge(back[0..k].iter().copied()),
+            Self::Loaded {
+                ref indices,
+                ref cycles,
+            } => {
+                let k = cycles.len();
+                indices[0..k] >= back[0..k]
+            }
+            Self::End => true,
+        }
+    }
+
     fn size_hint_for(&self, n: usize) -> SizeHint {
         // At the beginning, there are `n!/(n-k)!` items to come.
         let at_start = |n, k| {
             debug_assert!(n >= k);
-            let total = (n - k + 1..=n).try_fold(1usize, |acc, i| acc.checked_mul(i));
+            let total = falling_factorial(n, k);
             (total.unwrap_or(usize::MAX), total)
         };
         match *self {
@@ -1,18 +1,23 @@
 use alloc::vec::Vec;
 use std::fmt;
 use std::iter::FusedIterator;
+use std::ops::{Bound, RangeBounds};
 
-use super::combinations::{combinations, Combinations};
-use crate::adaptors::checked_binomial;
+use super::combinations::{self, combinations, Combinations};
+use crate::count::binomial;
 use crate::size_hint::{self, SizeHint};
 
 /// An iterator to iterate through the powerset of the elements from an iterator.
 ///
-/// See [`.powerset()`](crate::Itertools::powerset) for more
-/// information.
+/// See [`.powerset()`](crate::Itertools::powerset) and
+/// [`.powerset_range()`](crate::Itertools::powerset_range) for more information.
 #[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
 pub struct Powerset<I: Iterator> {
     combs: Combinations<I>,
+    /// The maximal size of the subsets.
+    max: usize,
+    /// The indices of the last subset generated from the back, if any.
+    back: Option<Vec<usize>>,
 }
 
 impl<I> Clone for Powerset<I>
@@ -20,7 +25,7 @@ where
     I: Clone + Iterator,
     I::Item: Clone,
 {
-    clone_fields!(combs);
+    clone_fields!(combs, max, back);
 }
 
 impl<I> fmt::Debug for Powerset<I>
@@ -28,7 +33,7 @@ where
     I: Iterator + fmt::Debug,
     I::Item: fmt::Debug,
 {
-    debug_fmt_fields!(Powerset, combs);
+    debug_fmt_fields!(Powerset, combs, max, back);
 }
 
 /// Create a new `Powerset` from a cloneable iterator.
@@ -39,19 +44,66 @@ where
 {
     Powerset {
         combs: combinations(src, 0),
+        max: usize::MAX,
+        back: None,
+    }
+}
+
+/// Create a new `Powerset` of the subsets whose sizes are in the given range.
+pub fn powerset_range<I, R>(src: I, range: R) -> Powerset<I>
+where
+    I: Iterator,
+    I::Item: Clone,
+    R: RangeBounds<usize>,
+{
+    let min = match range.start_bound() {
+        Bound::Included(&start) => start,
+        Bound::Excluded(&start) => start.saturating_add(1),
+        Bound::Unbounded => 0,
+    };
+    let max = match range.end_bound() {
+        Bound::Included(&end) => Some(end),
+        Bound::Excluded(&end) => end.checked_sub(1),
+        Bound::Unbounded => Some(usize::MAX),
+    };
+    // An empty range is represented by `max < min`.
+    let (min, max) = max.map_or((1, 0), |max| (min, max));
+    Powerset {
+        combs: combinations(src, min),
+        max,
+        back: None,
     }
 }
 
 impl<I: Iterator> Powerset<I> {
     /// Returns true if `k` has been incremented, false otherwise.
     fn increment_k(&mut self) -> bool {
-        if self.combs.k() < self.combs.n() || self.combs.k() == 0 {
+        let k = self.combs.k();
+        if k < self.max && (k < self.combs.n() || k == 0) {
             self.combs.reset(self.combs.k() + 1);
             true
         } else {
             false
         }
     }
+
+    /// Returns the count of subsets generated from the back, or None if it would overflow.
+    fn back_count(&self, n: usize) -> Option<usize> {
+        self.back.as_ref().map_or(Some(0), |back| {
+            remaining_for(n, back.len(), self.max)?
+                .checked_add(combinations::remaining_for(n, false, back)?)?
+                .checked_add(1)
+        })
+    }
+}
+
+/// Returns true if the last subset generated from the front is not before the `back` subset.
+fn reached<I: Iterator>(combs: &Combinations<I>, back: &[usize]) -> bool {
+    match combs.current() {
+        Some(indices) => (back.len(), back) <= (indices.len(), indices),
+        // All the smaller subsets have been generated.
+        None => back.len() < combs.k(),
+    }
 }
 
 impl<I> Iterator for Powerset<I>
@@ -62,16 +114,29 @@ where
     type Item = Vec<I::Item>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if let Some(elt) = self.combs.next() {
-            Some(elt)
+        if self.combs.k() > self.max {
+            return None;
+        }
+        let elt = if let Some(elt) = self.combs.next() {
+            elt
         } else if self.increment_k() {
-            self.combs.next()
+            self.combs.next()?
         } else {
-            None
+            return None;
+        };
+        match &self.back {
+            Some(back) if reached(&self.combs, back) => None,
+            _ => Some(elt),
         }
     }
 
     fn nth(&mut self, mut n: usize) -> Option<Self::Item> {
+        if self.back.is_some() || self.combs.k() > self.max {
+            for _ in 0..n {
+                self.next()?;
+            }
+            return self.next();
+        }
         loop {
             match self.combs.try_nth(n) {
                 Ok(item) => return Some(item),
@@ -87,31 +152,53 @@ where
 
     fn size_hint(&self) -> SizeHint {
         let k = self.combs.k();
+        if k > self.max {
+            return (0, Some(0));
+        }
         // Total bounds for source iterator.
         let (n_min, n_max) = self.combs.src().size_hint();
-        let low = remaining_for(n_min, k).unwrap_or(usize::MAX);
-        let upp = n_max.and_then(|n| remaining_for(n, k));
-        size_hint::add(self.combs.size_hint(), (low, upp))
+        let low = remaining_for(n_min, k, self.max).unwrap_or(usize::MAX);
+        let upp = n_max.and_then(|n| remaining_for(n, k, self.max));
+        let sh = size_hint::add(self.combs.size_hint(), (low, upp));
+        // `n` is known to be exact when subsets are generated from the back.
+        let back_count = self.back_count(n_min).unwrap_or(usize::MAX);
+        size_hint::sub_scalar(sh, back_count)
     }
 
     fn count(self) -> usize {
         let k = self.combs.k();
+        if k > self.max {
+            return 0;
+        }
+        let back_count = self.back_count(self.combs.n());
         let (n, combs_count) = self.combs.n_and_count();
-        combs_count + remaining_for(n, k).unwrap()
+        (combs_count + remaining_for(n, k, self.max).unwrap()).saturating_sub(back_count.unwrap())
     }
 
     fn fold<B, F>(self, mut init: B, mut f: F) -> B
     where
         F: FnMut(B, Self::Item) -> B,
     {
-        let mut it = self.combs;
+        if self.back.is_some() || self.combs.k() > self.max {
+            // Subsets generated from the back must be excluded.
+            for elt in self {
+                init = f(init, elt);
+            }
+            return init;
+        }
+        let Self {
+            combs: mut it, max, ..
+        } = self;
         if it.k() == 0 {
             init = it.by_ref().fold(init, &mut f);
+            if max == 0 {
+                return init;
+            }
             it.reset(1);
         }
         init = it.by_ref().fold(init, &mut f);
         // n is now known for sure because k >= 1 and all k-combinations have been generated.
-        for k in it.k() + 1..=it.n() {
+        for k in it.k() + 1..=it.n().min(max) {
             it.reset(k);
             init = it.by_ref().fold(init, &mut f);
         }
@@ -119,6 +206,63 @@ where
     }
 }
 
+impl<I> DoubleEndedIterator for Powerset<I>
+where
+    I: Iterator,
+    I::Item: Clone,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        // The last subsets can only be known once the source is exhausted.
+        self.combs.fill();
+        let n = self.combs.n();
+        let back = match &mut self.back {
+            Some(back) => {
+                if !retreat(back, n) {
+                    return None;
+                }
+                back
+            }
+            None => self.back.insert((n.saturating_sub(self.max)..n).collect()),
+        };
+        if reached(&self.combs, back) {
+            return None;
+        }
+        Some(self.combs.src().get_at(back))
+    }
+
+    fn rfold<B, F>(mut self, init: B, mut f: F) -> B
+    where
+        F: FnMut(B, Self::Item) -> B,
+    {
+        let mut acc = match self.next_back() {
+            Some(elt) => f(init, elt),
+            None => return init,
+        };
+        let Self { combs, back, .. } = self;
+        let n = combs.n();
+        // `next_back` has loaded the whole pool and initialized `back`.
+        let mut back = back.unwrap();
+        while retreat(&mut back, n) && !reached(&combs, &back) {
+            acc = f(acc, combs.src().get_at(&back));
+        }
+        acc
+    }
+}
+
+/// Moves `back` to the previous subset of `0..n`, returns false if there is none.
+fn retreat(back: &mut Vec<usize>, n: usize) -> bool {
+    if !combinations::decrement_indices(back, n) {
+        return true;
+    }
+    if back.is_empty() {
+        return false;
+    }
+    // Go to the last subset of the previous size.
+    back.pop();
+    combinations::set_last(back, n);
+    true
+}
+
 impl<I> FusedIterator for Powerset<I>
 where
     I: Iterator,
@@ -126,6 +270,7 @@ where
 {
 }
 
-fn remaining_for(n: usize, k: usize) -> Option<usize> {
-    (k + 1..=n).try_fold(0usize, |sum, i| sum.checked_add(checked_binomial(n, i)?))
+/// Returns the count of subsets of `0..n` of sizes in `k + 1..=max`, or None if it would overflow.
+fn remaining_for(n: usize, k: usize, max: usize) -> Option<usize> {
+    (k + 1..=n.min(max)).try_fold(0usize, |sum, i| sum.checked_add(binomial(n, i)?))
 }
@@ -0,0 +1,252 @@
+use std::convert::TryFrom;
+use std::fmt;
+use std::iter::{Fuse, FusedIterator};
+
+use super::lazy_buffer::LazyBuffer;
+use crate::size_hint::SizeHint;
+
+/// The value yielded by [`PowersetGray`]: how a subset differs from the previous one.
+///
+/// See [`.powerset_gray()`](crate::Itertools::powerset_gray) for more information.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum SubsetDelta<T> {
+    /// The element is added to the previous subset.
+    Added(T),
+    /// The element is removed from the previous subset.
+    Removed(T),
+}
+
+impl<T> SubsetDelta<T> {
+    /// Returns true if the element is added to the previous subset.
+    pub fn is_added(&self) -> bool {
+        matches!(self, Self::Added(_))
+    }
+
+    /// Returns true if the element is removed from the previous subset.
+    pub fn is_removed(&self) -> bool {
+        matches!(self, Self::Removed(_))
+    }
+
+    /// Returns the added or removed element.
+    pub fn into_inner(self) -> T {
+        match self {
+            Self::Added(elt) | Self::Removed(elt) => elt,
+        }
+    }
+}
+
+/// An iterator over the changes between consecutive subsets of the powerset of the
+/// elements from an iterator, in binary-reflected Gray code order.
+///
+/// See [`.powerset_gray()`](crate::Itertools::powerset_gray) for more information.
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+pub struct PowersetGray<I: Iterator> {
+    pool: LazyBuffer<I>,
+    /// The count of deltas generated so far.
+    steps: u64,
+    done: bool,
+}
+
+impl<I> Clone for PowersetGray<I>
+where
+    I: Clone + Iterator,
+    I::Item: Clone,
+{
+    clone_fields!(pool, steps, done);
+}
+
+impl<I> fmt::Debug for PowersetGray<I>
+where
+    I: Iterator + fmt::Debug,
+    I::Item: fmt::Debug,
+{
+    debug_fmt_fields!(PowersetGray, pool, steps, done);
+}
+
+/// Create a new `PowersetGray` from an iterator.
+pub fn powerset_gray<I: Iterator>(iter: I) -> PowersetGray<I> {
+    PowersetGray {
+        pool: LazyBuffer::new(iter),
+        steps: 0,
+        done: false,
+    }
+}
+
+impl<I> Iterator for PowersetGray<I>
+where
+    I: Iterator,
+    I::Item: Clone,
+{
+    type Item = SubsetDelta<I::Item>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        let step = self.steps + 1;
+        // The `j`-th bit of the Gray code flips at steps that are odd multiples of `2^j`,
+        // so the `j`-th element is only needed at step `2^j`.
+        let j = step.trailing_zeros() as usize;
+        if j == self.pool.len() && !self.pool.get_next() {
+            self.done = true;
+            return None;
+        }
+        self.steps = step;
+        let elt = self.pool[j].clone();
+        let gray = step ^ (step >> 1u32);
+        if gray >> j & 1 == 1 {
+            Some(SubsetDelta::Added(elt))
+        } else {
+            Some(SubsetDelta::Removed(elt))
+        }
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        if self.done {
+            return (0, Some(0));
+        }
+        let remaining = |n: usize| {
+            let total = u32::try_from(n).ok().and_then(|n| 1u128.checked_shl(n))? - 1;
+            usize::try_from(total - u128::from(self.steps)).ok()
+        };
+        let (low, upp) = self.pool.size_hint();
+        (
+            remaining(low).unwrap_or(usize::MAX),
+            upp.and_then(remaining),
+        )
+    }
+}
+
+impl<I> FusedIterator for PowersetGray<I>
+where
+    I: Iterator,
+    I::Item: Clone,
+{
+}
+
+mod private_bit_mask {
+    pub trait Sealed: Copy + Eq {
+        const BITS: u32;
+        const ZERO: Self;
+        fn increment(self) -> Option<Self>;
+        fn to_u128(self) -> u128;
+    }
+
+    macro_rules! impl_sealed {
+        ($($t:ty),*) => {
+            $(
+                impl Sealed for $t {
+                    const BITS: u32 = <$t>::BITS;
+                    const ZERO: Self = 0;
+                    fn increment(self) -> Option<Self> {
+                        self.checked_add(1)
+                    }
+                    fn to_u128(self) -> u128 {
+                        self as u128
+                    }
+                }
+            )*
+        };
+    }
+
+    impl_sealed!(u8, u16, u32, u64, u128, usize);
+}
+
+/// An unsigned integer type used by [`Itertools::powerset_masks`](crate::Itertools::powerset_masks)
+/// to represent subsets of up to `BITS` elements.
+pub trait BitMask: private_bit_mask::Sealed {}
+
+impl<T: private_bit_mask::Sealed> BitMask for T {}
+
+/// An iterator over the bitmasks of all the subsets of the elements from an iterator.
+///
+/// See [`.powerset_masks()`](crate::Itertools::powerset_masks) for more information.
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+pub struct PowersetMasks<I, M> {
+    iter: Fuse<I>,
+    /// The count of elements seen so far.
+    len: u32,
+    next: Option<M>,
+}
+
+impl<I, M> Clone for PowersetMasks<I, M>
+where
+    I: Clone,
+    M: Clone,
+{
+    clone_fields!(iter, len, next);
+}
+
+impl<I, M> fmt::Debug for PowersetMasks<I, M>
+where
+    I: fmt::Debug,
+    M: fmt::Debug,
+{
+    debug_fmt_fields!(PowersetMasks, iter, len, next);
+}
+
+/// Create a new `PowersetMasks` from an iterator.
+pub fn powerset_masks<I: Iterator, M: BitMask>(iter: I) -> PowersetMasks<I, M> {
+    PowersetMasks {
+        iter: iter.fuse(),
+        len: 0,
+        next: Some(M::ZERO),
+    }
+}
+
+impl<I, M> Iterator for PowersetMasks<I, M>
+where
+    I: Iterator,
+    M: BitMask,
+{
+    type Item = M;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mask = self.next?;
+        // The mask `2^len` is the first one needing one more element.
+        if 1u128.checked_shl(self.len) == Some(mask.to_u128()) {
+            if self.iter.next().is_none() {
+                self.next = None;
+                return None;
+            }
+            self.len += 1;
+        }
+        self.next = mask.increment();
+        if self.next.is_none() {
+            assert!(
+                self.iter.next().is_none(),
+                "powerset_masks: the iterator has more than {} elements",
+                M::BITS,
+            );
+        }
+        Some(mask)
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        let next = match self.next {
+            Some(next) => next.to_u128(),
+            None => return (0, Some(0)),
+        };
+        let remaining = |n: usize| {
+            let n = u32::try_from(n).unwrap_or(u32::MAX).min(M::BITS);
+            let last = 1u128.checked_shl(n).map_or(u128::MAX, |total| total - 1);
+            if next > last {
+                return Some(0);
+            }
+            usize::try_from((last - next).checked_add(1)?).ok()
+        };
+        let len = self.len as usize;
+        let (low, upp) = self.iter.size_hint();
+        (
+            remaining(len.saturating_add(low)).unwrap_or(usize::MAX),
+            upp.and_then(|upp| remaining(len.checked_add(upp)?)),
+        )
+    }
+}
+
+impl<I, M> FusedIterator for PowersetMasks<I, M>
+where
+    I: Iterator,
+    M: BitMask,
+{
+}
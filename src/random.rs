@@ -0,0 +1,214 @@
+//! Random selection and shuffling of the elements of an iterator.
+//!
+//! The functions take any random number generator implementing [`RngCore`] from
+//! the `rand_core` crate (like the ones of the `rand` crate), and the result only
+//! depends on the elements and the state of the generator, so a seeded generator
+//! gives reproducible results.
+//!
+//! ```
+//! use itertools::random;
+//! use rand_pcg::{rand_core::SeedableRng, Pcg64};
+//!
+//! let mut rng = Pcg64::seed_from_u64(42);
+//! let sample = random::sample(0..100, 5, &mut rng);
+//! assert_eq!(sample.len(), 5);
+//!
+//! let mut rng = Pcg64::seed_from_u64(42);
+//! assert_eq!(random::sample(0..100, 5, &mut rng), sample);
+//! ```
+
+use alloc::vec::Vec;
+use rand_core::RngCore;
+
+/// Returns an index uniformly distributed in `0..bound`, with Lemire's method.
+fn gen_index<R>(rng: &mut R, bound: usize) -> usize
+where
+    R: RngCore + ?Sized,
+{
+    debug_assert!(bound > 0);
+    let bound = bound as u64;
+    // Reject the values that would make some results more likely than others.
+    let threshold = bound.wrapping_neg() % bound;
+    loop {
+        let m = u128::from(rng.next_u64()) * u128::from(bound);
+        if m as u64 >= threshold {
+            return (m >> 64) as usize;
+        }
+    }
+}
+
+/// Returns a float uniformly distributed in `(0, 1]`.
+#[cfg(feature = "use_std")]
+fn gen_unit<R>(rng: &mut R) -> f64
+where
+    R: RngCore + ?Sized,
+{
+    // The 53 most significant bits fill the mantissa.
+    1.0_f64 - (rng.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+}
+
+/// Selects `k` elements uniformly at random with reservoir sampling ("Algorithm R"), paired with
+/// their positions in the iterator. Fewer elements are selected if the iterator is shorter.
+fn reservoir<I, R>(iter: I, k: usize, rng: &mut R) -> Vec<(usize, I::Item)>
+where
+    I: Iterator,
+    R: RngCore + ?Sized,
+{
+    let mut iter = iter.enumerate();
+    let mut reservoir: Vec<_> = iter.by_ref().take(k).collect();
+    if reservoir.len() == k && k > 0 {
+        for (i, elt) in iter {
+            // The `i`-th element replaces a selected one with probability `k / (i + 1)`.
+            let j = gen_index(rng, i + 1);
+            if j < k {
+                reservoir[j] = (i, elt);
+            }
+        }
+    }
+    reservoir
+}
+
+/// Consumes `iterable` and returns `k` of its elements, selected uniformly at random
+/// with reservoir sampling.
+///
+/// All the elements are returned if there are fewer than `k` of them. The order of the
+/// selected elements is unspecified, see [`random_combination`] to keep the order of
+/// the iterator.
+///
+/// ```
+/// use itertools::{random, Itertools};
+/// use rand_pcg::{rand_core::SeedableRng, Pcg64};
+///
+/// let mut rng = Pcg64::seed_from_u64(42);
+/// let sample = random::sample(0..100, 5, &mut rng).into_iter().sorted().collect_vec();
+/// assert_eq!(sample.len(), 5);
+/// assert!(sample.windows(2).all(|w| w[0] < w[1] && w[1] < 100));
+/// ```
+pub fn sample<I, R>(iterable: I, k: usize, rng: &mut R) -> Vec<I::Item>
+where
+    I: IntoIterator,
+    R: RngCore + ?Sized,
+{
+    reservoir(iterable.into_iter(), k, rng)
+        .into_iter()
+        .map(|(_, elt)| elt)
+        .collect()
+}
+
+/// Consumes `iterable` and returns `k` of its elements, selected at random without
+/// replacement with probabilities proportional to their weights.
+///
+/// Elements whose weight is not positive (including NaN) are never selected, so fewer
+/// than `k` elements are returned if there are not enough elements with a positive weight.
+/// The selected elements are returned in an unspecified order.
+///
+/// This function is only available with the `use_std` feature, which provides the
+/// floating point logarithm it relies on.
+///
+/// ```
+/// use itertools::random;
+/// use rand_pcg::{rand_core::SeedableRng, Pcg64};
+///
+/// let mut rng = Pcg64::seed_from_u64(42);
+/// let sample = random::sample_weighted(0..10, 3, |&x| if x % 2 == 0 { 1.0 } else { 0.0 }, &mut rng);
+/// assert_eq!(sample.len(), 3);
+/// assert!(sample.iter().all(|x| x % 2 == 0));
+/// ```
+#[cfg(feature = "use_std")]
+pub fn sample_weighted<I, F, R>(iterable: I, k: usize, mut weight: F, rng: &mut R) -> Vec<I::Item>
+where
+    I: IntoIterator,
+    F: FnMut(&I::Item) -> f64,
+    R: RngCore + ?Sized,
+{
+    // "Algorithm A-Res": each element gets the key `u^(1/w)` with `u` uniform in `(0, 1]`,
+    // and the elements with the `k` largest keys are selected. Logarithms of the keys are
+    // compared instead, to avoid underflows.
+    let keyed = iterable.into_iter().filter_map(|elt| {
+        let w = weight(&elt);
+        if w > 0.0f64 {
+            Some((gen_unit(rng).ln() / w, elt))
+        } else {
+            None
+        }
+    });
+    crate::k_smallest::k_smallest_general(keyed, k, |a, b| b.0.total_cmp(&a.0))
+        .into_iter()
+        .map(|(_, elt)| elt)
+        .collect()
+}
+
+/// Consumes `iterable` and returns one of its elements selected uniformly at random,
+/// or `None` if it is empty.
+///
+/// ```
+/// use itertools::random;
+/// use rand_pcg::{rand_core::SeedableRng, Pcg64};
+///
+/// let mut rng = Pcg64::seed_from_u64(42);
+/// let chosen = random::choose(0..10, &mut rng).unwrap();
+/// assert!(chosen < 10);
+/// assert_eq!(random::choose(0..0, &mut rng), None);
+/// ```
+pub fn choose<I, R>(iterable: I, rng: &mut R) -> Option<I::Item>
+where
+    I: IntoIterator,
+    R: RngCore + ?Sized,
+{
+    reservoir(iterable.into_iter(), 1, rng)
+        .pop()
+        .map(|(_, elt)| elt)
+}
+
+/// Consumes `iterable` and returns all its elements in a uniformly random order.
+///
+/// ```
+/// use itertools::{random, Itertools};
+/// use rand_pcg::{rand_core::SeedableRng, Pcg64};
+///
+/// let mut rng = Pcg64::seed_from_u64(42);
+/// let shuffled = random::shuffled(0..10, &mut rng);
+/// itertools::assert_equal(shuffled.iter().copied().sorted(), 0..10);
+/// ```
+pub fn shuffled<I, R>(iterable: I, rng: &mut R) -> Vec<I::Item>
+where
+    I: IntoIterator,
+    R: RngCore + ?Sized,
+{
+    let mut v: Vec<_> = iterable.into_iter().collect();
+    // Fisher-Yates shuffle.
+    for i in (1..v.len()).rev() {
+        let j = gen_index(rng, i + 1);
+        v.swap(i, j);
+    }
+    v
+}
+
+/// Consumes `iterable` and returns one of its `k`-length combinations, selected
+/// uniformly at random, or `None` if there are fewer than `k` elements.
+///
+/// The elements of the combination are in the order of the iterator, as with
+/// [`.combinations(k)`](crate::Itertools::combinations).
+///
+/// ```
+/// use itertools::random;
+/// use rand_pcg::{rand_core::SeedableRng, Pcg64};
+///
+/// let mut rng = Pcg64::seed_from_u64(42);
+/// let combination = random::random_combination(0..10, 3, &mut rng).unwrap();
+/// assert!(combination.windows(2).all(|w| w[0] < w[1]));
+/// assert_eq!(random::random_combination(0..2, 3, &mut rng), None);
+/// ```
+pub fn random_combination<I, R>(iterable: I, k: usize, rng: &mut R) -> Option<Vec<I::Item>>
+where
+    I: IntoIterator,
+    R: RngCore + ?Sized,
+{
+    let mut selected = reservoir(iterable.into_iter(), k, rng);
+    if selected.len() < k {
+        return None;
+    }
+    // Combinations keep the order of the iterator.
+    selected.sort_unstable_by_key(|&(i, _)| i);
+    Some(selected.into_iter().map(|(_, elt)| elt).collect())
+}
@@ -0,0 +1,198 @@
+use alloc::vec::Vec;
+use std::fmt;
+use std::iter::FusedIterator;
+
+use super::lazy_buffer::LazyBuffer;
+use crate::size_hint::SizeHint;
+
+/// An iterator to iterate through the partitions of the elements from an iterator
+/// into non-empty blocks.
+///
+/// See [`.set_partitions()`](crate::Itertools::set_partitions) and
+/// [`.set_partitions_k()`](crate::Itertools::set_partitions_k) for more information.
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+pub struct SetPartitions<I: Iterator> {
+    pool: LazyBuffer<I>,
+    /// The exact number of blocks, if any.
+    k: Option<usize>,
+    /// The restricted growth string of the current partition: the block of each element.
+    blocks: Vec<usize>,
+    /// For each element, the greatest block among it and the previous elements.
+    maxes: Vec<usize>,
+    first: bool,
+    /// The count of remaining partitions, or `None` if it would overflow.
+    remaining: Option<usize>,
+}
+
+impl<I> Clone for SetPartitions<I>
+where
+    I: Clone + Iterator,
+    I::Item: Clone,
+{
+    clone_fields!(pool, k, blocks, maxes, first, remaining);
+}
+
+impl<I> fmt::Debug for SetPartitions<I>
+where
+    I: Iterator + fmt::Debug,
+    I::Item: fmt::Debug,
+{
+    debug_fmt_fields!(SetPartitions, pool, k, blocks, first, remaining);
+}
+
+/// Create a new `SetPartitions` from an iterator.
+pub fn set_partitions<I: Iterator>(iter: I, k: Option<usize>) -> SetPartitions<I> {
+    SetPartitions {
+        pool: LazyBuffer::new(iter),
+        k,
+        blocks: Vec::new(),
+        maxes: Vec::new(),
+        first: true,
+        remaining: None,
+    }
+}
+
+impl<I: Iterator> SetPartitions<I> {
+    /// Loads the whole pool and sets the first partition, returns the count of partitions.
+    fn init(&mut self) -> Option<usize> {
+        self.first = false;
+        self.pool.fill();
+        let n = self.pool.len();
+        self.blocks = alloc::vec![0; n];
+        self.maxes = alloc::vec![0; n];
+        fill_smallest(&mut self.blocks, &mut self.maxes, 0, self.k);
+        count(n, self.k)
+    }
+}
+
+impl<I> Iterator for SetPartitions<I>
+where
+    I: Iterator,
+    I::Item: Clone,
+{
+    type Item = Vec<Vec<I::Item>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.first {
+            self.remaining = self.init();
+        } else if self.remaining == Some(0) {
+            return None;
+        } else if advance(&mut self.blocks, &mut self.maxes, self.k) {
+            self.remaining = Some(0);
+            return None;
+        }
+        match self.remaining.as_mut() {
+            Some(0) => return None,
+            Some(remaining) => *remaining -= 1,
+            None => {}
+        }
+        let len = self.maxes.last().map_or(0, |max| max + 1);
+        let mut partition: Vec<Vec<_>> = (0..len).map(|_| Vec::new()).collect();
+        for (elt, &block) in self.pool[..].iter().zip(&self.blocks) {
+            partition[block].push(elt.clone());
+        }
+        Some(partition)
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        if self.first {
+            let (low, upp) = self.pool.size_hint();
+            if self.k == Some(0) {
+                // Only the empty set has a partition into no blocks.
+                return (usize::from(upp == Some(0)), Some(usize::from(low == 0)));
+            }
+            // Otherwise the count of partitions does not decrease with the count of elements.
+            (
+                count(low, self.k).unwrap_or(usize::MAX),
+                upp.and_then(|n| count(n, self.k)),
+            )
+        } else {
+            (self.remaining.unwrap_or(usize::MAX), self.remaining)
+        }
+    }
+
+    fn count(mut self) -> usize {
+        if self.first {
+            self.pool.fill();
+            count(self.pool.len(), self.k).unwrap()
+        } else {
+            self.remaining.unwrap()
+        }
+    }
+}
+
+impl<I> FusedIterator for SetPartitions<I>
+where
+    I: Iterator,
+    I::Item: Clone,
+{
+}
+
+/// Sets `blocks[start..]` to the smallest restricted growth string completing `blocks[..start]`,
+/// with exactly `k` blocks if any.
+fn fill_smallest(blocks: &mut [usize], maxes: &mut [usize], start: usize, k: Option<usize>) {
+    let n = blocks.len();
+    let mut max = if start == 0 { 0 } else { maxes[start - 1] };
+    // The last elements open the missing blocks.
+    let missing = k.map_or(0, |k| k.saturating_sub(max + 1));
+    for i in start..n {
+        if i + missing >= n && i > 0 {
+            max += 1;
+            blocks[i] = max;
+        } else {
+            blocks[i] = 0;
+        }
+        maxes[i] = max;
+    }
+}
+
+/// Moves `blocks` to the next restricted growth string in lexicographic order,
+/// with exactly `k` blocks if any.
+///
+/// Returns true if there is no next restricted growth string, false otherwise.
+fn advance(blocks: &mut [usize], maxes: &mut [usize], k: Option<usize>) -> bool {
+    let n = blocks.len();
+    // Scan from the end, looking for an element that can go to a later block...
+    for i in (1..n).rev() {
+        let prev_max = maxes[i - 1];
+        let limit = k.map_or(prev_max + 1, |k| (prev_max + 1).min(k - 1));
+        let next = (blocks[i] + 1..=limit).find(|&block| {
+            // ...while leaving enough elements to open the missing blocks.
+            let max = prev_max.max(block);
+            k.map_or(true, |k| n - 1 - i >= k - 1 - max)
+        });
+        if let Some(block) = next {
+            blocks[i] = block;
+            maxes[i] = prev_max.max(block);
+            // Reset the ones to its right.
+            fill_smallest(blocks, maxes, i + 1, k);
+            return false;
+        }
+    }
+    true
+}
+
+/// Returns the count of partitions of `n` elements into exactly `k` blocks if any, or into
+/// any number of blocks, or `None` if it would overflow.
+fn count(n: usize, k: Option<usize>) -> Option<usize> {
+    // The row `n` of the Stirling numbers of the second kind, with
+    // `S(n, j) = j * S(n - 1, j) + S(n - 1, j - 1)`.
+    let max_k = k.map_or(n, |k| k.min(n));
+    let mut row = alloc::vec![Some(0usize); max_k + 1];
+    row[0] = Some(1);
+    for _ in 0..n {
+        for j in (1..=max_k).rev() {
+            row[j] = row[j]
+                .and_then(|count| count.checked_mul(j))
+                .and_then(|count| count.checked_add(row[j - 1]?));
+        }
+        row[0] = Some(0);
+    }
+    match k {
+        Some(k) if k > n => Some(0),
+        Some(k) => row[k],
+        None => row
+            .into_iter()
+            .try_fold(0usize, |sum, count| sum.checked_add(count?)),
+    }
+}
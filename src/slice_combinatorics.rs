@@ -0,0 +1,240 @@
+use alloc::vec::Vec;
+use std::fmt;
+use std::slice;
+
+use crate::combinations::{array_combinations, ArrayCombinations};
+use crate::index_combinatorics::{combination_indices, CombinationIndices};
+use crate::permutations;
+
+/// Combinatoric adaptors over slices that borrow the elements instead of cloning them.
+///
+/// Since the length of the slice is known up front, only the indices of the current
+/// item are kept. They are advanced with the same logic as
+/// [`.combinations()`](crate::Itertools::combinations) and
+/// [`.permutations()`](crate::Itertools::permutations), without buffering the elements.
+///
+/// Except for [`array_combinations_ref`](Self::array_combinations_ref), the returned
+/// types are not iterators: their `next_ref` method lends the references to the
+/// elements of the current item from a buffer that is reused by the following calls,
+/// so that nothing is allocated per item.
+///
+/// ```
+/// use itertools::SliceCombinatorics;
+///
+/// #[derive(Debug, PartialEq)]
+/// struct Heavy(u32); // Not `Clone`.
+///
+/// let items = [Heavy(1), Heavy(2), Heavy(3)];
+/// let pairs: Vec<[&Heavy; 2]> = items.array_combinations_ref().collect();
+/// assert_eq!(pairs, [
+///     [&items[0], &items[1]],
+///     [&items[0], &items[2]],
+///     [&items[1], &items[2]],
+/// ]);
+///
+/// let mut sums = Vec::new();
+/// let mut triples = items.combinations_ref(3);
+/// while let Some(triple) = triples.next_ref() {
+///     sums.push(triple.iter().map(|heavy| heavy.0).sum::<u32>());
+/// }
+/// assert_eq!(sums, [6]);
+/// ```
+pub trait SliceCombinatorics<T> {
+    /// Return the `k`-length combinations of references to the elements of the slice,
+    /// in the same order as [`.combinations()`](crate::Itertools::combinations).
+    ///
+    /// ```
+    /// use itertools::SliceCombinatorics;
+    ///
+    /// let names = [String::from("a"), String::from("b"), String::from("c")];
+    /// let mut it = names.combinations_ref(2);
+    /// assert_eq!(it.next_ref(), Some(&[&names[0], &names[1]][..]));
+    /// assert_eq!(it.next_ref(), Some(&[&names[0], &names[2]][..]));
+    /// assert_eq!(it.next_ref(), Some(&[&names[1], &names[2]][..]));
+    /// assert_eq!(it.next_ref(), None);
+    /// ```
+    fn combinations_ref(&self, k: usize) -> SliceCombinations<'_, T>;
+
+    /// Return an iterator adaptor that iterates over the combinations of `K` references
+    /// to the elements of the slice, without allocating per combination.
+    ///
+    /// Iterator element type is `[&T; K]`.
+    ///
+    /// See [`.array_combinations()`](crate::Itertools::array_combinations) for more information.
+    fn array_combinations_ref<const K: usize>(&self) -> ArrayCombinations<slice::Iter<'_, T>, K>;
+
+    /// Return the `k`-length permutations of references to the elements of the slice,
+    /// in the same order as [`.permutations()`](crate::Itertools::permutations).
+    ///
+    /// ```
+    /// use itertools::SliceCombinatorics;
+    ///
+    /// let v = [vec![1], vec![2]];
+    /// let mut it = v.permutations_ref(2);
+    /// assert_eq!(it.next_ref(), Some(&[&v[0], &v[1]][..]));
+    /// assert_eq!(it.next_ref(), Some(&[&v[1], &v[0]][..]));
+    /// assert_eq!(it.next_ref(), None);
+    /// ```
+    fn permutations_ref(&self, k: usize) -> SlicePermutations<'_, T>;
+
+    /// Return the powerset of references to the elements of the slice,
+    /// in the same order as [`.powerset()`](crate::Itertools::powerset).
+    ///
+    /// ```
+    /// use itertools::SliceCombinatorics;
+    ///
+    /// let v = [vec![1], vec![2]];
+    /// let mut sets = Vec::new();
+    /// let mut it = v.powerset_ref();
+    /// while let Some(set) = it.next_ref() {
+    ///     sets.push(set.len());
+    /// }
+    /// assert_eq!(sets, [0, 1, 1, 2]);
+    /// ```
+    fn powerset_ref(&self) -> SlicePowerset<'_, T>;
+}
+
+impl<T> SliceCombinatorics<T> for [T] {
+    fn combinations_ref(&self, k: usize) -> SliceCombinations<'_, T> {
+        SliceCombinations {
+            slice: self,
+            indices: combination_indices(self.len(), k),
+            items: Vec::with_capacity(k),
+        }
+    }
+
+    fn array_combinations_ref<const K: usize>(&self) -> ArrayCombinations<slice::Iter<'_, T>, K> {
+        array_combinations(self.iter())
+    }
+
+    fn permutations_ref(&self, k: usize) -> SlicePermutations<'_, T> {
+        let n = self.len();
+        SlicePermutations {
+            slice: self,
+            indices: (0..n).collect(),
+            cycles: (n.saturating_sub(k)..n).rev().collect(),
+            first: true,
+            done: k > n,
+            items: Vec::with_capacity(k),
+        }
+    }
+
+    fn powerset_ref(&self) -> SlicePowerset<'_, T> {
+        SlicePowerset {
+            slice: self,
+            indices: combination_indices(self.len(), 0),
+            items: Vec::with_capacity(self.len()),
+        }
+    }
+}
+
+/// Fills `items` with references to the elements of `slice` at the given `indices`.
+fn select<'s, 'a, T>(slice: &'a [T], indices: &[usize], items: &'s mut Vec<&'a T>) -> &'s [&'a T] {
+    items.clear();
+    items.extend(indices.iter().map(|&i| &slice[i]));
+    items
+}
+
+/// The `k`-length combinations of references to the elements of a slice.
+///
+/// See [`.combinations_ref()`](SliceCombinatorics::combinations_ref) for more information.
+#[must_use = "SliceCombinations is lazy and do nothing unless consumed"]
+pub struct SliceCombinations<'a, T> {
+    slice: &'a [T],
+    indices: CombinationIndices,
+    items: Vec<&'a T>,
+}
+
+impl<'a, T> SliceCombinations<'a, T> {
+    /// Advances to the next combination and returns references to its elements,
+    /// or `None` if there is no combination left.
+    pub fn next_ref(&mut self) -> Option<&[&'a T]> {
+        if !self.indices.advance() {
+            return None;
+        }
+        Some(select(self.slice, self.indices.current(), &mut self.items))
+    }
+}
+
+/// The `k`-length permutations of references to the elements of a slice.
+///
+/// See [`.permutations_ref()`](SliceCombinatorics::permutations_ref) for more information.
+#[must_use = "SlicePermutations is lazy and do nothing unless consumed"]
+pub struct SlicePermutations<'a, T> {
+    slice: &'a [T],
+    /// All the indices of the slice, the current permutation being the first `k` ones.
+    indices: Vec<usize>,
+    /// The state of [`Permutations`](crate::Permutations), of length `k`.
+    cycles: Vec<usize>,
+    first: bool,
+    done: bool,
+    items: Vec<&'a T>,
+}
+
+impl<'a, T> SlicePermutations<'a, T> {
+    /// Advances to the next permutation and returns references to its elements,
+    /// or `None` if there is no permutation left.
+    pub fn next_ref(&mut self) -> Option<&[&'a T]> {
+        if self.done {
+            return None;
+        }
+        if self.first {
+            self.first = false;
+        } else if permutations::advance(&mut self.indices, &mut self.cycles) {
+            self.done = true;
+            return None;
+        }
+        let k = self.cycles.len();
+        Some(select(self.slice, &self.indices[..k], &mut self.items))
+    }
+}
+
+/// The powerset of references to the elements of a slice.
+///
+/// See [`.powerset_ref()`](SliceCombinatorics::powerset_ref) for more information.
+#[must_use = "SlicePowerset is lazy and do nothing unless consumed"]
+pub struct SlicePowerset<'a, T> {
+    slice: &'a [T],
+    /// The combinations of the current size.
+    indices: CombinationIndices,
+    items: Vec<&'a T>,
+}
+
+impl<'a, T> SlicePowerset<'a, T> {
+    /// Advances to the next subset and returns references to its elements,
+    /// or `None` if there is no subset left.
+    pub fn next_ref(&mut self) -> Option<&[&'a T]> {
+        while !self.indices.advance() {
+            let k = self.indices.current().len() + 1;
+            if k > self.slice.len() {
+                return None;
+            }
+            self.indices = combination_indices(self.slice.len(), k);
+        }
+        Some(select(self.slice, self.indices.current(), &mut self.items))
+    }
+}
+
+macro_rules! impl_clone_debug {
+    ($name:ident, $($field:ident),*) => {
+        impl<'a, T> Clone for $name<'a, T> {
+            clone_fields!($($field),*);
+        }
+
+        impl<'a, T: fmt::Debug> fmt::Debug for $name<'a, T> {
+            debug_fmt_fields!($name, slice, indices);
+        }
+    };
+}
+
+impl_clone_debug!(SliceCombinations, slice, indices, items);
+impl_clone_debug!(
+    SlicePermutations,
+    slice,
+    indices,
+    cycles,
+    first,
+    done,
+    items
+);
+impl_clone_debug!(SlicePowerset, slice, indices, items);
@@ -2,6 +2,8 @@
 //! not from another iterator).
 #![allow(deprecated)]
 
+#[cfg(feature = "use_alloc")]
+use alloc::vec::Vec;
 use std::fmt;
 use std::mem;
 
@@ -151,3 +153,291 @@ where
         f,
     }
 }
+
+/// An iterator over the partitions of an integer, see [`integer_partitions()`](crate::integer_partitions).
+#[cfg(feature = "use_alloc")]
+#[derive(Clone, Debug)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct IntegerPartitions {
+    n: usize,
+    max_parts: usize,
+    max_part: usize,
+    /// The current partition, in non-increasing order.
+    parts: Vec<usize>,
+    first: bool,
+    /// The count of remaining partitions, or `None` if it would overflow.
+    remaining: Option<usize>,
+}
+
+#[cfg(feature = "use_alloc")]
+impl IntegerPartitions {
+    /// Only generate the partitions with at most `k` parts.
+    ///
+    /// This restarts the iteration from the first partition, and counts the partitions
+    /// again in `O(k·m·n)` time for at most `k` parts, each at most `m`.
+    pub fn max_parts(mut self, k: usize) -> Self {
+        self.max_parts = k;
+        self.reset();
+        self
+    }
+
+    /// Only generate the partitions whose parts are at most `m`.
+    ///
+    /// This restarts the iteration from the first partition, and counts the partitions
+    /// again like [`max_parts`](Self::max_parts).
+    pub fn max_part(mut self, m: usize) -> Self {
+        self.max_part = m;
+        self.reset();
+        self
+    }
+
+    fn reset(&mut self) {
+        self.first = true;
+        self.parts.clear();
+        let cap = self.max_part.min(self.n);
+        // Even the partition with the fewest parts may not fit.
+        if self.n > 0 && (cap == 0 || div_ceil(self.n, cap) > self.max_parts) {
+            self.remaining = Some(0);
+            return;
+        }
+        self.remaining = count_partitions(self.n, self.max_parts, self.max_part);
+        if self.n > 0 {
+            push_parts(&mut self.parts, self.n, cap);
+        }
+    }
+}
+
+#[cfg(feature = "use_alloc")]
+impl Iterator for IntegerPartitions {
+    type Item = Vec<usize>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == Some(0) {
+            return None;
+        }
+        if self.first {
+            self.first = false;
+        } else if advance_partition(&mut self.parts, self.max_parts) {
+            self.remaining = Some(0);
+            return None;
+        }
+        if let Some(remaining) = self.remaining.as_mut() {
+            *remaining -= 1;
+        }
+        Some(self.parts.clone())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining.unwrap_or(usize::MAX), self.remaining)
+    }
+}
+
+#[cfg(feature = "use_alloc")]
+impl std::iter::FusedIterator for IntegerPartitions {}
+
+/// Creates a new iterator over the partitions of `n`: the ways to write `n` as a sum of
+/// positive integers, regardless of their order.
+///
+/// Iterator element type is `Vec<usize>`. Each partition is sorted in non-increasing order,
+/// and the partitions are generated in reverse lexicographic order, starting from `[n]`.
+///
+/// The partitions can be restricted to at most `k` parts with
+/// [`.max_parts(k)`](IntegerPartitions::max_parts), or to parts at most `m` with
+/// [`.max_part(m)`](IntegerPartitions::max_part).
+///
+/// ```
+/// use itertools::integer_partitions;
+///
+/// itertools::assert_equal(integer_partitions(5), vec![
+///     vec![5],
+///     vec![4, 1],
+///     vec![3, 2],
+///     vec![3, 1, 1],
+///     vec![2, 2, 1],
+///     vec![2, 1, 1, 1],
+///     vec![1, 1, 1, 1, 1],
+/// ]);
+/// itertools::assert_equal(integer_partitions(5).max_parts(2).max_part(3), vec![
+///     vec![3, 2],
+/// ]);
+/// assert_eq!(integer_partitions(100).size_hint(), (190_569_292, Some(190_569_292)));
+/// ```
+#[cfg(feature = "use_alloc")]
+pub fn integer_partitions(n: usize) -> IntegerPartitions {
+    let mut partitions = IntegerPartitions {
+        n,
+        max_parts: usize::MAX,
+        max_part: usize::MAX,
+        parts: Vec::new(),
+        first: true,
+        remaining: None,
+    };
+    partitions.reset();
+    partitions
+}
+
+/// Pushes the parts of the smallest partition of `sum` with parts at most `cap`:
+/// as many `cap` as possible, then the remainder.
+#[cfg(feature = "use_alloc")]
+fn push_parts(parts: &mut Vec<usize>, sum: usize, cap: usize) {
+    parts.extend(std::iter::repeat(cap).take(sum / cap));
+    if sum % cap > 0 {
+        parts.push(sum % cap);
+    }
+}
+
+#[cfg(feature = "use_alloc")]
+fn div_ceil(a: usize, b: usize) -> usize {
+    (a + b - 1) / b
+}
+
+/// Moves `parts` to the next partition in reverse lexicographic order with at most
+/// `max_parts` parts.
+///
+/// Returns true if there is no next partition, false otherwise.
+#[cfg(feature = "use_alloc")]
+fn advance_partition(parts: &mut Vec<usize>, max_parts: usize) -> bool {
+    let mut suffix_sum = 0;
+    // Scan from the end, looking for a part that can be decremented...
+    for i in (0..parts.len()).rev() {
+        suffix_sum += parts[i];
+        if parts[i] > 1 {
+            let cap = parts[i] - 1;
+            let rest = suffix_sum - cap;
+            // ...while the parts to its right still fit.
+            if i + 1 + div_ceil(rest, cap) <= max_parts {
+                parts.truncate(i);
+                parts.push(cap);
+                push_parts(parts, rest, cap);
+                return false;
+            }
+        }
+    }
+    true
+}
+
+/// Returns the count of partitions of `n` with at most `max_parts` parts, each at most
+/// `max_part`, or `None` if it would overflow.
+#[cfg(feature = "use_alloc")]
+fn count_partitions(n: usize, max_parts: usize, max_part: usize) -> Option<usize> {
+    let (k, m) = (max_parts.min(n), max_part.min(n));
+    // By transposing their Young diagrams, partitions with at most `k` parts are
+    // in bijection with partitions with parts at most `k`.
+    if k == n || m == n {
+        let cap = if k == n { m } else { k };
+        let mut counts = alloc::vec![Some(0usize); n + 1];
+        counts[0] = Some(1);
+        for part in 1..=cap {
+            for sum in part..=n {
+                counts[sum] = counts[sum].and_then(|count| count.checked_add(counts[sum - part]?));
+            }
+            // The counts only grow with the parts allowed: stop at the first overflow.
+            counts[n]?;
+        }
+        return counts[n];
+    }
+    // The same bijection swaps the bounds, keep the fewest rows.
+    let (k, m) = (k.min(m), k.max(m));
+    // `counts[c][sum]` is the count of partitions of `sum` into `c` parts at most `part`.
+    let mut counts = alloc::vec![alloc::vec![Some(0usize); n + 1]; k + 1];
+    counts[0][0] = Some(1);
+    for part in 1..=m {
+        for c in 1..=k {
+            for sum in part..=n {
+                counts[c][sum] =
+                    counts[c][sum].and_then(|count| count.checked_add(counts[c - 1][sum - part]?));
+            }
+            counts[c][n]?;
+        }
+    }
+    counts
+        .iter()
+        .try_fold(0usize, |total, counts| total.checked_add(counts[n]?))
+}
+
+/// An iterator over the compositions of an integer, see [`compositions()`](crate::compositions).
+#[cfg(feature = "use_alloc")]
+#[derive(Clone, Debug)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct Compositions {
+    n: usize,
+    /// The positions `1..n` where the current composition is cut, minus one.
+    cuts: Vec<usize>,
+    first: bool,
+    /// The count of remaining compositions, or `None` if it would overflow.
+    remaining: Option<usize>,
+}
+
+#[cfg(feature = "use_alloc")]
+impl Iterator for Compositions {
+    type Item = Vec<usize>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == Some(0) {
+            return None;
+        }
+        if self.first {
+            self.first = false;
+        } else if crate::combinations::increment_indices(&mut self.cuts, self.n - 1) {
+            self.remaining = Some(0);
+            return None;
+        }
+        if let Some(remaining) = self.remaining.as_mut() {
+            *remaining -= 1;
+        }
+        if self.n == 0 {
+            return Some(Vec::new());
+        }
+        let mut parts = Vec::with_capacity(self.cuts.len() + 1);
+        let mut start = 0;
+        for &cut in &self.cuts {
+            parts.push(cut + 1 - start);
+            start = cut + 1;
+        }
+        parts.push(self.n - start);
+        Some(parts)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining.unwrap_or(usize::MAX), self.remaining)
+    }
+}
+
+#[cfg(feature = "use_alloc")]
+impl std::iter::FusedIterator for Compositions {}
+
+/// Creates a new iterator over the compositions of `n` into `k` parts: the ways to write
+/// `n` as an ordered sum of `k` positive integers.
+///
+/// Iterator element type is `Vec<usize>` of length `k`. The compositions are generated
+/// in lexicographic order, and there are `binomial(n - 1, k - 1)` of them.
+///
+/// ```
+/// use itertools::compositions;
+///
+/// itertools::assert_equal(compositions(5, 3), vec![
+///     vec![1, 1, 3],
+///     vec![1, 2, 2],
+///     vec![1, 3, 1],
+///     vec![2, 1, 2],
+///     vec![2, 2, 1],
+///     vec![3, 1, 1],
+/// ]);
+/// assert_eq!(compositions(5, 0).count(), 0);
+/// assert_eq!(compositions(0, 0).count(), 1);
+/// ```
+#[cfg(feature = "use_alloc")]
+pub fn compositions(n: usize, k: usize) -> Compositions {
+    let remaining = match k.checked_sub(1) {
+        // Only zero is the sum of no parts.
+        None => Some(usize::from(n == 0)),
+        Some(_) if n < k => Some(0),
+        Some(cuts) => crate::count::binomial(n - 1, cuts),
+    };
+    Compositions {
+        n,
+        cuts: (0..k.saturating_sub(1)).collect(),
+        first: true,
+        remaining,
+    }
+}
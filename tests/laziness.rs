@@ -57,6 +57,10 @@ must_use_tests! {
     interleave_shortest {
         let _ = Panicking.interleave_shortest(Panicking);
     }
+    interleave_all {
+        let _ = vec![Panicking, Panicking, Panicking].into_iter().interleave_all();
+        let _ = vec![Panicking, Panicking, Panicking].into_iter().interleave_all_shortest();
+    }
     intersperse {
         let _ = Panicking.intersperse(0);
     }
@@ -86,6 +90,20 @@ must_use_tests! {
         // Groups
         let _ = Panicking.chunk_by(|x| *x).into_iter();
     }
+    chunk_by_owned {
+        let _ = Panicking.chunk_by_owned(|x| *x);
+    }
+    chunks_owned {
+        let _ = Panicking.chunks_owned(1);
+        let _ = Panicking.chunks_owned(2);
+    }
+    aggregate_runs {
+        // AggregateRuns
+        let _ = Panicking.aggregate_runs(|x| *x);
+        // RunAggregates
+        let _ = Panicking.aggregate_runs(|x| *x).count();
+        let _ = Panicking.aggregate_runs(|x| *x).fold(0, |acc, _, x| acc + x);
+    }
     chunks {
         // IntoChunks
         let _ = Panicking.chunks(1);
@@ -169,6 +187,12 @@ must_use_tests! {
     multi_cartesian_product {
         let _ = vec![Panicking, Panicking, Panicking].into_iter().multi_cartesian_product();
     }
+    diagonal_product {
+        let _ = Panicking.diagonal_product(Panicking);
+    }
+    multi_diagonal_product {
+        let _ = vec![Panicking, Panicking, Panicking].into_iter().multi_diagonal_product();
+    }
     coalesce {
         let _ = Panicking.coalesce(|x, y| if x == y { Ok(x) } else { Err((x, y)) });
     }
@@ -236,6 +260,20 @@ must_use_tests! {
         let _ = Panicking.combinations(1);
         let _ = Panicking.combinations(2);
     }
+    combinations_pruned {
+        let _ = Panicking.combinations_pruned(2, |_| true);
+    }
+    cartesian_power {
+        let _ = Panicking.cartesian_power(0);
+        let _ = Panicking.cartesian_power(2);
+        let _ = Panicking.array_cartesian_power::<3>();
+    }
+    multiset_combinations {
+        let _ = Panicking.multiset_combinations(0);
+        let _ = Panicking.multiset_combinations(1);
+        let _ = Panicking.multiset_combinations_by_key(2, |x| *x % 3);
+        let _ = Panicking.multiset_combinations_hashed(2);
+    }
     combinations_with_replacement {
         let _ = Panicking.combinations_with_replacement(0);
         let _ = Panicking.combinations_with_replacement(1);
@@ -251,9 +289,29 @@ must_use_tests! {
         let _ = Panicking.permutations(1);
         let _ = Panicking.permutations(2);
     }
+    permutations_minimal_change {
+        let _ = Panicking.permutations_minimal_change();
+    }
+    distinct_permutations {
+        let _ = Panicking.distinct_permutations();
+        let _ = Panicking.distinct_permutations_by(|a, b| b.cmp(a));
+    }
     powerset {
         let _ = Panicking.powerset();
     }
+    powerset_range {
+        let _ = Panicking.powerset_range(1..=2);
+    }
+    set_partitions {
+        let _ = Panicking.set_partitions();
+        let _ = Panicking.set_partitions_k(2);
+    }
+    powerset_gray {
+        let _ = Panicking.powerset_gray();
+    }
+    powerset_masks {
+        let _ = Panicking.powerset_masks::<u64>();
+    }
     pad_using {
         let _ = Panicking.pad_using(25, |_| 10);
     }
@@ -276,6 +334,21 @@ must_use_tests! {
     into_grouping_map_by {
         let _ = Panicking.into_grouping_map_by(|x| *x);
     }
+    into_grouping_map_ok {
+        let _ = Panicking.map(|x| Ok::<_, ()>((x, x + 1))).into_grouping_map_ok();
+    }
+    into_grouping_map_ok_by {
+        let _ = Panicking.map(Ok::<_, ()>).into_grouping_map_ok_by(|x| *x);
+    }
+    into_grouping_btree_map {
+        let _ = Panicking.map(|x| (x, x + 1)).into_grouping_btree_map();
+    }
+    into_grouping_btree_map_by {
+        let _ = Panicking.into_grouping_btree_map_by(|x| *x);
+    }
+    into_grouping_vec {
+        let _ = Panicking.into_grouping_vec(10, |x| usize::from(*x));
+    }
     // Macros:
     iproduct {
         let _ = itertools::iproduct!(Panicking);
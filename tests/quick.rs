@@ -16,7 +16,7 @@ use itertools::Itertools;
 use itertools::{iproduct, izip, multizip, EitherOrBoth};
 use quickcheck as qc;
 use std::cmp::{max, min, Ordering};
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::default::Default;
 use std::num::Wrapping;
 use std::ops::Range;
@@ -429,6 +429,12 @@ quickcheck! {
     fn size_multi_product(a: ShiftRange) -> bool {
         correct_size_hint(a.multi_cartesian_product())
     }
+    fn size_diagonal_product(a: Iter<u16>, b: Iter<u16>) -> bool {
+        correct_size_hint(a.diagonal_product(b))
+    }
+    fn size_multi_diagonal_product(a: ShiftRange) -> bool {
+        correct_size_hint(a.multi_diagonal_product())
+    }
     fn correct_multi_product3(a: ShiftRange, take_manual: usize) -> () {
         // Fix no. of iterators at 3
         let a = ShiftRange { iter_count: 3, ..a };
@@ -631,6 +637,28 @@ quickcheck! {
     fn exact_interleave_shortest(a: Vec<()>, b: Vec<()>) -> bool {
         exact_size_for_this(a.iter().interleave_shortest(&b))
     }
+    fn size_interleave_all(a: Iter<i16>, b: Iter<i16>, c: Iter<i16>) -> bool {
+        correct_size_hint(vec![a, b, c].into_iter().interleave_all())
+    }
+    fn exact_interleave_all(a: Iter<i16, Exact>, b: Iter<i16, Exact>) -> bool {
+        exact_size_for_this(vec![a, b].into_iter().interleave_all())
+    }
+    fn size_interleave_all_shortest(a: Iter<i16>, b: Iter<i16>, c: Iter<i16>) -> bool {
+        correct_size_hint(vec![a, b, c].into_iter().interleave_all_shortest())
+    }
+    fn exact_interleave_all_shortest(a: Vec<()>, b: Vec<()>, c: Vec<()>) -> bool {
+        exact_size_for_this(vec![a, b, c].into_iter().interleave_all_shortest())
+    }
+    fn equal_interleave_all(a: Vec<i16>, b: Vec<i16>) -> bool {
+        // Two iterators are interleaved like with `interleave`.
+        itertools::equal(
+            vec![a.clone(), b.clone()].into_iter().interleave_all(),
+            a.iter().copied().interleave(b.iter().copied()),
+        ) && itertools::equal(
+            vec![a.clone(), b.clone()].into_iter().interleave_all_shortest(),
+            a.into_iter().interleave_shortest(b),
+        )
+    }
     fn size_intersperse(a: Iter<i16>, x: i16) -> bool {
         correct_size_hint(a.intersperse(x))
     }
@@ -738,6 +766,67 @@ quickcheck! {
         correct_size_hint(a.take(5).permutations(k))
     }
 
+    fn distinct_permutations_match_unique_permutations(a: Vec<u8>) -> TestResult {
+        if a.len() > 6 {
+            return TestResult::discard();
+        }
+        let a = a.into_iter().map(|x| x % 3).collect_vec();
+        let n = a.len();
+        let expected = a.iter().permutations(n).unique().sorted().collect_vec();
+        let actual = a.iter().distinct_permutations().collect_vec();
+        TestResult::from_bool(expected == actual)
+    }
+
+    fn distinct_permutations_size(a: Iter<u8>) -> bool {
+        correct_size_hint(a.take(6).map(|x| x % 3).distinct_permutations())
+    }
+
+    fn distinct_permutations_count(a: Vec<u8>) -> TestResult {
+        if a.len() > 6 {
+            return TestResult::discard();
+        }
+        TestResult::from_bool(correct_count(|| a.iter().map(|x| x % 3).distinct_permutations()))
+    }
+
+    fn multiset_combinations_match_unique_combinations(a: Vec<u8>, k: u8) -> TestResult {
+        if a.len() > 8 || k > 5 {
+            return TestResult::discard();
+        }
+        let a = a.into_iter().map(|x| x % 4).collect_vec();
+        let actual = a.iter().multiset_combinations(k as usize).collect_vec();
+        let sorted = a.iter().sorted().collect_vec();
+        let expected = sorted.into_iter().combinations(k as usize).unique().collect_vec();
+        TestResult::from_bool(expected == actual)
+    }
+
+    fn multiset_combinations_hashed_match_unique_combinations(a: Vec<u8>, k: u8) -> TestResult {
+        if a.len() > 8 || k > 5 {
+            return TestResult::discard();
+        }
+        let a = a.into_iter().map(|x| x % 4).collect_vec();
+        let actual = a.iter().multiset_combinations_hashed(k as usize).collect_vec();
+        let first_seen = |x: &&u8| a.iter().position(|y| y == *x);
+        let grouped = a.iter().sorted_by_key(first_seen).collect_vec();
+        let expected = grouped.into_iter().combinations(k as usize).unique().collect_vec();
+        TestResult::from_bool(expected == actual)
+    }
+
+    fn multiset_combinations_size(a: Iter<u8>, k: u8) -> TestResult {
+        if k > 5 {
+            return TestResult::discard();
+        }
+        let it = a.take(8).map(|x| x % 4).multiset_combinations(k as usize);
+        TestResult::from_bool(correct_size_hint(it))
+    }
+
+    fn multiset_combinations_count(a: Vec<u8>, k: u8) -> TestResult {
+        if a.len() > 8 || k > 5 {
+            return TestResult::discard();
+        }
+        let it = || a.iter().multiset_combinations_by_key(k as usize, |x| *x % 4);
+        TestResult::from_bool(correct_count(it))
+    }
+
     fn permutations_k0_yields_once(n: usize) -> () {
         let k = 0;
         let expected: Vec<Vec<usize>> = vec![vec![]];
@@ -946,6 +1035,11 @@ quickcheck! {
         correct_size_hint(it.clone()) && it.count() == binomial(a.count(), 2)
     }
 
+    fn size_combinations_pruned(a: Iter<i16, Exact>, k: u8) -> bool {
+        let it = a.take(10).combinations_pruned(k as usize % 5, |prefix| prefix[0] % 3 != 0);
+        correct_size_hint(it)
+    }
+
     fn exact_size_combinations_1(a: Vec<u8>) -> bool {
         let it = a.iter().tuple_combinations::<(_,)>();
         exact_size_for_this(it.clone()) && it.count() == binomial(a.len(), 1)
@@ -1042,6 +1136,26 @@ quickcheck! {
         // Powerset cardinality gets large very quickly, limit input to keep test fast.
         correct_size_hint(it.take(12).powerset())
     }
+
+    fn size_powerset_range(it: Iter<u8, Exact>, min: u8, max: u8) -> bool {
+        correct_size_hint(it.take(12).powerset_range(usize::from(min % 8)..=usize::from(max % 8)))
+    }
+
+    fn size_set_partitions(it: Iter<u8, Exact>, k: Option<u8>) -> bool {
+        let it = it.take(7);
+        match k {
+            Some(k) => correct_size_hint(it.set_partitions_k(k as usize % 9)),
+            None => correct_size_hint(it.set_partitions()),
+        }
+    }
+
+    fn size_powerset_gray(it: Iter<u8, Exact>) -> bool {
+        correct_size_hint(it.take(12).powerset_gray())
+    }
+
+    fn size_powerset_masks(it: Iter<u8, Exact>) -> bool {
+        correct_size_hint(it.take(12).powerset_masks::<u16>())
+    }
 }
 
 quickcheck! {
@@ -1154,6 +1268,77 @@ quickcheck! {
     }
 }
 
+quickcheck! {
+    fn equal_chunk_by_owned(data: Vec<u8>) -> bool {
+        let lazy = data.iter().chunk_by(|k| *k / 10);
+        let owned = data.iter().chunk_by_owned(|k| *k / 10);
+        itertools::equal(
+            lazy.into_iter().map(|(key, group)| (key, group.collect_vec())),
+            owned,
+        )
+    }
+    fn size_chunk_by_owned(a: Iter<u8>) -> bool {
+        correct_size_hint(a.chunk_by_owned(|k| *k / 10))
+    }
+    fn equal_chunks_owned(a: Vec<u8>, size: u8) -> bool {
+        let size = usize::from(size.max(1));
+        itertools::equal(a.iter().chunks_owned(size), a.chunks(size).map(|s| s.iter().collect_vec()))
+    }
+    fn size_chunks_owned(a: Iter<u8>, size: u8) -> bool {
+        correct_size_hint(a.chunks_owned(usize::from(size.max(1))))
+    }
+    fn exact_chunks_owned(a: Vec<u8>, size: u8) -> bool {
+        exact_size_for_this(a.iter().chunks_owned(usize::from(size.max(1))))
+    }
+}
+
+quickcheck! {
+    fn equal_aggregate_runs_fold(data: Vec<u8>) -> bool {
+        let expected = data.iter().chunk_by(|k| *k / 10).into_iter()
+            .map(|(key, group)| (key, group.fold(u64::from(key), |acc, &x| acc.wrapping_mul(3).wrapping_add(u64::from(x)))))
+            .collect_vec();
+        let runs = data.iter().aggregate_runs(|k| *k / 10)
+            .fold_with(|&key, _| u64::from(key), |acc, _, &x| acc.wrapping_mul(3).wrapping_add(u64::from(x)));
+        itertools::equal(runs, expected)
+    }
+    fn equal_aggregate_runs_collect(data: Vec<u8>) -> bool {
+        let runs = data.iter().aggregate_runs(|k| *k / 10).collect::<Vec<_>>();
+        itertools::equal(runs, data.iter().chunk_by_owned(|k| *k / 10))
+    }
+    fn equal_aggregate_runs_count_sum(data: Vec<u8>) -> bool {
+        let expected = data.iter().chunk_by_owned(|k| *k / 10).collect_vec();
+        let counts = data.iter().aggregate_runs(|k| *k / 10).count();
+        let sums = data.iter().map(|&x| u64::from(x)).aggregate_runs(|k| *k / 10).sum();
+        itertools::equal(counts, expected.iter().map(|(key, group)| (*key, group.len())))
+            && itertools::equal(sums, expected.iter().map(|(key, group)| (u64::from(*key), group.iter().map(|&&x| u64::from(x)).sum())))
+    }
+    fn equal_aggregate_runs_minmax(data: Vec<(u8, u8)>) -> bool {
+        // Compare on the second field only, to check which of equal elements are picked.
+        let expected = data.iter().chunk_by(|(k, _)| *k / 10).into_iter()
+            .map(|(key, group)| (key, group.minmax_by_key(|(_, v)| *v)))
+            .collect_vec();
+        let runs = data.iter().aggregate_runs(|(k, _)| *k / 10).minmax_by(|_, (_, v1), (_, v2)| v1.cmp(v2));
+        itertools::equal(runs, expected)
+    }
+    fn equal_aggregate_runs_extrema_by_key(data: Vec<(u8, u8)>) -> bool {
+        let expected = data.iter().chunk_by_owned(|(k, _)| *k / 10).collect_vec();
+        let runs = || data.iter().aggregate_runs(|(k, _)| *k / 10);
+        itertools::equal(
+            runs().max_by_key(|_, (_, v)| *v),
+            expected.iter().map(|(key, group)| (*key, group.iter().copied().max_by_key(|(_, v)| *v).unwrap())),
+        ) && itertools::equal(
+            runs().min_by_key(|_, (_, v)| *v),
+            expected.iter().map(|(key, group)| (*key, group.iter().copied().min_by_key(|(_, v)| *v).unwrap())),
+        ) && itertools::equal(
+            runs().minmax_by_key(|_, (_, v)| *v),
+            expected.iter().map(|(key, group)| (*key, group.iter().copied().minmax_by_key(|(_, v)| *v))),
+        )
+    }
+    fn size_aggregate_runs(a: Iter<u8>) -> bool {
+        correct_size_hint(a.aggregate_runs(|k| *k / 10).count())
+    }
+}
+
 // tuple iterators
 quickcheck! {
     fn equal_circular_tuple_windows_1(a: Vec<u8>) -> bool {
@@ -1836,6 +2021,133 @@ quickcheck! {
         }
     }
 
+    fn correct_grouping_map_by_statistics_modulo_key(a: Vec<u8>, modulo: u8) -> () {
+        let modulo = if modulo == 0 { 1 } else { modulo }; // Avoid `% 0`
+        let grouping_map = a.iter().copied().into_grouping_map_by(|i| i % modulo);
+        let group_map = a.iter().copied().map(|i| (i % modulo, i)).into_group_map();
+
+        let count = grouping_map.clone().count();
+        let first = grouping_map.clone().first();
+        let last = grouping_map.clone().last();
+        let mean = grouping_map.clone().mean();
+        let unique = grouping_map.unique();
+        assert_eq!(count.len(), group_map.len());
+        for (key, vals) in group_map {
+            assert_eq!(count[&key], vals.len());
+            assert_eq!(first[&key], vals[0]);
+            assert_eq!(last[&key], vals[vals.len() - 1]);
+            let sum: u64 = vals.iter().map(|&v| u64::from(v)).sum();
+            assert!((mean[&key] - sum as f64 / vals.len() as f64).abs() < 1e-9);
+            assert_eq!(unique[&key], vals.into_iter().unique().collect_vec());
+        }
+    }
+
+    fn correct_grouping_map_by_mean_by_modulo_key(a: Vec<u64>, modulo: u64) -> () {
+        let modulo = if modulo == 0 { 1 } else { modulo }; // Avoid `% 0`
+        let mean = a.iter().copied().into_grouping_map_by(|i| i % modulo).mean_by(|_, val| val as f64);
+        let group_map = a.iter().copied().map(|i| (i % modulo, i)).into_group_map();
+        assert_eq!(mean.len(), group_map.len());
+        for (key, vals) in group_map {
+            let sum: f64 = vals.iter().map(|&v| v as f64).sum();
+            assert_eq!(mean[&key], sum / vals.len() as f64);
+        }
+    }
+
+    fn correct_grouping_map_by_k_smallest_modulo_key(a: Vec<u8>, modulo: u8, k: u8) -> () {
+        let modulo = if modulo == 0 { 1 } else { modulo }; // Avoid `% 0`
+        let k = usize::from(k % 8);
+        let grouping_map = a.iter().copied().into_grouping_map_by(|i| i % modulo);
+        let smallest = grouping_map.clone().k_smallest(k);
+        let largest = grouping_map.clone().k_largest(k);
+        let smallest_by_key = grouping_map.k_smallest_by_key(k, |_, &val| val / 4);
+
+        let group_map = a.iter().copied().map(|i| (i % modulo, i)).into_group_map();
+        assert_eq!(smallest.len(), group_map.len());
+        for (key, vals) in group_map {
+            assert_eq!(smallest[&key], vals.iter().copied().sorted().take(k).collect_vec());
+            assert_eq!(largest[&key], vals.iter().copied().sorted().rev().take(k).collect_vec());
+            assert_eq!(
+                smallest_by_key[&key].iter().map(|&val| val / 4).collect_vec(),
+                vals.iter().map(|&val| val / 4).sorted().take(k).collect_vec()
+            );
+        }
+    }
+
+    fn correct_grouping_map_by_try_fold_modulo_key(a: Vec<u8>, modulo: u8, limit: u16) -> () {
+        let modulo = if modulo == 0 { 1 } else { modulo }; // Avoid `% 0`
+        let mut consumed = 0;
+        let lookup = a.iter()
+            .inspect(|_| consumed += 1)
+            .map(|&b| u16::from(b))
+            .into_grouping_map_by(|i| i % u16::from(modulo))
+            .try_fold(0, |acc, _, val| {
+                let acc = acc + val;
+                if acc > limit { Err(acc) } else { Ok(acc) }
+            });
+
+        // The first group sum to exceed the limit is the error.
+        let mut sums = HashMap::new();
+        let mut expected = Ok(());
+        let mut expected_consumed = a.len();
+        for (idx, &val) in a.iter().enumerate() {
+            let sum = sums.entry(val % modulo).or_insert(0);
+            *sum += u16::from(val);
+            if *sum > limit {
+                expected = Err(*sum);
+                expected_consumed = idx + 1;
+                break;
+            }
+        }
+        assert_eq!(consumed, expected_consumed);
+        match expected {
+            Ok(()) => {
+                let sums = sums.into_iter().map(|(key, sum)| (u16::from(key), sum)).collect::<HashMap<_, _>>();
+                assert_eq!(lookup, Ok(sums));
+            }
+            Err(sum) => assert_eq!(lookup, Err(sum)),
+        }
+    }
+
+    fn correct_grouping_map_by_try_reduce_modulo_key(a: Vec<u8>, modulo: u8) -> () {
+        let modulo = if modulo == 0 { 1 } else { modulo }; // Avoid `% 0`
+        let lookup = a.iter().copied()
+            .into_grouping_map_by(|i| i % modulo)
+            .try_reduce(|acc, _, val| acc.checked_add(val).ok_or(()));
+
+        let sums = a.iter().map(|&b| u64::from(b)).into_grouping_map_by(|i| i % u64::from(modulo)).sum();
+        if sums.values().all(|&sum| sum <= u64::from(u8::MAX)) {
+            let lookup = lookup.unwrap();
+            assert_eq!(lookup.len(), sums.len());
+            for (key, sum) in lookup {
+                assert_eq!(u64::from(sum), sums[&u64::from(key)]);
+            }
+        } else {
+            assert_eq!(lookup, Err(()));
+        }
+    }
+
+    fn correct_grouping_map_ok_modulo_key(a: Vec<Result<u8, i8>>, modulo: u8) -> () {
+        let modulo = if modulo == 0 { 1 } else { modulo }; // Avoid `% 0`
+        let results = || a.iter().map(|res| res.map(|val| (val % modulo, val)));
+
+        let lookup = results().into_grouping_map_ok().collect::<Vec<_>>();
+        assert_eq!(lookup, results().process_results(|iter| iter.into_group_map()));
+        let lookup = results().into_grouping_map_ok().max();
+        assert_eq!(lookup, results().process_results(|iter| iter.into_grouping_map().max()));
+        let lookup = a.iter().copied().into_grouping_map_ok_by(|val| val % modulo).unique();
+        assert_eq!(lookup, results().process_results(|iter| iter.into_grouping_map().unique()));
+        // The first error, of either the source or the operation, comes first.
+        let lookup = results().into_grouping_map_ok().try_reduce(|_, _, _| Err(0));
+        let first_error = a.iter().position(Result::is_err);
+        let first_pair = a.iter().copied().enumerate().filter_map(|(idx, res)| res.ok().map(|val| (idx, val % modulo))).duplicates_by(|&(_, key)| key).next();
+        match (first_error, first_pair) {
+            (Some(err_idx), Some((pair_idx, _))) if pair_idx < err_idx => assert_eq!(lookup, Err(0)),
+            (Some(err_idx), _) => assert_eq!(lookup, Err(a[err_idx].unwrap_err())),
+            (None, Some(_)) => assert_eq!(lookup, Err(0)),
+            (None, None) => assert!(lookup.is_ok()),
+        }
+    }
+
     fn correct_grouping_map_by_product_modulo_key(a: Vec<u8>, modulo: u8) -> () {
         let modulo = Wrapping(u64::from(if modulo == 0 { 1 } else { modulo })); // Avoid `% 0`
         let lookup = a.iter().map(|&b| Wrapping(u64::from(b))) // Avoid overflows
@@ -1908,6 +2220,113 @@ quickcheck! {
     }
 }
 
+quickcheck! {
+    fn consistent_grouping_btree_map_with_grouping_map(a: Vec<u8>, modulo: u8) -> () {
+        let modulo = if modulo == 0 { 1 } else { modulo }; // Avoid `% 0`
+        fn sorted<K: Ord, V>(map: HashMap<K, V>) -> BTreeMap<K, V> {
+            map.into_iter().collect()
+        }
+
+        let lookup = a.iter().copied().map(|i| (i % modulo, i)).into_grouping_btree_map().collect::<Vec<_>>();
+        let lookup_by = a.iter().copied().into_grouping_btree_map_by(|i| i % modulo).collect::<Vec<_>>();
+        assert_eq!(lookup, lookup_by);
+        assert_eq!(lookup, sorted(a.iter().copied().into_grouping_map_by(|i| i % modulo).collect::<Vec<_>>()));
+        assert_eq!(lookup, a.iter().copied().into_group_btree_map_by(|i| i % modulo));
+        assert_eq!(lookup, a.iter().copied().map(|i| (i % modulo, i)).into_group_btree_map());
+
+        let fold = |acc: u64, _: &u8, val: u8| acc.wrapping_mul(3).wrapping_add(u64::from(val) + 1);
+        assert_eq!(
+            a.iter().copied().into_grouping_btree_map_by(|i| i % modulo).fold(0, fold),
+            sorted(a.iter().copied().into_grouping_map_by(|i| i % modulo).fold(0, fold))
+        );
+        assert_eq!(
+            a.iter().copied().into_grouping_btree_map_by(|i| i % modulo).minmax_by_key(|_, &val| val / 4),
+            sorted(a.iter().copied().into_grouping_map_by(|i| i % modulo).minmax_by_key(|_, &val| val / 4))
+        );
+        assert_eq!(
+            a.iter().map(|&i| u64::from(i)).into_grouping_btree_map_by(|i| i % u64::from(modulo)).sum(),
+            sorted(a.iter().map(|&i| u64::from(i)).into_grouping_map_by(|i| i % u64::from(modulo)).sum())
+        );
+        assert_eq!(
+            a.iter().copied().into_grouping_btree_map_by(|i| i % modulo).k_largest(3),
+            sorted(a.iter().copied().into_grouping_map_by(|i| i % modulo).k_largest(3))
+        );
+        assert_eq!(
+            a.iter().copied().into_grouping_btree_map_by(|i| i % modulo).unique(),
+            sorted(a.iter().copied().into_grouping_map_by(|i| i % modulo).unique())
+        );
+        assert_eq!(
+            a.iter().copied().into_grouping_btree_map_by(|i| i % modulo).mean(),
+            sorted(a.iter().copied().into_grouping_map_by(|i| i % modulo).mean())
+        );
+    }
+
+    fn consistent_grouping_vec_with_grouping_map(a: Vec<u8>, modulo: u8) -> () {
+        let modulo = usize::from(if modulo == 0 { 1 } else { modulo }); // Avoid `% 0`
+        let num_keys = modulo + 1;
+        fn dense<V: Clone>(map: HashMap<usize, V>, num_keys: usize) -> Vec<Option<V>> {
+            (0..num_keys).map(|key| map.get(&key).cloned()).collect()
+        }
+
+        let lookup = a.iter().copied().into_grouping_vec(num_keys, |&i| usize::from(i) % modulo);
+        assert_eq!(
+            lookup.clone().collect::<Vec<_>>().unwrap(),
+            (0..num_keys)
+                .map(|key| a.iter().copied().filter(|&i| usize::from(i) % modulo == key).collect_vec())
+                .map(|group| Some(group).filter(|group| !group.is_empty()))
+                .collect_vec()
+        );
+        let map = a.iter().copied().into_grouping_map_by(|&i| usize::from(i) % modulo);
+        assert_eq!(lookup.clone().max_by_key(|_, &val| val / 4).unwrap(), dense(map.clone().max_by_key(|_, &val| val / 4), num_keys));
+        assert_eq!(lookup.clone().min().unwrap(), dense(map.clone().min(), num_keys));
+        let fold = |acc: u64, _: &usize, val: u8| acc.wrapping_mul(3).wrapping_add(u64::from(val) + 1);
+        assert_eq!(lookup.clone().fold(7, fold).unwrap(), dense(map.clone().fold(7, fold), num_keys));
+        assert_eq!(lookup.clone().k_smallest(2).unwrap(), dense(map.clone().k_smallest(2), num_keys));
+        assert_eq!(lookup.unique().unwrap(), dense(map.unique(), num_keys));
+    }
+
+    fn grouping_vec_key_out_of_range(a: Vec<u8>, num_keys: u8) -> () {
+        let num_keys = usize::from(num_keys);
+        let mut consumed = 0;
+        let res = a.iter()
+            .inspect(|_| consumed += 1)
+            .into_grouping_vec(num_keys, |&&i| usize::from(i))
+            .collect::<Vec<&u8>>();
+        match a.iter().position(|&i| usize::from(i) >= num_keys) {
+            Some(pos) => {
+                let err = res.unwrap_err();
+                assert_eq!((err.key, err.num_keys), (usize::from(a[pos]), num_keys));
+                // The source is not consumed after the first key out of range.
+                assert_eq!(consumed, pos + 1);
+            }
+            None => assert_eq!(res.unwrap().len(), num_keys),
+        }
+    }
+
+    fn consistent_counts_dense(nums: Vec<u8>) -> () {
+        let counts = nums.iter().map(|&i| usize::from(i)).counts_dense(256).unwrap();
+        let expected = nums.iter().counts();
+        assert_eq!(counts.iter().sum::<usize>(), nums.len());
+        for (key, &count) in counts.iter().enumerate() {
+            assert_eq!(count, expected.get(&(key as u8)).copied().unwrap_or(0));
+        }
+        if let Some(&max) = nums.iter().max() {
+            let err = nums.iter().map(|&i| usize::from(i)).counts_dense(usize::from(max)).unwrap_err();
+            assert!(err.key >= usize::from(max));
+        }
+    }
+
+    fn consistent_counts_ordered(nums: Vec<i8>) -> () {
+        let counts = nums.iter().counts_ordered();
+        assert_eq!(counts, nums.iter().counts().into_iter().collect::<BTreeMap<_, _>>());
+        assert_eq!(counts.values().sum::<usize>(), nums.len());
+        assert_eq!(
+            nums.iter().counts_ordered_by(|x| x / 10),
+            nums.iter().counts_by(|x| x / 10).into_iter().collect::<BTreeMap<_, _>>()
+        );
+    }
+}
+
 quickcheck! {
     fn test_double_ended_zip_2(a: Vec<u8>, b: Vec<u8>) -> TestResult {
         let mut x =
@@ -176,7 +176,15 @@ quickcheck! {
         if a.len() * b.len() * c.len() > 100 {
             return TestResult::discard();
         }
-        test_specializations(&vec![a, b, c].into_iter().multi_cartesian_product());
+        let it = vec![a.clone(), b.clone(), c.clone()].into_iter().multi_cartesian_product();
+        test_specializations(&it);
+        test_double_ended_specializations(&it);
+        // Without exact size hints.
+        let it = vec![a, b, c]
+            .into_iter()
+            .map(|v| v.into_iter().filter(|_| true))
+            .multi_cartesian_product();
+        test_specializations(&it);
         TestResult::passed()
     }
 
@@ -288,7 +296,9 @@ quickcheck! {
         if n > 3 || a.len() > 8 {
             return TestResult::discard();
         }
-        test_specializations(&a.iter().combinations(n as usize));
+        let it = a.iter().combinations(n as usize);
+        test_specializations(&it);
+        test_double_ended_specializations(&it);
         TestResult::passed()
     }
 
@@ -310,11 +320,23 @@ quickcheck! {
 
         TestResult::passed()
     }
+    fn cartesian_power(a: Vec<u8>, n: u8) -> TestResult {
+        if n > 3 || a.len() > 6 {
+            return TestResult::discard();
+        }
+        test_specializations(&a.iter().cartesian_power(n as usize));
+        test_specializations(&a.iter().filter(|_| true).cartesian_power(n as usize));
+        test_specializations(&a.iter().array_cartesian_power::<2>());
+        TestResult::passed()
+    }
+
     fn permutations(a: Vec<u8>, n: u8) -> TestResult {
         if n > 3 || a.len() > 8 {
             return TestResult::discard();
         }
-        test_specializations(&a.iter().permutations(n as usize));
+        let it = a.iter().permutations(n as usize);
+        test_specializations(&it);
+        test_double_ended_specializations(&it);
         TestResult::passed()
     }
 
@@ -322,7 +344,19 @@ quickcheck! {
         if a.len() > 6 {
             return TestResult::discard();
         }
-        test_specializations(&a.iter().powerset());
+        let it = a.iter().powerset();
+        test_specializations(&it);
+        test_double_ended_specializations(&it);
+        TestResult::passed()
+    }
+
+    fn powerset_range(a: Vec<u8>, min: u8, max: u8) -> TestResult {
+        if a.len() > 6 {
+            return TestResult::discard();
+        }
+        let it = a.iter().powerset_range(usize::from(min % 8)..=usize::from(max % 8));
+        test_specializations(&it);
+        test_double_ended_specializations(&it);
         TestResult::passed()
     }
 
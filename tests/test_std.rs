@@ -86,6 +86,35 @@ fn interleave_shortest() {
     assert_eq!(it.size_hint(), (6, Some(6)));
 }
 
+#[test]
+fn interleave_all() {
+    let it = vec![vec![0, 3, 6, 8], vec![1, 4], vec![], vec![2, 5, 7]]
+        .into_iter()
+        .interleave_all();
+    assert_eq!(it.size_hint(), (9, Some(9)));
+    it::assert_equal(it, 0..9);
+    // Each iterator gets its turn once per round, even when infinite.
+    let it = it::interleave_all(vec![0.., 100.., 200..]);
+    it::assert_equal(it.take(6), vec![0, 100, 200, 1, 101, 201]);
+    it::assert_equal(it::interleave_all(Vec::<Vec<i32>>::new()), vec![]);
+
+    let it = it::interleave_all_shortest(vec![vec![0, 3, 6, 9], vec![1, 4, 7], vec![2, 5]]);
+    assert_eq!(it.size_hint(), (8, Some(8)));
+    it::assert_equal(it, 0..8);
+    let it = vec![(0..).step_by(2), (1..).step_by(2)]
+        .into_iter()
+        .interleave_all_shortest();
+    assert_eq!(it.size_hint(), (usize::MAX, None));
+    it::assert_equal(it.take(5), 0..5);
+    let mut it = vec![0..2, 0..0, 5..10]
+        .into_iter()
+        .interleave_all_shortest();
+    assert_eq!(it.size_hint(), (1, Some(1)));
+    assert_eq!(it.next(), Some(0));
+    assert_eq!(it.size_hint(), (0, Some(0)));
+    assert_eq!(it.next(), None);
+}
+
 #[test]
 fn duplicates_by() {
     let xs = ["aaa", "bbbbb", "aa", "ccc", "bbbb", "aaaaa", "cccc"];
@@ -988,6 +1017,69 @@ fn chunk_by() {
     }
 }
 
+#[test]
+fn chunk_by_owned() {
+    let chunks = "AAABCCDDDD".chars().chunk_by_owned(|&x| x);
+    assert_eq!(chunks.size_hint(), (1, Some(10)));
+    it::assert_equal(
+        chunks,
+        vec![
+            ('A', vec!['A', 'A', 'A']),
+            ('B', vec!['B']),
+            ('C', vec!['C', 'C']),
+            ('D', vec!['D', 'D', 'D', 'D']),
+        ],
+    );
+    it::assert_equal("".chars().chunk_by_owned(|&x| x), vec![]);
+
+    // check that the key closure is called exactly n times
+    let mut ntimes = 0;
+    let text = "AABCCC";
+    let chunks = text.chars().chunk_by_owned(|&x| {
+        ntimes += 1;
+        x
+    });
+    assert_eq!(chunks.count(), 3);
+    assert_eq!(ntimes, text.len());
+
+    // The groups can be sent to another thread along with the adaptor.
+    fn assert_send<T: Send>(t: T) -> T {
+        t
+    }
+    let chunks = assert_send((0..10).chunk_by_owned(|x| x / 4));
+    let handle = std::thread::spawn(move || chunks.collect_vec());
+    assert_eq!(
+        handle.join().unwrap(),
+        vec![
+            (0, vec![0, 1, 2, 3]),
+            (1, vec![4, 5, 6, 7]),
+            (2, vec![8, 9])
+        ]
+    );
+}
+
+#[test]
+fn chunks_owned() {
+    let mut chunks = (0..7).chunks_owned(3);
+    assert_eq!(chunks.len(), 3);
+    assert_eq!(chunks.next(), Some(vec![0, 1, 2]));
+    assert_eq!(chunks.len(), 2);
+    it::assert_equal(chunks, vec![vec![3, 4, 5], vec![6]]);
+    it::assert_equal(
+        (0..6).chunks_owned(2),
+        vec![vec![0, 1], vec![2, 3], vec![4, 5]],
+    );
+    it::assert_equal((0..0).chunks_owned(2), Vec::<Vec<i32>>::new());
+    let chunks = (0..).chunks_owned(usize::MAX);
+    assert_eq!(chunks.size_hint(), (1, None));
+}
+
+#[test]
+#[should_panic]
+fn chunks_owned_zero() {
+    let _ = (0..3).chunks_owned(0);
+}
+
 #[test]
 fn chunk_by_lazy_2() {
     let data = [0, 1];
@@ -1131,6 +1223,45 @@ fn combinations_of_too_short() {
     }
 }
 
+#[test]
+fn combinations_pruned() {
+    for n in 0..7 {
+        for k in 0..5 {
+            // Reject the prefixes with two consecutive values or ending with `3`.
+            let accept = |prefix: &[i32]| {
+                let last = prefix[prefix.len() - 1];
+                last != 3
+                    && prefix
+                        .iter()
+                        .rev()
+                        .nth(1)
+                        .map_or(true, |&prev| prev + 1 != last)
+            };
+            let expected = (0..n)
+                .combinations(k)
+                .filter(|c| (1..=k).all(|len| accept(&c[..len])))
+                .collect_vec();
+            it::assert_equal((0..n).combinations_pruned(k, accept), expected);
+        }
+    }
+    // Rejected prefixes are not extended, and prefixes which can not be completed are not tried.
+    let mut calls = 0;
+    let it = (0..10).combinations_pruned(3, |prefix| {
+        calls += 1;
+        prefix[0] == 0
+    });
+    assert_eq!(it.count(), 36);
+    assert_eq!(calls, 8 + 8 + 36);
+    // The source is consumed lazily.
+    it::assert_equal(
+        (0..)
+            .combinations_pruned(2, |prefix| prefix[0] == 0)
+            .take(3),
+        vec![vec![0, 1], vec![0, 2], vec![0, 3]],
+    );
+    it::assert_equal((0..3).combinations_pruned(0, |_| false), vec![vec![]]);
+}
+
 #[test]
 fn combinations_zero() {
     it::assert_equal((1..3).combinations(0), vec![vec![]]);
@@ -1213,6 +1344,89 @@ fn combinations_inexact_size_hints() {
     }
 }
 
+#[test]
+fn multiset_combinations() {
+    it::assert_equal(
+        "aabbc".chars().multiset_combinations(2),
+        vec![
+            vec!['a', 'a'],
+            vec!['a', 'b'],
+            vec!['a', 'c'],
+            vec!['b', 'b'],
+            vec!['b', 'c'],
+        ],
+    );
+    it::assert_equal("aab".chars().multiset_combinations(0), vec![vec![]]);
+    it::assert_equal(
+        "aab".chars().multiset_combinations(3),
+        vec![vec!['a', 'a', 'b']],
+    );
+    assert_eq!("aab".chars().multiset_combinations(4).next(), None);
+    it::assert_equal(
+        vec![(1, 'x'), (0, 'y'), (1, 'z')]
+            .into_iter()
+            .multiset_combinations_by_key(2, |&(k, _)| k),
+        vec![vec![(0, 'y'), (1, 'x')], vec![(1, 'x'), (1, 'z')]],
+    );
+
+    let it = std::iter::repeat(0).take(20).multiset_combinations(10);
+    assert_eq!(it.size_hint(), (1, Some(binomial(20, 10))));
+    assert_eq!(it.count(), 1);
+}
+
+#[test]
+fn combinations_nth_jumps() {
+    for n in 0..=7 {
+        for k in 0..=7 {
+            let all = (0..n).combinations(k).collect_vec();
+            for skip in 0..=all.len() + 1 {
+                let mut it = (0..n).combinations(k);
+                assert_eq!(it.nth(skip).as_ref(), all.get(skip));
+                assert_eq!(it.next().as_ref(), all.get(skip + 1));
+            }
+        }
+    }
+    // Far away combinations are reached without stepping through the previous ones.
+    let mut it = (0..1_000_000).combinations(3);
+    let nth = binomial(1_000_000, 3) - 1;
+    assert_eq!(it.nth(nth), Some(vec![999_997, 999_998, 999_999]));
+    assert_eq!(it.next(), None);
+    // Close combinations do not load the whole source.
+    let pulled = std::cell::Cell::new(0);
+    let mut it = (0..1_000_000)
+        .inspect(|_| pulled.set(pulled.get() + 1))
+        .combinations(3);
+    assert_eq!(it.nth(1), Some(vec![0, 1, 3]));
+    assert_eq!(pulled.get(), 4);
+}
+
+#[test]
+fn combinations_rank_unrank() {
+    for n in 0..=7 {
+        for k in 0..=n {
+            let all = (0..n).combinations(k).collect_vec();
+            let mut it = (0..n).combinations(k);
+            it.nth(usize::MAX); // Load the whole pool.
+            for (rank, indices) in all.iter().enumerate() {
+                let rank = rank as u128;
+                assert_eq!(it.rank(indices), Some(rank));
+                assert_eq!(
+                    it::count::unrank_combination(n, k, rank).as_ref(),
+                    Some(indices)
+                );
+            }
+            let rank = all.len() as u128;
+            assert_eq!(it::count::unrank_combination(n, k, rank), None);
+        }
+    }
+    let mut it = (0..4).combinations(2);
+    it.nth(100);
+    assert_eq!(it.rank(&[0, 3]), Some(2));
+    assert_eq!(it.rank(&[0, 4]), None);
+    assert_eq!(it.rank(&[1, 1]), None);
+    assert_eq!(it.rank(&[0, 1, 2]), None);
+}
+
 #[test]
 fn permutations_zero() {
     it::assert_equal((1..3).permutations(0), vec![vec![]]);
@@ -1241,6 +1455,48 @@ fn permutations_range_count() {
     }
 }
 
+#[test]
+fn permutations_nth_jumps() {
+    for n in 0..=5 {
+        for k in 0..=6 {
+            let all = (0..n).permutations(k).collect_vec();
+            // Start from each kind of state: not started, not fully loaded and loaded.
+            for start in 0..=n + 2 {
+                for skip in 0..=all.len() + 1 {
+                    let mut it = (0..n).permutations(k);
+                    it.by_ref().take(start).for_each(drop);
+                    let at = start.min(all.len()) + skip;
+                    assert_eq!(it.nth(skip).as_ref(), all.get(at));
+                    assert_eq!(it.next().as_ref(), all.get(at + 1));
+                }
+            }
+            // Jumps stop before the permutations generated from the back.
+            for skip in 0..=all.len() {
+                let mut it = (0..n).permutations(k);
+                it.next_back();
+                let expected = all.get(skip).filter(|_| skip + 1 < all.len());
+                assert_eq!(it.nth(skip).as_ref(), expected);
+            }
+        }
+    }
+    // Far away permutations are reached without stepping through the previous ones.
+    let mut it = (0..20).permutations(20);
+    let mut last = (0..20).collect_vec();
+    last.reverse();
+    assert_eq!(it.nth((1..=20).product::<usize>() - 1), Some(last));
+    assert_eq!(it.next(), None);
+    let parts = (0..11).permutations(11).split_into(8);
+    let lens = parts.iter().map(|part| part.size_hint().1).collect_vec();
+    assert_eq!(lens, [Some(4_989_600); 8]);
+    // Close permutations do not load the whole source.
+    let pulled = std::cell::Cell::new(0);
+    let mut it = (0..1_000_000)
+        .inspect(|_| pulled.set(pulled.get() + 1))
+        .permutations(2);
+    assert_eq!(it.nth(1), Some(vec![0, 2]));
+    assert_eq!(pulled.get(), 3);
+}
+
 #[test]
 fn permutations_overflowed_size_hints() {
     let mut it = std::iter::repeat(()).permutations(2);
@@ -1253,6 +1509,83 @@ fn permutations_overflowed_size_hints() {
     }
 }
 
+#[test]
+fn permutation_swaps() {
+    for n in 0..=6 {
+        let mut v = (0..n).collect_vec();
+        let mut seen = std::collections::HashSet::new();
+        seen.insert(v.clone());
+        let mut swaps = it::permutation_swaps(n);
+        let len = (1..=n).product::<usize>();
+        assert_eq!(swaps.size_hint(), (len - 1, Some(len - 1)));
+        for (i, j) in swaps.by_ref() {
+            assert_eq!(i + 1, j);
+            v.swap(i, j);
+            assert!(seen.insert(v.clone()));
+        }
+        assert_eq!(seen.len(), len);
+        assert_eq!(swaps.next(), None);
+        assert_eq!(swaps.size_hint(), (0, Some(0)));
+    }
+}
+
+#[test]
+fn permutations_minimal_change() {
+    for n in 0..=5 {
+        let mut it = (0..n).permutations_minimal_change();
+        let (first, swap) = it.next().unwrap();
+        assert_eq!(first, (0..n).collect_vec());
+        assert_eq!(swap, None);
+        let mut prev = first;
+        let mut count = 1;
+        for (perm, swap) in it.by_ref() {
+            let (i, j) = swap.unwrap();
+            prev.swap(i, j);
+            assert_eq!(prev, perm);
+            count += 1;
+        }
+        assert_eq!(count, (1..=n).product::<usize>());
+        assert_eq!(it.next(), None);
+    }
+    let mut it = (0..5).permutations_minimal_change();
+    for len in (0..=120).rev() {
+        assert_eq!(it.size_hint(), (len, Some(len)));
+        it.next();
+    }
+}
+
+#[test]
+fn distinct_permutations() {
+    it::assert_equal((0..0).distinct_permutations(), vec![vec![]]);
+    it::assert_equal(
+        vec![1, 1].into_iter().distinct_permutations(),
+        vec![vec![1, 1]],
+    );
+    it::assert_equal(
+        vec![2, 1, 2, 1].into_iter().distinct_permutations(),
+        vec![
+            vec![1, 1, 2, 2],
+            vec![1, 2, 1, 2],
+            vec![1, 2, 2, 1],
+            vec![2, 1, 1, 2],
+            vec![2, 1, 2, 1],
+            vec![2, 2, 1, 1],
+        ],
+    );
+    it::assert_equal(
+        (1..4).distinct_permutations_by(|a, b| b.cmp(a)),
+        (1..4).permutations(3).sorted().rev(),
+    );
+
+    let it = "mississippi".chars().distinct_permutations();
+    assert_eq!(it.size_hint(), (1, Some(39_916_800)));
+    // 11! / (4! * 4! * 2!)
+    assert_eq!(it.count(), 34_650);
+    let mut it = "aab".chars().distinct_permutations();
+    it.next();
+    assert_eq!(it.size_hint(), (2, Some(2)));
+}
+
 #[test]
 #[cfg(not(miri))]
 fn combinations_with_replacement() {
@@ -1371,6 +1704,712 @@ fn powerset() {
     }
 }
 
+#[test]
+fn combinatorics_double_ended() {
+    fn check<I>(it: I)
+    where
+        I: DoubleEndedIterator + Clone,
+        I::Item: PartialEq + std::fmt::Debug,
+    {
+        let forward = it.clone().collect_vec();
+        let mut backward = it.clone().rev().collect_vec();
+        backward.reverse();
+        assert_eq!(backward, forward);
+        // Alternate between both ends, they must meet without overlapping.
+        for start_back in [false, true] {
+            let mut it = it.clone();
+            let (mut front, mut back) = (vec![], vec![]);
+            let mut from_back = start_back;
+            loop {
+                let elt = if from_back { it.next_back() } else { it.next() };
+                let size_hint = it.size_hint();
+                match elt {
+                    Some(elt) if from_back => back.push(elt),
+                    Some(elt) => front.push(elt),
+                    None => break,
+                }
+                let len = forward.len() - front.len() - back.len();
+                assert_eq!(size_hint, (len, Some(len)));
+                from_back = !from_back;
+            }
+            assert_eq!(it.next(), None);
+            assert_eq!(it.next_back(), None);
+            front.extend(back.into_iter().rev());
+            assert_eq!(front, forward);
+        }
+    }
+    for n in 0..=5 {
+        for k in 0..=6 {
+            check((0..n).combinations(k));
+            check((0..n).permutations(k));
+        }
+        check((0..n).array_combinations::<2>());
+        check((0..n).powerset());
+    }
+    it::assert_equal(
+        (1..5).combinations(2).rev().take(2),
+        vec![vec![3, 4], vec![2, 4]],
+    );
+    it::assert_equal(
+        (1..4).permutations(2).rev().take(2),
+        vec![vec![3, 2], vec![3, 1]],
+    );
+    it::assert_equal(
+        (1..4).powerset().rev().take(3),
+        vec![vec![1, 2, 3], vec![2, 3], vec![1, 3]],
+    );
+}
+
+#[test]
+fn powerset_range() {
+    use std::ops::Bound::{self, Excluded, Included, Unbounded};
+    let bounds = |n: usize| {
+        let mut bounds = vec![Unbounded];
+        for i in 0..=n + 1 {
+            bounds.push(Included(i));
+            bounds.push(Excluded(i));
+        }
+        bounds
+    };
+    for n in 0..5 {
+        for start in bounds(n) {
+            for end in bounds(n) {
+                let range: (Bound<usize>, Bound<usize>) = (start, end);
+                let expected = (0..n)
+                    .powerset()
+                    .filter(|set| std::ops::RangeBounds::contains(&range, &set.len()))
+                    .collect_vec();
+                let it = (0..n).powerset_range(range);
+                assert_eq!(it.size_hint(), (expected.len(), Some(expected.len())));
+                assert_eq!(it.clone().count(), expected.len());
+                it::assert_equal(it.clone(), expected.iter().cloned());
+                it::assert_equal(it.clone().rev(), expected.iter().rev().cloned());
+                let folded = it.clone().fold(vec![], |mut acc, set| {
+                    acc.push(set);
+                    acc
+                });
+                assert_eq!(folded, expected);
+                for i in 0..=expected.len() {
+                    let mut it = it.clone();
+                    assert_eq!(it.nth(i).as_ref(), expected.get(i));
+                    assert_eq!(it.size_hint().1, Some(expected.len().saturating_sub(i + 1)));
+                }
+                let mut it = it;
+                let mut front = vec![];
+                let mut back = vec![];
+                while let Some(set) = it.next() {
+                    front.push(set);
+                    match it.next_back() {
+                        Some(set) => back.push(set),
+                        None => break,
+                    }
+                }
+                back.reverse();
+                front.extend(back);
+                assert_eq!(front, expected);
+            }
+        }
+    }
+    it::assert_equal(
+        (0..).powerset_range(1..=1).take(3),
+        vec![vec![0], vec![1], vec![2]],
+    );
+}
+
+#[test]
+fn powerset_gray() {
+    use it::SubsetDelta::{Added, Removed};
+    for n in 0..=8 {
+        let mut subset = std::collections::BTreeSet::new();
+        let mut seen = std::collections::HashSet::new();
+        seen.insert(subset.iter().copied().collect_vec());
+        let mut it = (0..n).powerset_gray();
+        assert_eq!(it.size_hint(), ((1 << n) - 1, Some((1 << n) - 1)));
+        for delta in it.by_ref() {
+            match delta {
+                Added(x) => assert!(subset.insert(x)),
+                Removed(x) => assert!(subset.remove(&x)),
+            }
+            assert!(seen.insert(subset.iter().copied().collect_vec()));
+        }
+        assert_eq!(seen.len(), 1 << n);
+        assert_eq!(it.next(), None);
+        assert_eq!(it.size_hint(), (0, Some(0)));
+    }
+    // Only the elements needed so far are pulled.
+    it::assert_equal(
+        (0..).powerset_gray().take(4),
+        vec![Added(0), Added(1), Removed(0), Added(2)],
+    );
+}
+
+#[test]
+fn powerset_masks() {
+    for n in 0..=8 {
+        let mut it = (0..n).powerset_masks::<u16>();
+        assert_eq!(it.size_hint(), (1 << n, Some(1 << n)));
+        it::assert_equal(it.by_ref(), 0..1 << n);
+        assert_eq!(it.next(), None);
+    }
+    assert_eq!((0..8).powerset_masks::<u8>().count(), 256);
+    assert_eq!((0..64).powerset_masks::<u64>().size_hint().1, None);
+    it::assert_equal((0..).powerset_masks::<u128>().take(5), 0..5);
+}
+
+#[test]
+#[should_panic]
+fn powerset_masks_too_many_elements() {
+    (0..9).powerset_masks::<u8>().for_each(drop);
+}
+
+#[test]
+fn slice_combinatorics() {
+    use it::SliceCombinatorics;
+    // Not `Clone`.
+    #[derive(Debug, PartialEq)]
+    struct Heavy(usize);
+    let items = (0..5).map(Heavy).collect_vec();
+    let by_index = |indices: Vec<usize>| indices.into_iter().map(|i| &items[i]).collect_vec();
+    macro_rules! collect_ref {
+        ($it:expr) => {{
+            let mut it = $it;
+            let mut all = Vec::new();
+            while let Some(item) = it.next_ref() {
+                all.push(item.to_vec());
+            }
+            assert_eq!(it.next_ref(), None);
+            all
+        }};
+    }
+    for k in 0..=6 {
+        it::assert_equal(
+            collect_ref!(items.combinations_ref(k)),
+            (0..5).combinations(k).map(by_index),
+        );
+        it::assert_equal(
+            collect_ref!(items.permutations_ref(k)),
+            (0..5).permutations(k).map(by_index),
+        );
+    }
+    it::assert_equal(
+        items.array_combinations_ref::<3>(),
+        (0..5)
+            .array_combinations::<3>()
+            .map(|c| c.map(|i| &items[i])),
+    );
+    it::assert_equal(
+        collect_ref!(items.powerset_ref()),
+        (0..5).powerset().map(by_index),
+    );
+    it::assert_equal(
+        collect_ref!(items[..0].powerset_ref()),
+        vec![Vec::<&Heavy>::new()],
+    );
+}
+
+#[test]
+fn index_combinatorics() {
+    for n in 0..=6 {
+        for k in 0..=7 {
+            let mut it = it::combination_indices(n, k);
+            assert_eq!(it.len(), binomial(n, k));
+            it::assert_equal(it.by_ref(), (0..n).combinations(k));
+            assert_eq!(it.next(), None);
+            let mut it = it::permutation_indices(n, k);
+            assert_eq!(it.len(), (0..n).permutations(k).count());
+            it::assert_equal(it.by_ref(), (0..n).permutations(k));
+            assert_eq!(it.next(), None);
+        }
+    }
+    for dims in [vec![], vec![0], vec![3], vec![2, 0, 4], vec![2, 3, 4]] {
+        let mut it = it::product_indices(&dims);
+        assert_eq!(it.len(), dims.iter().product::<usize>());
+        let expected = dims.iter().map(|&dim| 0..dim).multi_cartesian_product();
+        if dims.is_empty() {
+            it::assert_equal(it.by_ref(), vec![vec![]]);
+        } else {
+            it::assert_equal(it.by_ref(), expected);
+        }
+        assert_eq!(it.next(), None);
+        assert_eq!(it.len(), 0);
+    }
+    assert_eq!(
+        it::product_indices(&[usize::MAX, 2]).size_hint(),
+        (usize::MAX, None)
+    );
+    assert_eq!(
+        it::combination_indices(200, 100).size_hint(),
+        (usize::MAX, None)
+    );
+    assert_eq!(
+        it::permutation_indices(200, 100).size_hint(),
+        (usize::MAX, None)
+    );
+}
+
+#[test]
+fn set_partitions() {
+    const BELL: [usize; 8] = [1, 1, 2, 5, 15, 52, 203, 877];
+    for (n, bell) in BELL.iter().copied().enumerate() {
+        let mut it = (0..n).set_partitions();
+        assert_eq!(it.size_hint(), (bell, Some(bell)));
+        let partitions = it.by_ref().collect_vec();
+        assert_eq!(it.next(), None);
+        assert_eq!(partitions.len(), bell);
+        // The restricted growth strings are increasing.
+        let rgs = partitions
+            .iter()
+            .map(|blocks| {
+                let mut rgs = vec![usize::MAX; n];
+                for (b, block) in blocks.iter().enumerate() {
+                    assert!(!block.is_empty());
+                    assert!(block.windows(2).all(|w| w[0] < w[1]));
+                    for &i in block {
+                        assert_eq!(rgs[i], usize::MAX);
+                        rgs[i] = b;
+                    }
+                }
+                rgs
+            })
+            .collect_vec();
+        assert!(rgs.windows(2).all(|w| w[0] < w[1]));
+        for k in 0..=n + 1 {
+            let by_k = (0..n).set_partitions_k(k);
+            let count = by_k.size_hint().0;
+            assert_eq!(by_k.size_hint(), (count, Some(count)));
+            let expected = partitions.iter().filter(|blocks| blocks.len() == k);
+            it::assert_equal((0..n).set_partitions_k(k), expected.cloned());
+            assert_eq!((0..n).set_partitions_k(k).count(), count);
+        }
+    }
+}
+
+#[test]
+fn integer_partitions() {
+    for n in 0..=12 {
+        let all = it::integer_partitions(n).collect_vec();
+        // Sorted parts, in reverse lexicographic order.
+        assert!(all.iter().all(|p| p.iter().sum::<usize>() == n));
+        assert!(all
+            .iter()
+            .all(|p| p.windows(2).all(|w| w[0] >= w[1] && w[1] > 0)));
+        assert!(all.windows(2).all(|w| w[0] > w[1]));
+        for k in 0..=n + 1 {
+            for m in 0..=n + 1 {
+                let mut it = it::integer_partitions(n).max_parts(k).max_part(m);
+                let expected = all
+                    .iter()
+                    .filter(|p| p.len() <= k && p.iter().all(|&part| part <= m))
+                    .cloned()
+                    .collect_vec();
+                assert_eq!(it.size_hint(), (expected.len(), Some(expected.len())));
+                if it.next().is_some() {
+                    let len = expected.len() - 1;
+                    assert_eq!(it.size_hint(), (len, Some(len)));
+                }
+                it::assert_equal(it.by_ref(), expected.into_iter().skip(1));
+                assert_eq!(it.next(), None);
+                assert_eq!(it.size_hint(), (0, Some(0)));
+            }
+        }
+    }
+    assert_eq!(it::integer_partitions(1000).size_hint(), (usize::MAX, None));
+    assert_eq!(
+        it::integer_partitions(1000).max_parts(2).size_hint(),
+        (501, Some(501))
+    );
+    // The count stops at the first overflow.
+    let mut it = it::integer_partitions(1000).max_parts(500).max_part(500);
+    assert_eq!(it.size_hint(), (usize::MAX, None));
+    assert_eq!(it.next(), Some(vec![500, 500]));
+}
+
+#[test]
+fn compositions() {
+    for n in 0..=6 {
+        for k in 0..=n + 1 {
+            let mut it = it::compositions(n, k);
+            let expected = (0..k)
+                .map(|_| 1..=n)
+                .multi_cartesian_product()
+                .filter(|c| c.iter().sum::<usize>() == n)
+                .collect_vec();
+            let expected = if k == 0 && n == 0 {
+                vec![vec![]]
+            } else {
+                expected
+            };
+            assert_eq!(it.size_hint(), (expected.len(), Some(expected.len())));
+            it::assert_equal(it.by_ref(), expected);
+            assert_eq!(it.next(), None);
+        }
+    }
+}
+
+#[test]
+fn cartesian_power() {
+    for n in 0..=4 {
+        for k in 0..=4 {
+            let expected = if k == 0 {
+                vec![vec![]]
+            } else {
+                (0..k).map(|_| 0..n).multi_cartesian_product().collect_vec()
+            };
+            let mut it = (0..n).cartesian_power(k);
+            assert_eq!(it.size_hint(), (expected.len(), Some(expected.len())));
+            it::assert_equal(it.by_ref(), expected.iter().cloned());
+            assert_eq!(it.next(), None);
+            for i in 0..=expected.len() {
+                let mut it = (0..n).cartesian_power(k);
+                assert_eq!(it.nth(i).as_ref(), expected.get(i));
+                assert_eq!(it.count(), expected.len().saturating_sub(i + 1));
+            }
+            // Without a known upper bound, the source is consumed lazily.
+            let lazy = (0..n).filter(|_| true).take_while(|_| true);
+            it::assert_equal(lazy.cartesian_power(k), expected.iter().cloned());
+        }
+    }
+    it::assert_equal(
+        (0..3).array_cartesian_power::<2>(),
+        (0..3).cartesian_power(2).map(|v| [v[0], v[1]]),
+    );
+    it::assert_equal(
+        (0..).cartesian_power(2).take(3),
+        vec![vec![0, 0], vec![0, 1], vec![0, 2]],
+    );
+    assert_eq!(
+        (0..10).cartesian_power(19).size_hint(),
+        (10usize.pow(19), Some(10usize.pow(19)))
+    );
+    assert_eq!((0..10).cartesian_power(20).size_hint(), (usize::MAX, None));
+    assert_eq!(
+        (0..10).cartesian_power(30).nth(usize::MAX),
+        Some(vec![
+            0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1, 8, 4, 4, 6, 7, 4, 4, 0, 7, 3, 7, 0, 9, 5, 5, 1, 6, 1,
+            5
+        ])
+    );
+    // Close tuples do not load the whole source.
+    let pulled = std::cell::Cell::new(0);
+    let mut it = (0..1_000_000)
+        .inspect(|_| pulled.set(pulled.get() + 1))
+        .cartesian_power(2);
+    assert_eq!(it.nth(2), Some(vec![0, 2]));
+    assert_eq!(pulled.get(), 3);
+}
+
+#[test]
+fn multi_cartesian_product_random_access() {
+    for dims in [
+        vec![],
+        vec![0],
+        vec![3],
+        vec![2, 0, 4],
+        vec![2, 3, 4],
+        vec![1, 5, 1],
+    ] {
+        let product = || dims.iter().map(|&dim| 0..dim).multi_cartesian_product();
+        let expected = product().collect_vec();
+        for i in 0..=expected.len() {
+            let mut it = product();
+            assert_eq!(it.nth(i).as_ref(), expected.get(i));
+            assert_eq!(it.size_hint().1, Some(expected.len().saturating_sub(i + 1)));
+            it::assert_equal(it, expected.iter().skip(i + 1).cloned());
+            if let Some(item) = expected.get(i) {
+                assert_eq!(product().rank_of(item), Some(i));
+            }
+        }
+        it::assert_equal(product().rev(), expected.iter().rev().cloned());
+        // Meet in the middle.
+        let mut it = product();
+        let mut front = vec![];
+        let mut back = vec![];
+        while let Some(item) = it.next() {
+            front.push(item);
+            let len = expected.len() - front.len() - back.len();
+            assert_eq!(it.size_hint(), (len, Some(len)));
+            match it.next_back() {
+                Some(item) => back.push(item),
+                None => break,
+            }
+        }
+        back.reverse();
+        front.extend(back);
+        assert_eq!(front, expected);
+        assert_eq!(it.next(), None);
+        assert_eq!(it.next_back(), None);
+    }
+    let mut it = vec![0..10; 8].into_iter().multi_cartesian_product();
+    assert_eq!(it.nth(12_345_678), Some(vec![1, 2, 3, 4, 5, 6, 7, 8]));
+    assert_eq!(it.next_back(), Some(vec![9; 8]));
+    assert_eq!(it.size_hint(), (87_654_320, Some(87_654_320)));
+    assert_eq!(it.rank_of(&[0, 0, 0, 0, 0, 0, 1, 0]), Some(10));
+    assert_eq!(it.rank_of(&[0, 0]), None);
+    // The whole product is too long to be counted, but not what remains before the back.
+    let (mut head, _) = vec![0..usize::MAX; 2]
+        .into_iter()
+        .multi_cartesian_product()
+        .split_at(3);
+    assert_eq!(head.size_hint(), (3, Some(3)));
+    assert_eq!(head.clone().count(), 3);
+    head.next();
+    assert_eq!(head.count(), 2);
+}
+
+#[test]
+fn split_combinatorics() {
+    fn check<T, I>(
+        it: I,
+        expected: &[T],
+        split_at: fn(I, usize) -> (I, I),
+        split_into: fn(I, usize) -> Vec<I>,
+    ) where
+        T: PartialEq + std::fmt::Debug + Clone,
+        I: Iterator<Item = T> + Clone,
+    {
+        for rank in 0..=expected.len() + 1 {
+            let (head, tail) = split_at(it.clone(), rank);
+            let mid = rank.min(expected.len());
+            assert_eq!(head.size_hint(), (mid, Some(mid)));
+            assert_eq!(tail.size_hint().1, Some(expected.len() - mid));
+            assert_eq!(head.clone().count(), mid);
+            it::assert_equal(head.clone(), expected[..mid].iter().cloned());
+            it::assert_equal(tail, expected[mid..].iter().cloned());
+            // Split the head again, now bounded from the back.
+            let (first, second) = split_at(head, 1);
+            it::assert_equal(first.chain(second), expected[..mid].iter().cloned());
+        }
+        for parts in 1..=expected.len() + 2 {
+            let pieces = split_into(it.clone(), parts);
+            assert_eq!(pieces.len(), parts);
+            let lens = pieces
+                .iter()
+                .map(|piece| piece.clone().count())
+                .collect_vec();
+            assert!(lens.iter().max().unwrap() - lens.iter().min().unwrap() <= 1);
+            it::assert_equal(pieces.into_iter().flatten(), expected.iter().cloned());
+        }
+    }
+    for n in 0..5 {
+        for k in 0..4 {
+            let combinations = (0..n).combinations(k);
+            let expected = combinations.clone().collect_vec();
+            check(
+                combinations.clone(),
+                &expected,
+                it::Combinations::split_at,
+                it::Combinations::split_into,
+            );
+            let mut started = combinations;
+            if started.next().is_some() {
+                check(
+                    started,
+                    &expected[1..],
+                    it::Combinations::split_at,
+                    it::Combinations::split_into,
+                );
+            }
+            let permutations = (0..n).permutations(k);
+            let expected = permutations.clone().collect_vec();
+            check(
+                permutations.clone(),
+                &expected,
+                it::Permutations::split_at,
+                it::Permutations::split_into,
+            );
+            let mut started = permutations;
+            if started.next().is_some() {
+                check(
+                    started,
+                    &expected[1..],
+                    it::Permutations::split_at,
+                    it::Permutations::split_into,
+                );
+            }
+        }
+    }
+    for dims in [vec![], vec![0], vec![3], vec![2, 0, 4], vec![2, 3, 2]] {
+        let product = dims.iter().map(|&dim| 0..dim).multi_cartesian_product();
+        let expected = product.clone().collect_vec();
+        check(
+            product.clone(),
+            &expected,
+            it::MultiProduct::split_at,
+            it::MultiProduct::split_into,
+        );
+        let mut started = product;
+        if started.next().is_some() {
+            check(
+                started,
+                &expected[1..],
+                it::MultiProduct::split_at,
+                it::MultiProduct::split_into,
+            );
+        }
+    }
+}
+
+#[test]
+fn count_module() {
+    use it::count;
+    for n in 0..7 {
+        assert_eq!(count::powerset_len(n), Some((0..n).powerset().count()));
+        for k in 0..7 {
+            assert_eq!(count::binomial(n, k), Some((0..n).combinations(k).count()));
+            assert_eq!(
+                count::binomial_u128(n, k),
+                count::binomial(n, k).map(|c| c as u128)
+            );
+            let with_replacement = (0..n).combinations_with_replacement(k).count();
+            assert_eq!(count::multichoose(n, k), Some(with_replacement));
+            let permutations = (0..n).permutations(k).count();
+            assert_eq!(count::falling_factorial(n, k), Some(permutations));
+            let product = (0..k).map(|_| 0..n).multi_cartesian_product().count();
+            assert_eq!(count::product_len((0..k).map(|_| n)), Some(product));
+        }
+    }
+    assert_eq!(count::powerset_len(64), None);
+    assert_eq!(count::multichoose(usize::MAX, 2), None);
+    assert_eq!(count::falling_factorial(usize::MAX, 2), None);
+
+    let mut it = (0..10).combinations(3);
+    it.nth(5);
+    let len = it.checked_len();
+    assert_eq!(len, Some(114));
+    assert_eq!(it.next_back(), Some(vec![7, 8, 9]));
+    assert_eq!(it.checked_len(), Some(113));
+    assert_eq!(it.count(), 113);
+    assert_eq!((0..200).combinations(100).checked_len(), None);
+}
+
+#[test]
+#[cfg(feature = "rand")]
+fn random_sampling() {
+    use it::random;
+    use rand_pcg::{rand_core::SeedableRng, Pcg64};
+    let rng = || Pcg64::seed_from_u64(1234);
+    let seeded = |f: fn(&mut Pcg64) -> Vec<u32>| {
+        let first = f(&mut rng());
+        assert_eq!(f(&mut rng()), first, "not reproducible");
+        first
+    };
+
+    let sample = seeded(|rng| random::sample(0..10, 7, rng));
+    assert_eq!(sample.len(), 7);
+    assert!(sample.iter().all_unique() && sample.iter().all(|&x| x < 10));
+    for k in 0..12 {
+        assert_eq!(random::sample(0..10, k, &mut rng()).len(), k.min(10));
+        let combination = random::random_combination(0..10u32, k, &mut rng());
+        match combination {
+            Some(c) => {
+                assert_eq!(c.len(), k);
+                assert!(c.windows(2).all(|w| w[0] < w[1]));
+            }
+            None => assert!(k > 10),
+        }
+    }
+    let shuffled = seeded(|rng| random::shuffled(0..20, rng));
+    it::assert_equal(shuffled.iter().copied().sorted(), 0..20);
+    let weighted = seeded(|rng| {
+        random::sample_weighted(0..20, 5, |&x| if x < 10 { 0.0 } else { f64::from(x) }, rng)
+    });
+    assert_eq!(weighted.len(), 5);
+    assert!(weighted.iter().all_unique() && weighted.iter().all(|&x| x >= 10));
+    assert_eq!(
+        random::sample_weighted(0..20, 15, |_| f64::NAN, &mut rng()).len(),
+        0
+    );
+    assert_eq!(random::choose(0..0, &mut rng()), None::<u32>);
+
+    // Check that the selections are roughly uniform.
+    let mut rng = rng();
+    let mut counts = [0; 5];
+    for _ in 0..5000 {
+        counts[random::choose(0..5, &mut rng).unwrap()] += 1;
+    }
+    assert!(
+        counts.iter().all(|&c| (800..1200).contains(&c)),
+        "{:?}",
+        counts
+    );
+    let mut counts = HashMap::new();
+    for _ in 0..6000 {
+        *counts
+            .entry(random::random_combination(0..4, 2, &mut rng).unwrap())
+            .or_insert(0) += 1;
+    }
+    assert_eq!(counts.len(), 6);
+    assert!(
+        counts.values().all(|&c| (800..1200).contains(&c)),
+        "{:?}",
+        counts
+    );
+    let mut counts = [0; 3];
+    for _ in 0..6000 {
+        for x in random::sample_weighted(0..3, 1, |&x| f64::from(x + 1), &mut rng) {
+            counts[x as usize] += 1;
+        }
+    }
+    // The weights are 1, 2 and 3.
+    assert!((800..1200).contains(&counts[0]), "{:?}", counts);
+    assert!((2700..3300).contains(&counts[2]), "{:?}", counts);
+}
+
+#[test]
+fn diagonal_product() {
+    // Sort the items of the cartesian product by the sum of their positions, stably.
+    fn diagonal_order(lens: &[usize]) -> Vec<Vec<usize>> {
+        let mut expected = lens
+            .iter()
+            .map(|&len| 0..len)
+            .multi_cartesian_product()
+            .collect_vec();
+        expected.sort_by_key(|item| item.iter().sum::<usize>());
+        expected
+    }
+    for la in 0..5 {
+        for lb in 0..5 {
+            let expected = diagonal_order(&[la, lb])
+                .into_iter()
+                .map(|item| (item[0], item[1]))
+                .collect_vec();
+            let it = (0..la).diagonal_product(0..lb);
+            assert_eq!(it.size_hint(), (la * lb, Some(la * lb)));
+            it::assert_equal(it, expected);
+        }
+    }
+    for lens in [
+        vec![],
+        vec![0],
+        vec![3],
+        vec![2, 0, 4],
+        vec![2, 3, 4],
+        vec![1, 5, 1, 2],
+    ] {
+        let it = lens.iter().map(|&len| 0..len).multi_diagonal_product();
+        let len = lens.iter().product();
+        assert_eq!(it.size_hint(), (len, Some(len)));
+        it::assert_equal(it, diagonal_order(&lens));
+    }
+    // Every pair of `N x N` is reached in finite time.
+    let position = (0..).diagonal_product(0..).position(|pair| pair == (3, 5));
+    assert_eq!(position, Some(36 + 3));
+    let position = (0..4)
+        .map(|_| 0..)
+        .multi_diagonal_product()
+        .position(|item| item == [1, 0, 2, 0]);
+    assert!(position.is_some());
+    // The sources are buffered lazily and do not need to be `Clone`.
+    let mut pulled = 0;
+    let source = std::iter::from_fn(|| {
+        pulled += 1;
+        Some(pulled)
+    });
+    let it = source.diagonal_product(0..).take(6);
+    assert_eq!(it.count(), 6);
+    assert_eq!(pulled, 3);
+}
+
 #[test]
 fn diff_mismatch() {
     let a = [1, 2, 3, 4];